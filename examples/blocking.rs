@@ -0,0 +1,54 @@
+use std::time::Instant;
+
+use anyhow::Result;
+use clap::Parser;
+use rlt::{cli::BenchCli, BenchResult, Blocking, BlockingBenchSuite, IterInfo, IterReport, Status};
+
+#[derive(Parser, Clone)]
+pub struct Opts {
+    /// Number of rounds to hash per iteration.
+    #[clap(long, default_value = "10000")]
+    pub rounds: u64,
+
+    /// Embed BenchCli into this Opts.
+    #[command(flatten)]
+    pub bench_opts: BenchCli,
+}
+
+#[derive(Clone)]
+struct HashBench {
+    rounds: u64,
+}
+
+impl BlockingBenchSuite for HashBench {
+    type WorkerState = u64;
+
+    fn state(&self, worker_id: u32) -> Result<Self::WorkerState> {
+        Ok(worker_id.into())
+    }
+
+    fn bench(&mut self, seed: &mut Self::WorkerState, _: &IterInfo) -> BenchResult<IterReport> {
+        let t = Instant::now();
+
+        // CPU-bound work which would block the async runtime
+        let mut h = *seed;
+        for i in 0..self.rounds {
+            h = (h ^ i).wrapping_mul(0x100000001b3);
+        }
+        *seed = h;
+
+        Ok(IterReport {
+            duration: t.elapsed(),
+            status: Status::success(0),
+            items: self.rounds,
+            ..Default::default()
+        })
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let opts: Opts = Opts::parse();
+    let bench = HashBench { rounds: opts.rounds };
+    Ok(rlt::cli::run(opts.bench_opts, Blocking(bench)).await?)
+}
@@ -1,61 +1,59 @@
-use anyhow::Result;
-use async_trait::async_trait;
-use bytes::Bytes;
+use anyhow::{Context, Result};
 use clap::Parser;
-use http_body_util::{BodyExt, Full};
-use hyper::Uri;
-use hyper_tls::HttpsConnector;
-use hyper_util::{
-    client::legacy::{connect::HttpConnector, Client},
-    rt::TokioExecutor,
+use hyper::{
+    header::{HeaderName, HeaderValue},
+    Method, Uri,
 };
-use rlt::{
-    cli::BenchCli,
-    IterReport, {BenchSuite, IterInfo},
-};
-use tokio::time::Instant;
+use rlt::{cli::BenchCli, HttpBenchSuite, HttpRequest};
 
 #[derive(Parser, Clone)]
 pub struct Opts {
-    /// Target URL.
-    pub url: Uri,
+    /// Target requests, each as `[METHOD] URL [WEIGHT]`, e.g. "POST http://localhost:8080/items 3".
+    #[clap(required = true, value_parser = parse_request)]
+    pub requests: Vec<HttpRequest>,
+
+    /// Header of every request, e.g. "content-type: application/json".
+    #[clap(short = 'H', long = "header", value_parser = parse_header)]
+    pub headers: Vec<(HeaderName, HeaderValue)>,
+
+    /// Body of every request.
+    #[clap(long)]
+    pub body: Option<String>,
 
     /// Embed BenchCli into this Opts.
     #[command(flatten)]
     pub bench_opts: BenchCli,
 }
 
-#[derive(Clone)]
-struct HttpBench {
-    url: Uri,
+fn parse_request(s: &str) -> Result<HttpRequest> {
+    let parts = s.split_whitespace().collect::<Vec<_>>();
+    let (method, url, weight) = match parts[..] {
+        [url] => (Method::GET, url, 1),
+        [method, url] => (method.parse()?, url, 1),
+        [method, url, weight] => (method.parse()?, url, weight.parse()?),
+        _ => anyhow::bail!("expected `[METHOD] URL [WEIGHT]`"),
+    };
+    Ok(HttpRequest::new(method, url.parse::<Uri>()?).weight(weight))
 }
 
-#[async_trait]
-impl BenchSuite for HttpBench {
-    type WorkerState = Client<HttpsConnector<HttpConnector>, Full<Bytes>>;
-
-    async fn state(&self, _: u32) -> Result<Self::WorkerState> {
-        let https = HttpsConnector::new();
-        let client = Client::builder(TokioExecutor::new()).build(https);
-        Ok(client)
-    }
-
-    async fn bench(&mut self, client: &mut Self::WorkerState, _: &IterInfo) -> Result<IterReport> {
-        let t = Instant::now();
-        let mut resp = client.get(self.url.clone()).await?;
-        let status = resp.status().into();
-        let mut bytes = 0;
-        while let Some(next) = resp.frame().await {
-            bytes += next?.data_ref().map(Bytes::len).unwrap_or_default() as u64;
-        }
-        let duration = t.elapsed();
-        Ok(IterReport { duration, status, bytes, items: 1 })
-    }
+fn parse_header(s: &str) -> Result<(HeaderName, HeaderValue)> {
+    let (name, value) = s.split_once(':').context("expected `NAME: VALUE`")?;
+    Ok((name.trim().parse()?, value.trim().parse()?))
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let opts: Opts = Opts::parse();
-    let bench = HttpBench { url: opts.url };
-    rlt::cli::run(opts.bench_opts, bench).await
+    let requests = opts.requests.into_iter().map(|req| {
+        let req = opts
+            .headers
+            .iter()
+            .fold(req, |req, (name, value)| req.header(name.clone(), value.clone()));
+        match &opts.body {
+            Some(body) => req.body(body.clone()),
+            None => req,
+        }
+    });
+    let bench = HttpBenchSuite::new(requests)?;
+    Ok(rlt::cli::run(opts.bench_opts, bench).await?)
 }
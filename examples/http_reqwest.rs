@@ -2,14 +2,11 @@ use anyhow::Result;
 use async_trait::async_trait;
 use clap::Parser;
 use reqwest::{Client, Url};
-use rlt::{
-    cli::BenchCli,
-    IterReport, {BenchSuite, IterInfo},
-};
+use rlt::{cli::BenchCli, BenchResult, IterInfo, IterReport, Shared, SharedBenchSuite};
 use tokio::time::Instant;
 
 #[derive(Parser, Clone)]
-pub struct HttpBench {
+pub struct Opts {
     /// Target URL.
     pub url: Url,
 
@@ -18,26 +15,46 @@ pub struct HttpBench {
     pub bench_opts: BenchCli,
 }
 
+/// The workers share the suite, and with it the connection pool of a single client.
+struct HttpBench {
+    url: Url,
+    client: Client,
+}
+
 #[async_trait]
-impl BenchSuite for HttpBench {
-    type WorkerState = Client;
+impl SharedBenchSuite for HttpBench {
+    type WorkerState = ();
+
+    async fn preflight(&self) -> Result<()> {
+        let resp = self.client.get(self.url.clone()).send().await?;
+        let status = resp.status();
+        anyhow::ensure!(
+            !status.is_client_error() && !status.is_server_error(),
+            "{} returned {status}",
+            self.url
+        );
+        Ok(())
+    }
 
     async fn state(&self, _: u32) -> Result<Self::WorkerState> {
-        Ok(Client::new())
+        Ok(())
     }
 
-    async fn bench(&mut self, client: &mut Self::WorkerState, _: &IterInfo) -> Result<IterReport> {
+    async fn bench(&self, _: &mut Self::WorkerState, _: &IterInfo) -> BenchResult<IterReport> {
         let t = Instant::now();
-        let resp = client.get(self.url.clone()).send().await?;
+        let resp = self.client.get(self.url.clone()).send().await?;
+        let ttfb = t.elapsed();
         let status = resp.status().into();
-        let bytes = resp.bytes().await?.len() as u64;
+        let bytes_in = resp.bytes().await?.len() as u64;
         let duration = t.elapsed();
-        Ok(IterReport { duration, status, bytes, items: 1 })
+        let phases = vec![("ttfb".into(), ttfb), ("body".into(), duration - ttfb)];
+        Ok(IterReport { duration, status, bytes_in, phases, ..Default::default() })
     }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let bs = HttpBench::parse();
-    rlt::cli::run(bs.bench_opts, bs).await
+    let opts = Opts::parse();
+    let bench = HttpBench { url: opts.url, client: Client::new() };
+    Ok(rlt::cli::run(opts.bench_opts, Shared::new(bench)).await?)
 }
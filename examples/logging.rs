@@ -1,17 +1,15 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use clap::Parser;
-use rlt::{cli::BenchCli, IterInfo, IterReport, StatelessBenchSuite, Status, StatusKind};
+use rlt::{cli::BenchCli, BenchResult, IterInfo, IterReport, StatelessBenchSuite, Status, StatusKind};
 use tokio::time::{Duration, Instant};
 
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
-
 #[derive(Clone)]
 struct SimpleBench;
 
 #[async_trait]
 impl StatelessBenchSuite for SimpleBench {
-    async fn bench(&mut self, info: &IterInfo) -> Result<IterReport> {
+    async fn bench(&mut self, info: &IterInfo) -> BenchResult<IterReport> {
         let t = Instant::now();
 
         // simulate some work
@@ -27,31 +25,18 @@ impl StatelessBenchSuite for SimpleBench {
         };
 
         match status.kind() {
-            StatusKind::Success => tracing::info!(?status, seq),
+            StatusKind::Success | StatusKind::Informational | StatusKind::Redirect => tracing::info!(?status, seq),
             StatusKind::ClientError => tracing::warn!(?status, seq),
             StatusKind::ServerError | StatusKind::Error => tracing::error!(?status, seq),
         };
 
-        Ok(IterReport { duration, status, bytes: 0, items: 1 })
+        Ok(IterReport::new(duration, status))
     }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let opt = BenchCli::parse();
-    match opt.collector() {
-        rlt::cli::Collector::Tui => {
-            tracing_subscriber::registry()
-                .with(EnvFilter::from_default_env())
-                .with(rlt::tui_tracing_subscriber_layer())
-                .init();
-        }
-        rlt::cli::Collector::Silent => {
-            tracing_subscriber::fmt()
-                .with_env_filter(EnvFilter::from_default_env())
-                .init();
-        }
-    }
-
-    rlt::cli::run(opt, SimpleBench).await
+    rlt::init_tracing(opt.collector())?;
+    Ok(rlt::cli::run(opt, SimpleBench).await?)
 }
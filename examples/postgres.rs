@@ -1,7 +1,7 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use clap::Parser;
-use rlt::{cli::BenchCli, BenchSuite, IterInfo, IterReport, Status};
+use rlt::{cli::BenchCli, BenchResult, BenchSuite, IterInfo, IterReport, Status};
 use tokio::time::Instant;
 use tokio_postgres::{Client, NoTls};
 
@@ -70,12 +70,17 @@ impl BenchSuite for DBBench {
         Ok(())
     }
 
-    async fn bench(&mut self, client: &mut Self::WorkerState, _: &IterInfo) -> Result<IterReport> {
+    async fn bench(&mut self, client: &mut Self::WorkerState, info: &IterInfo) -> BenchResult<IterReport> {
+        // every worker inserts its own share of the keyspace
+        let batch = info.worker_seq * info.total_workers as u64 + info.worker_id as u64;
+        let start = (batch * self.batch_size as u64) as i64 + 1;
+        let end = start + self.batch_size as i64 - 1;
+
         let t = Instant::now();
         client
             .query(
-                "INSERT INTO t(name) SELECT MD5(i::TEXT) FROM generate_series(1, $1) i",
-                &[&(self.batch_size as i32)],
+                "INSERT INTO t(name) SELECT MD5(i::TEXT) FROM generate_series($1, $2) i",
+                &[&start, &end],
             )
             .await?;
         let duration = t.elapsed();
@@ -83,8 +88,8 @@ impl BenchSuite for DBBench {
         Ok(IterReport {
             duration,
             status: Status::success(0),
-            bytes: 0,
             items: self.batch_size as u64,
+            ..Default::default()
         })
     }
 
@@ -97,5 +102,5 @@ impl BenchSuite for DBBench {
 #[tokio::main]
 async fn main() -> Result<()> {
     let bs: DBBench = DBBench::parse();
-    rlt::cli::run(bs.bench_opts, bs).await
+    Ok(rlt::cli::run(bs.bench_opts.clone(), bs).await?)
 }
@@ -3,7 +3,7 @@ use async_trait::async_trait;
 use clap::Parser;
 use rlt::{
     cli::BenchCli,
-    IterReport, Status, {IterInfo, StatelessBenchSuite},
+    BenchResult, IterReport, Status, {IterInfo, StatelessBenchSuite},
 };
 use tokio::time::{Duration, Instant};
 
@@ -12,7 +12,7 @@ struct SimpleBench;
 
 #[async_trait]
 impl StatelessBenchSuite for SimpleBench {
-    async fn bench(&mut self, info: &IterInfo) -> Result<IterReport> {
+    async fn bench(&mut self, info: &IterInfo) -> BenchResult<IterReport> {
         let t = Instant::now();
 
         // simulate some work
@@ -29,11 +29,17 @@ impl StatelessBenchSuite for SimpleBench {
         // simulate items processed in current iteration
         let items = info.worker_seq % 100;
 
-        Ok(IterReport { duration, status, bytes: items * 1024, items })
+        Ok(IterReport {
+            duration,
+            status,
+            bytes: items * 1024,
+            items,
+            ..Default::default()
+        })
     }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    rlt::cli::run(BenchCli::parse(), SimpleBench).await
+    Ok(rlt::cli::run(BenchCli::parse(), SimpleBench).await?)
 }
@@ -0,0 +1,350 @@
+//! This module provides assertions on the final benchmark report, e.g. the service level objectives a CI job
+//! gates on.
+use std::{fmt, str::FromStr, sync::OnceLock, time::Duration};
+
+use anyhow::{anyhow, bail};
+use regex::Regex;
+
+use crate::{duration::DurationExt, report::BenchReport};
+
+/// An expectation on a metric of the benchmark report, e.g. `p99<250ms` or `success>=99.5%`.
+///
+/// An assertion is written as `<metric><comparator><value>`, where the comparator is one of `<`, `<=`, `>` and
+/// `>=`. The supported metrics are:
+///
+/// | Metric                                   | Value                            |
+/// |------------------------------------------|----------------------------------|
+/// | `p<N>`, e.g. `p50`, `p99`, `p99.9`       | Latency percentile, e.g. `250ms` |
+/// | `avg`, `min`, `max`, `stdev`             | Latency statistic, e.g. `1.5s`   |
+/// | `success`, `error`                       | Ratio, e.g. `99.5%` or `0.995`   |
+/// | `iters`, `errors`, `items`, `bytes`      | Total count                      |
+/// | `iters_rate`, `items_rate`, `bytes_rate` | Count per second                 |
+#[derive(Clone, Debug, PartialEq)]
+pub struct Assertion {
+    metric: Metric,
+    cmp: Comparator,
+    /// The threshold, in seconds for latencies and as a fraction for ratios.
+    value: f64,
+    /// The threshold as written.
+    raw: String,
+}
+
+/// A metric of the benchmark report which can be asserted on.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Metric {
+    Percentile(f64),
+    Avg,
+    Min,
+    Max,
+    Stdev,
+    Success,
+    Error,
+    Iters,
+    Errors,
+    Items,
+    Bytes,
+    ItersRate,
+    ItemsRate,
+    BytesRate,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Comparator {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// The outcome of an [`Assertion`] evaluated against a benchmark report.
+#[derive(Clone, Debug)]
+pub struct AssertionResult {
+    /// The evaluated assertion.
+    pub assertion: Assertion,
+    /// The actual value of the metric, in seconds for latencies and as a fraction for ratios.
+    pub actual: f64,
+    /// Whether the actual value satisfied the assertion.
+    pub passed: bool,
+}
+
+impl Assertion {
+    /// Evaluate the assertion against the given report.
+    pub fn eval(&self, report: &BenchReport) -> AssertionResult {
+        let actual = self.metric.value(report);
+        let passed = match self.cmp {
+            Comparator::Lt => actual < self.value,
+            Comparator::Le => actual <= self.value,
+            Comparator::Gt => actual > self.value,
+            Comparator::Ge => actual >= self.value,
+        };
+        AssertionResult { assertion: self.clone(), actual, passed }
+    }
+}
+
+impl AssertionResult {
+    /// The actual value of the metric formatted in its unit.
+    pub(crate) fn actual(&self) -> String {
+        let actual = self.actual;
+        match self.assertion.metric.unit() {
+            Unit::Duration => {
+                let d = Duration::from_secs_f64(actual.max(0.0));
+                let u = d.appropriate_unit();
+                format!("{:.2}{u}", d.as_f64(u))
+            }
+            Unit::Ratio => format!("{:.2}%", 100.0 * actual),
+            Unit::Number if actual.fract() == 0.0 => format!("{actual}"),
+            Unit::Number => format!("{actual:.2}"),
+        }
+    }
+}
+
+enum Unit {
+    Duration,
+    Ratio,
+    Number,
+}
+
+impl Metric {
+    fn unit(self) -> Unit {
+        match self {
+            Metric::Percentile(_) | Metric::Avg | Metric::Min | Metric::Max | Metric::Stdev => Unit::Duration,
+            Metric::Success | Metric::Error => Unit::Ratio,
+            _ => Unit::Number,
+        }
+    }
+
+    fn value(self, report: &BenchReport) -> f64 {
+        let counter = &report.stats.counter;
+        let elapsed = report.elapsed.as_secs_f64();
+        let rate = |n: u64| if elapsed > 0.0 { n as f64 / elapsed } else { 0.0 };
+        match self {
+            Metric::Percentile(p) => report.hist.percentile(p).as_secs_f64(),
+            Metric::Avg => report.hist.mean().as_secs_f64(),
+            Metric::Min => report.hist.min().as_secs_f64(),
+            Metric::Max => report.hist.max().as_secs_f64(),
+            Metric::Stdev => report.hist.stdev().as_secs_f64(),
+            Metric::Success => report.success_ratio(),
+            Metric::Error => report.error_ratio(),
+            Metric::Iters => counter.iters as f64,
+            Metric::Errors => report.stats.counter.errors as f64,
+            Metric::Items => counter.items as f64,
+            Metric::Bytes => counter.bytes as f64,
+            Metric::ItersRate => rate(counter.iters),
+            Metric::ItemsRate => rate(counter.items),
+            Metric::BytesRate => rate(counter.bytes),
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "avg" => Metric::Avg,
+            "min" => Metric::Min,
+            "max" => Metric::Max,
+            "stdev" => Metric::Stdev,
+            "success" => Metric::Success,
+            "error" => Metric::Error,
+            "iters" => Metric::Iters,
+            "errors" => Metric::Errors,
+            "items" => Metric::Items,
+            "bytes" => Metric::Bytes,
+            "iters_rate" => Metric::ItersRate,
+            "items_rate" => Metric::ItemsRate,
+            "bytes_rate" => Metric::BytesRate,
+            _ => match s.strip_prefix('p').and_then(|p| p.parse::<f64>().ok()) {
+                Some(p) if (0.0..=100.0).contains(&p) => Metric::Percentile(p),
+                _ => bail!("unknown metric: {s}"),
+            },
+        })
+    }
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Metric::Percentile(p) => write!(f, "p{p}"),
+            Metric::Avg => write!(f, "avg"),
+            Metric::Min => write!(f, "min"),
+            Metric::Max => write!(f, "max"),
+            Metric::Stdev => write!(f, "stdev"),
+            Metric::Success => write!(f, "success"),
+            Metric::Error => write!(f, "error"),
+            Metric::Iters => write!(f, "iters"),
+            Metric::Errors => write!(f, "errors"),
+            Metric::Items => write!(f, "items"),
+            Metric::Bytes => write!(f, "bytes"),
+            Metric::ItersRate => write!(f, "iters_rate"),
+            Metric::ItemsRate => write!(f, "items_rate"),
+            Metric::BytesRate => write!(f, "bytes_rate"),
+        }
+    }
+}
+
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Comparator::Lt => write!(f, "<"),
+            Comparator::Le => write!(f, "<="),
+            Comparator::Gt => write!(f, ">"),
+            Comparator::Ge => write!(f, ">="),
+        }
+    }
+}
+
+impl FromStr for Assertion {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        static EXPR: OnceLock<Regex> = OnceLock::new();
+        let caps = EXPR
+            .get_or_init(|| {
+                Regex::new(r"^\s*([a-z_0-9.]+)\s*(<=|>=|<|>)\s*(\S+)\s*$").expect("invalid assertion regex")
+            })
+            .captures(s)
+            .ok_or_else(|| anyhow!("invalid assertion `{s}`, expected e.g. p99<250ms"))?;
+
+        let metric: Metric = caps[1].parse()?;
+        let cmp = match &caps[2] {
+            "<" => Comparator::Lt,
+            "<=" => Comparator::Le,
+            ">" => Comparator::Gt,
+            _ => Comparator::Ge,
+        };
+        let raw = &caps[3];
+        let value = match metric.unit() {
+            Unit::Duration => humantime::parse_duration(raw)
+                .map_err(|e| anyhow!("invalid latency `{raw}` of {metric}: {e}"))?
+                .as_secs_f64(),
+            Unit::Ratio => match raw.strip_suffix('%') {
+                Some(percent) => percent.parse::<f64>().map(|p| p / 100.0),
+                None => raw.parse::<f64>(),
+            }
+            .map_err(|_| anyhow!("invalid ratio `{raw}` of {metric}, expected e.g. 99.5%"))?,
+            Unit::Number => raw
+                .parse::<f64>()
+                .map_err(|_| anyhow!("invalid number `{raw}` of {metric}"))?,
+        };
+        Ok(Assertion { metric, cmp, value, raw: raw.to_string() })
+    }
+}
+
+impl fmt::Display for Assertion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.metric, self.cmp, self.raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        collector::ReportAggregator,
+        report::{IterReport, IterResult},
+        runner::{BenchOpts, IterInfo},
+        status::Status,
+    };
+
+    fn parse(s: &str) -> Assertion {
+        s.parse().unwrap()
+    }
+
+    fn parse_err(s: &str) -> String {
+        s.parse::<Assertion>().unwrap_err().to_string()
+    }
+
+    #[test]
+    fn parses_assertions() {
+        let a = parse("p99<250ms");
+        assert_eq!(
+            (a.metric, a.cmp, a.value),
+            (Metric::Percentile(99.0), Comparator::Lt, 0.25)
+        );
+        let a = parse(" p99.9 <= 1s ");
+        assert_eq!(
+            (a.metric, a.cmp, a.value),
+            (Metric::Percentile(99.9), Comparator::Le, 1.0)
+        );
+        let a = parse("success>=99.5%");
+        assert_eq!((a.metric, a.cmp), (Metric::Success, Comparator::Ge));
+        assert!((a.value - 0.995).abs() < 1e-12);
+        let a = parse("error<0.01");
+        assert_eq!((a.metric, a.cmp, a.value), (Metric::Error, Comparator::Lt, 0.01));
+        let a = parse("iters_rate>1000");
+        assert_eq!((a.metric, a.cmp, a.value), (Metric::ItersRate, Comparator::Gt, 1000.0));
+    }
+
+    #[test]
+    fn displays_as_written() {
+        for s in [
+            "p99<250ms",
+            "p99.9<=1s",
+            "success>=99.5%",
+            "error<0.01",
+            "bytes_rate>1e6",
+        ] {
+            assert_eq!(parse(s).to_string(), s);
+        }
+        assert_eq!(parse(" avg <= 2ms ").to_string(), "avg<=2ms");
+    }
+
+    #[test]
+    fn rejects_invalid_assertions() {
+        assert!(parse_err("p99").contains("invalid assertion"));
+        assert!(parse_err("p99=250ms").contains("invalid assertion"));
+        assert!(parse_err("latency<1s").contains("unknown metric"));
+        assert!(parse_err("p101<1s").contains("unknown metric"));
+        assert!(parse_err("p99<fast").contains("invalid latency"));
+        assert!(parse_err("success>=most").contains("invalid ratio"));
+        assert!(parse_err("iters>many").contains("invalid number"));
+    }
+
+    /// A report of 100 iterations of 1ms to 100ms, of which the last `errors` failed.
+    fn report(errors: u64) -> BenchReport {
+        let mut agg = ReportAggregator::new(&BenchOpts::default());
+        for i in 0..100u64 {
+            let mut info = IterInfo::new(0, 1);
+            info.worker_seq = i;
+            let start = Duration::from_millis(10 * i);
+            let result = match i >= 100 - errors {
+                true => Err(anyhow!("connection reset").into()),
+                false => Ok(IterReport::new(Duration::from_millis(i + 1), Status::success(200))),
+            };
+            agg.ingest(IterResult { info, start, result }, start).unwrap();
+        }
+        agg.into_report(Duration::from_secs(1), 1)
+    }
+
+    fn passed(s: &str, report: &BenchReport) -> bool {
+        parse(s).eval(report).passed
+    }
+
+    #[test]
+    fn evaluates_against_the_report() {
+        let report = report(0);
+        assert!(passed("p50<=51ms", &report));
+        assert!(!passed("p50<49ms", &report));
+        assert!(passed("max>=99ms", &report));
+        assert!(passed("success>=99.5%", &report));
+        assert!(passed("iters>=100", &report) && !passed("iters>100", &report));
+        assert!(passed("iters_rate>=100", &report));
+
+        assert!(passed("min<2ms", &report));
+        assert_eq!(parse("iters>=100").eval(&report).actual(), "100");
+        assert_eq!(parse("avg<1s").eval(&report).actual(), "50.50ms");
+        assert_eq!(parse("success>=99.5%").eval(&report).actual(), "100.00%");
+    }
+
+    #[test]
+    fn failed_iterations_count_against_the_success_ratio() {
+        let report = report(90);
+        let success = parse("success>=99.5%").eval(&report);
+        assert!(!success.passed);
+        assert!((success.actual - 0.1).abs() < 1e-12);
+
+        assert!(passed("error>=90%", &report));
+        assert!(passed("errors>=90", &report) && !passed("errors>90", &report));
+    }
+}
@@ -0,0 +1,823 @@
+//! This module provides a builder to run benchmarks without the CLI.
+#[cfg(any(feature = "prometheus", feature = "control"))]
+use std::net::SocketAddr;
+#[cfg(feature = "rate_limit")]
+use std::num::NonZeroU32;
+use std::{
+    collections::BTreeMap,
+    io::stdout,
+    num::NonZeroU8,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use nonzero_ext::nonzero;
+use tokio::{sync::mpsc, time::Instant};
+use tokio_util::sync::CancellationToken;
+
+#[cfg(feature = "tracing")]
+use crate::collector::tui::{set_log_capture, LogFile};
+#[cfg(feature = "control")]
+use crate::control::ControlServer;
+#[cfg(feature = "prometheus")]
+use crate::prometheus::PrometheusServer;
+#[cfg(feature = "sysinfo")]
+use crate::resource::ResourceSampler;
+use crate::{
+    assertion::Assertion,
+    cli::Collector,
+    clock::Clock,
+    collector::{
+        CompositeCollector, HdrLogSink, JsonLinesSink, ProgressCollector, ReportCollector, ReportSink, SilentCollector,
+        TuiCollector,
+    },
+    error::Error,
+    partial_report::PartialReport,
+    pause::PauseControl,
+    report::{BenchReport, RunMetadata},
+    reporter::{BenchReporter, TextReporter},
+    runner::{BenchOpts, BenchPhase, BenchSuite, Bound, ErrorOpts, Runner, StableOpts},
+    status_file::StatusFile,
+    util::{write_atomic, ByteUnit},
+};
+
+/// Refresh rate of the progress collector, and of the TUI before it adapts the rate to the cost of drawing.
+const DEFAULT_FPS: NonZeroU8 = nonzero!(32u8);
+
+/// A builder which configures and runs a benchmark, e.g. from a configuration service instead of the command
+/// line.
+///
+/// [`cli::run`](crate::cli::run) runs through the same code path, see the conversion from
+/// [`BenchCli`](crate::cli::BenchCli).
+///
+/// # Examples
+///
+/// ```no_run
+/// # use rlt::{BenchBuilder, IterInfo, IterReport, StatelessBenchSuite, Status};
+/// # use std::time::Duration;
+/// # #[derive(Clone)]
+/// # struct SimpleBench;
+/// # #[async_trait::async_trait]
+/// # impl StatelessBenchSuite for SimpleBench {
+/// #     async fn bench(&mut self, _: &IterInfo) -> rlt::BenchResult<IterReport> { unimplemented!() }
+/// # }
+/// # async fn run() -> anyhow::Result<()> {
+/// let report = BenchBuilder::new()
+///     .concurrency(8)
+///     .duration(Duration::from_secs(10))
+///     .run(SimpleBench)
+///     .await?;
+/// println!("{:.2}% success", 100.0 * report.success_ratio());
+/// # Ok(())
+/// # }
+/// ```
+pub struct BenchBuilder {
+    /// The options of the runner. The clock is replaced when the benchmark starts.
+    opts: BenchOpts,
+    collector: Collector,
+    /// The refresh rate, or `None` to adapt the one of the TUI to the cost of drawing.
+    fps: Option<NonZeroU8>,
+    auto_quit: bool,
+    byte_unit: ByteUnit,
+    ascii: bool,
+    reporter: Option<Arc<dyn BenchReporter + Send + Sync>>,
+    print_report: bool,
+    report_file: Option<PathBuf>,
+    report_interval: Option<Duration>,
+    preflight: bool,
+    log_phases: bool,
+    caption: Option<String>,
+    tui_tty: Option<PathBuf>,
+    labels: BTreeMap<String, String>,
+    stream_file: Option<PathBuf>,
+    stream_rotation: Option<(u64, usize)>,
+    hdr_interval_log: Option<PathBuf>,
+    status_file: Option<PathBuf>,
+    assertions: Vec<Assertion>,
+    #[cfg(feature = "tracing")]
+    log_file: Option<(PathBuf, bool)>,
+    #[cfg(feature = "tracing")]
+    log_buffer: Option<usize>,
+    #[cfg(feature = "tracing")]
+    log_level: Option<log::LevelFilter>,
+    #[cfg(feature = "prometheus")]
+    prometheus_addr: Option<SocketAddr>,
+    #[cfg(feature = "control")]
+    control_addr: Option<SocketAddr>,
+}
+
+impl Default for BenchBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BenchBuilder {
+    /// Create a builder of a benchmark with a single worker, which runs until it is interrupted and does not
+    /// print anything.
+    pub fn new() -> Self {
+        Self {
+            opts: BenchOpts::default(),
+            collector: Collector::Silent,
+            fps: None,
+            auto_quit: true,
+            byte_unit: ByteUnit::default(),
+            ascii: false,
+            reporter: None,
+            print_report: true,
+            report_file: None,
+            report_interval: None,
+            preflight: true,
+            log_phases: false,
+            caption: None,
+            tui_tty: None,
+            labels: BTreeMap::new(),
+            stream_file: None,
+            stream_rotation: None,
+            hdr_interval_log: None,
+            status_file: None,
+            assertions: Vec::new(),
+            #[cfg(feature = "tracing")]
+            log_file: None,
+            #[cfg(feature = "tracing")]
+            log_buffer: None,
+            #[cfg(feature = "tracing")]
+            log_level: None,
+            #[cfg(feature = "prometheus")]
+            prometheus_addr: None,
+            #[cfg(feature = "control")]
+            control_addr: None,
+        }
+    }
+
+    /// Number of workers to run concurrently.
+    pub fn concurrency(mut self, concurrency: u32) -> Self {
+        self.opts.concurrency = concurrency;
+        self
+    }
+
+    /// Stop the benchmark after the given number of iterations.
+    pub fn iterations(mut self, iterations: u64) -> Self {
+        self.opts.iterations = Some(iterations);
+        self
+    }
+
+    /// Stop the benchmark after the given duration.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.opts.duration = Some(duration);
+        self
+    }
+
+    /// Limit the rate of the benchmark, in iterations per second.
+    #[cfg(feature = "rate_limit")]
+    pub fn rate(mut self, rate: NonZeroU32) -> Self {
+        self.opts.rate = Some(rate);
+        self
+    }
+
+    /// How errors are aggregated into the error distribution.
+    pub fn errors(mut self, errors: ErrorOpts) -> Self {
+        self.opts.errors = errors;
+        self
+    }
+
+    /// Maximum number of distinct statuses, see [`BenchOpts::status_max_kinds`].
+    pub fn status_max_kinds(mut self, n: usize) -> Self {
+        self.opts.status_max_kinds = n;
+        self
+    }
+
+    /// Keep per-second samples of the run in the report.
+    pub fn record_timeseries(mut self, record: bool) -> Self {
+        self.opts.record_timeseries = record;
+        self
+    }
+
+    /// Number of the slowest iterations to keep in the report.
+    pub fn slowest(mut self, n: usize) -> Self {
+        self.opts.slowest = n;
+        self
+    }
+
+    /// Count redirects as successful iterations.
+    pub fn redirect_is_success(mut self, redirect_is_success: bool) -> Self {
+        self.opts.redirect_is_success = redirect_is_success;
+        self
+    }
+
+    /// Also track the latency per item of the iterations, for workloads of variable batch sizes.
+    pub fn per_item_latency(mut self, per_item_latency: bool) -> Self {
+        self.opts.per_item_latency = per_item_latency;
+        self
+    }
+
+    /// Also track the latency of each worker, to tell whether some of them are slower than the others.
+    pub fn per_worker_latency(mut self, per_worker_latency: bool) -> Self {
+        self.opts.per_worker_latency = per_worker_latency;
+        self
+    }
+
+    /// Correct the latency histogram for coordinated omission, given the expected interval between iterations,
+    /// e.g. the inverse of the rate limit.
+    pub fn expected_interval(mut self, interval: Duration) -> Self {
+        self.opts.expected_interval = Some(interval);
+        self
+    }
+
+    /// Leave out the iterations of the first part of the run from the report, e.g. to skip the warmup of the
+    /// target without a separate warmup phase.
+    pub fn discard_first(mut self, duration: Duration) -> Self {
+        self.opts.discard_first = Some(duration);
+        self
+    }
+
+    /// Count the latencies above the given one as suspect, see [`BenchOpts::suspect_ceiling`].
+    pub fn suspect_ceiling(mut self, ceiling: Duration) -> Self {
+        self.opts.suspect_ceiling = ceiling;
+        self
+    }
+
+    /// Leave the suspect latencies out of the latency histograms, e.g. those of a bench suite with a
+    /// non-monotonic time source.
+    pub fn exclude_suspect(mut self, exclude: bool) -> Self {
+        self.opts.exclude_suspect = exclude;
+        self
+    }
+
+    /// Delay the first iteration of each worker by a random duration below the given one, to spread the first
+    /// requests of the workers.
+    pub fn start_jitter(mut self, jitter: Duration) -> Self {
+        self.opts.start_jitter = Some(jitter);
+        self
+    }
+
+    /// Seed of the random choices of the run, e.g. the start jitter, to reproduce them.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.opts.seed = Some(seed);
+        self
+    }
+
+    /// Stop the benchmark once it reaches a steady state, see [`StableOpts`].
+    pub fn until_stable(mut self, opts: StableOpts) -> Self {
+        self.opts.until_stable = Some(opts);
+        self
+    }
+
+    /// The collector which shows the progress of the benchmark.
+    pub fn collector(mut self, collector: Collector) -> Self {
+        self.collector = collector;
+        self
+    }
+
+    /// Refresh rate of the TUI and progress collectors, in frames per second.
+    ///
+    /// If not set, the progress collector refreshes 32 times per second and the TUI starts at the same rate, but
+    /// backs off down to 2 fps while drawing takes a noticeable share of the frames.
+    pub fn fps(mut self, fps: NonZeroU8) -> Self {
+        self.fps = Some(fps);
+        self
+    }
+
+    /// Quit the TUI when the benchmark finishes, instead of waiting for the user.
+    pub fn auto_quit(mut self, auto_quit: bool) -> Self {
+        self.auto_quit = auto_quit;
+        self
+    }
+
+    /// The unit convention used by the TUI to display byte sizes.
+    pub fn byte_unit(mut self, byte_unit: ByteUnit) -> Self {
+        self.byte_unit = byte_unit;
+        self
+    }
+
+    /// Draw the TUI with ASCII characters only.
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Print the final report to stdout, or the [`report_file`](Self::report_file), with the given reporter,
+    /// which also writes the TUI snapshots.
+    pub fn reporter(mut self, reporter: Arc<dyn BenchReporter + Send + Sync>) -> Self {
+        self.reporter = Some(reporter);
+        self
+    }
+
+    /// Whether to print the final report with the [`reporter`](Self::reporter), which is the default.
+    pub fn print_report(mut self, print: bool) -> Self {
+        self.print_report = print;
+        self
+    }
+
+    /// Write the final report to a file instead of stdout, replacing the file atomically.
+    pub fn report_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.report_file = Some(path.into());
+        self
+    }
+
+    /// Rewrite the [`report_file`](Self::report_file) with the report of the run so far every interval, until
+    /// the final report replaces it.
+    ///
+    /// The reports are printed with [`BenchReporter::print_partial`], which marks the JSON report with
+    /// `"partial": true`. The run fails with [`Error::InvalidOptions`] without a report file and a
+    /// [`reporter`](Self::reporter).
+    pub fn report_interval(mut self, interval: Duration) -> Self {
+        self.report_interval = Some(interval);
+        self
+    }
+
+    /// Whether to run the [preflight check](BenchSuite::preflight) of the bench suite before the benchmark,
+    /// which is the default.
+    pub fn preflight(mut self, preflight: bool) -> Self {
+        self.preflight = preflight;
+        self
+    }
+
+    /// Print a line to stderr when the setup of the workers completes, the benchmark starts and finishes, so
+    /// that a run without the TUI does not look stuck. Disabled by default.
+    pub fn log_phases(mut self, log: bool) -> Self {
+        self.log_phases = log;
+        self
+    }
+
+    /// Caption shown in the TUI, e.g. the current step of a series of runs.
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    /// Draw the TUI on the terminal device at the given path, e.g. `/dev/pts/7`, and read its keys from there,
+    /// leaving stdout to the report.
+    pub fn tui_tty(mut self, path: impl Into<PathBuf>) -> Self {
+        self.tui_tty = Some(path.into());
+        self
+    }
+
+    /// User labels of the run, attached to the report metadata.
+    pub fn labels(mut self, labels: BTreeMap<String, String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Stream every iteration result to a file as JSON lines.
+    pub fn stream_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.stream_file = Some(path.into());
+        self
+    }
+
+    /// Rotate the stream file once it grows beyond `max_size` bytes, keeping up to `keep` rotated segments.
+    ///
+    /// See [`JsonLinesSink::rotate`] for the layout of the segments.
+    pub fn stream_rotation(mut self, max_size: u64, keep: usize) -> Self {
+        self.stream_rotation = Some((max_size, keep));
+        self
+    }
+
+    /// Write the latencies of every second to a file in the HdrHistogram interval log format, see [`HdrLogSink`].
+    pub fn hdr_interval_log(mut self, path: impl Into<PathBuf>) -> Self {
+        self.hdr_interval_log = Some(path.into());
+        self
+    }
+
+    /// Rewrite a small JSON file with the state of the run every second, and once more when it finished.
+    pub fn status_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.status_file = Some(path.into());
+        self
+    }
+
+    /// Assertions to evaluate against the final report, whose outcomes are included in the report.
+    pub fn assertions(mut self, assertions: Vec<Assertion>) -> Self {
+        self.assertions = assertions;
+        self
+    }
+
+    /// Write the captured logs to a file as well, appending to it unless `truncate` is set.
+    #[cfg(feature = "tracing")]
+    pub fn log_file(mut self, path: impl Into<PathBuf>, truncate: bool) -> Self {
+        self.log_file = Some((path.into(), truncate));
+        self
+    }
+
+    /// Keep the latest `lines` captured log lines, see [`TuiTracingLayer::with_buffer`].
+    ///
+    /// [`TuiTracingLayer::with_buffer`]: crate::TuiTracingLayer::with_buffer
+    #[cfg(feature = "tracing")]
+    pub fn log_buffer(mut self, lines: usize) -> Self {
+        self.log_buffer = Some(lines);
+        self
+    }
+
+    /// Capture the log lines of the given level and above only.
+    #[cfg(feature = "tracing")]
+    pub fn log_level(mut self, level: log::LevelFilter) -> Self {
+        self.log_level = Some(level);
+        self
+    }
+
+    /// Expose live metrics in the Prometheus exposition format on the given address.
+    #[cfg(feature = "prometheus")]
+    pub fn prometheus_addr(mut self, addr: SocketAddr) -> Self {
+        self.prometheus_addr = Some(addr);
+        self
+    }
+
+    /// Serve the HTTP control API on the given address.
+    #[cfg(feature = "control")]
+    pub fn control_addr(mut self, addr: SocketAddr) -> Self {
+        self.control_addr = Some(addr);
+        self
+    }
+
+    /// Run the benchmark with the given suite and return its report.
+    ///
+    /// Fails with [`Error::InvalidOptions`] if the options conflict, and with [`Error::Preflight`] if the
+    /// preflight check fails, before anything is started. The assertions are evaluated into the report, but
+    /// failing ones do not fail the run; see [`cli::run`] for that.
+    ///
+    /// [`cli::run`]: crate::cli::run
+    pub async fn run<BS>(self, bench_suite: BS) -> Result<BenchReport, Error>
+    where
+        BS: BenchSuite + Send + Sync + 'static,
+        BS::WorkerState: Send + Sync + 'static,
+    {
+        if let (Some(discard), Some(duration)) = (self.opts.discard_first, self.opts.duration) {
+            if discard >= duration {
+                return Err(Error::InvalidOptions(format!(
+                    "discarding the first {} leaves nothing of the {} benchmark to report",
+                    humantime::format_duration(discard),
+                    humantime::format_duration(duration)
+                )));
+            }
+        }
+        if self.report_interval.is_some() && (self.report_file.is_none() || self.reporter.is_none()) {
+            return Err(Error::InvalidOptions(
+                "the report interval needs a report file and a reporter".into(),
+            ));
+        }
+        if self.preflight {
+            bench_suite.preflight().await.map_err(Error::Preflight)?;
+        }
+
+        let (res_tx, res_rx) = mpsc::unbounded_channel();
+        let cancel = CancellationToken::new();
+
+        let mut opts = self.opts;
+        opts.clock = Clock::start_at(Instant::now());
+        opts.errors.classifier = opts.errors.classifier.or_else(|| bench_suite.error_classifier());
+        let pause = Arc::new(PauseControl::new(opts.clock.clone()));
+        let runner = Runner::new(bench_suite, opts.clone(), res_tx, pause.clone(), cancel.clone());
+        let in_flight = runner.in_flight();
+        let started_at = runner.started_at();
+
+        #[cfg(feature = "sysinfo")]
+        let sampler = ResourceSampler::start();
+
+        #[cfg(feature = "tracing")]
+        set_log_capture(self.log_buffer, self.log_level);
+        #[cfg(feature = "tracing")]
+        let _log_file = match &self.log_file {
+            Some((path, truncate)) => Some(LogFile::open(path, *truncate).map_err(Error::Io)?),
+            None => None,
+        };
+
+        let mut sinks: Vec<Box<dyn ReportSink>> = Vec::new();
+        if let Some(path) = &self.stream_file {
+            let sink = JsonLinesSink::create(path, opts.clock.clone()).map_err(Error::Io)?;
+            let sink = match self.stream_rotation {
+                Some((max_size, keep)) => sink.rotate(max_size, keep).map_err(Error::Io)?,
+                None => sink,
+            };
+            sinks.push(Box::new(sink));
+        }
+        if let Some(path) = &self.hdr_interval_log {
+            sinks.push(Box::new(HdrLogSink::create(path, &opts).map_err(Error::Io)?));
+        }
+
+        let status_file = match &self.status_file {
+            Some(path) => {
+                let (file, sink) = StatusFile::start(
+                    path,
+                    opts.clock.clone(),
+                    pause.clone(),
+                    cancel.clone(),
+                    opts.redirect_is_success,
+                    in_flight.clone(),
+                )
+                .map_err(Error::Io)?;
+                sinks.push(Box::new(sink));
+                Some(file)
+            }
+            None => None,
+        };
+
+        #[cfg(feature = "prometheus")]
+        let prometheus = match self.prometheus_addr {
+            Some(addr) => {
+                let (server, sink) = PrometheusServer::start(addr, in_flight.clone(), pause.subscribe())
+                    .await
+                    .map_err(Error::Io)?;
+                sinks.push(Box::new(sink));
+                Some(server)
+            }
+            None => None,
+        };
+
+        #[cfg(feature = "control")]
+        let control = match self.control_addr {
+            Some(addr) => {
+                let (server, sink) = ControlServer::start(
+                    addr,
+                    opts.clock.clone(),
+                    pause.clone(),
+                    cancel.clone(),
+                    in_flight.clone(),
+                )
+                .await
+                .map_err(Error::Io)?;
+                sinks.push(Box::new(sink));
+                Some(server)
+            }
+            None => None,
+        };
+
+        let (clock, bench_cancel) = (opts.clock.clone(), cancel.clone());
+        let bounds = (opts.duration, opts.iterations);
+        let workers = opts.concurrency;
+        let setup = runner.setup();
+
+        let metadata = RunMetadata {
+            command: std::env::args().collect(),
+            iterations: opts.iterations,
+            duration: opts.duration,
+            #[cfg(feature = "rate_limit")]
+            rate: opts.rate.map(|r| r.get()),
+            expected_interval: opts.expected_interval,
+            labels: self.labels,
+            ..Default::default()
+        };
+        let reporter = self.reporter.clone();
+        let partial_report = match (&self.report_file, self.report_interval, &reporter) {
+            (Some(path), Some(interval), Some(reporter)) => {
+                let (partial, sink) = PartialReport::start(
+                    path,
+                    interval,
+                    reporter.clone(),
+                    &opts,
+                    pause.clone(),
+                    metadata.clone(),
+                    started_at.clone(),
+                );
+                sinks.push(Box::new(sink));
+                Some(partial)
+            }
+            _ => None,
+        };
+        let snapshot_reporter = reporter
+            .clone()
+            .unwrap_or_else(|| Arc::new(TextReporter::new(self.byte_unit)));
+        let primary = |res_rx| -> anyhow::Result<Box<dyn ReportCollector>> {
+            Ok(match self.collector {
+                Collector::Tui => {
+                    let mut tui = TuiCollector::new(
+                        opts,
+                        self.fps.unwrap_or(DEFAULT_FPS),
+                        res_rx,
+                        pause.clone(),
+                        cancel,
+                        self.auto_quit,
+                        self.byte_unit,
+                        self.ascii,
+                        snapshot_reporter,
+                    )?;
+                    tui.caption = self.caption;
+                    tui.tty = self.tui_tty;
+                    tui.setup = Some(setup.clone());
+                    tui.in_flight = Some(in_flight.clone());
+                    tui.adaptive_fps = self.fps.is_none();
+                    #[cfg(feature = "sysinfo")]
+                    {
+                        tui.resources = Some(sampler.latest());
+                    }
+                    Box::new(tui)
+                }
+                Collector::Silent => Box::new(SilentCollector::new(opts, res_rx, cancel)),
+                Collector::Progress => Box::new(ProgressCollector::new(
+                    opts,
+                    self.fps.unwrap_or(DEFAULT_FPS),
+                    res_rx,
+                    cancel,
+                )),
+            })
+        };
+        let mut collector = match sinks.is_empty() {
+            true => primary(res_rx).map_err(Error::Collector)?,
+            false => Box::new(CompositeCollector::new(res_rx, sinks, primary).map_err(Error::Collector)?),
+        };
+
+        let report = tokio::spawn(async move { collector.run().await });
+
+        let phases = self.log_phases.then(|| {
+            let mut phase = runner.phase();
+            let setup = setup.clone();
+            tokio::spawn(async move {
+                let started = Instant::now();
+                if phase.wait_for(|p| *p == BenchPhase::Running).await.is_ok() {
+                    let (total, slowest) = (started.elapsed().as_secs_f64(), setup.borrow().max.as_secs_f64());
+                    let s = if workers == 1 { "" } else { "s" };
+                    eprintln!("setup complete ({workers} worker{s}, {total:.1}s, slowest {slowest:.1}s)");
+                    eprintln!("benchmark started");
+                }
+            })
+        });
+        let mut partial_report = partial_report;
+        let outcome = async {
+            let run = runner.run().await;
+            if let Some(phases) = phases {
+                // the watcher ends at the latest once the runner is dropped
+                let _ = phases.await;
+            }
+            if let Err(e) = run {
+                // stop the collector before the servers below, so that none of them outlives the run
+                bench_cancel.cancel();
+                let _ = report.await;
+                #[cfg(feature = "sysinfo")]
+                sampler.shutdown().await;
+                return Err(Error::Suite(e));
+            }
+            if self.log_phases {
+                eprintln!("benchmark finished ({:.1}s)", clock.elapsed().as_secs_f64());
+            }
+            // the runner cancels the benchmark once the duration is reached, and otherwise only stops by itself
+            // once the iterations are done
+            let finished_by = match bounds {
+                (Some(duration), _) if clock.elapsed() >= duration => Some(Bound::Duration),
+                (_, Some(_)) if !bench_cancel.is_cancelled() => Some(Bound::Iterations),
+                _ => None,
+            };
+
+            let mut report = report
+                .await
+                .map_err(|e| Error::Collector(e.into()))?
+                .map_err(Error::Collector)?;
+            report.paused_duration = pause.paused_duration();
+            report.pause_count = pause.pause_count();
+            report.metadata = RunMetadata {
+                started_at: started_at.get().copied(),
+                finished_at: Some(SystemTime::now()),
+                pauses: pause.pauses(),
+                finished_by,
+                cancelled_by: report.metadata.cancelled_by,
+                ..metadata
+            };
+            report.setup = Some(*setup.borrow()).filter(|s| s.workers > 0);
+            #[cfg(feature = "control")]
+            if control.as_ref().is_some_and(|c| c.stopped()) {
+                report.metadata.cancelled_by = Some(crate::CancelReason::Control);
+            }
+            #[cfg(feature = "sysinfo")]
+            {
+                report.resource_usage = sampler.shutdown().await;
+            }
+            report.assertions = self.assertions.iter().map(|a| a.eval(&report)).collect();
+            // stop the partial reports before the final one replaces them
+            if let Some(partial_report) = partial_report.take() {
+                partial_report.shutdown().await.map_err(Error::Io)?;
+            }
+            if let Some(reporter) = reporter.as_ref().filter(|_| self.print_report) {
+                match &self.report_file {
+                    Some(path) => write_atomic(path, |w| reporter.print(w, &report)),
+                    None => reporter.print(&mut stdout(), &report),
+                }
+                .map_err(Error::Io)?;
+            }
+            Ok(report)
+        }
+        .await;
+
+        // stop the tasks and servers also when the run failed, in which case its error wins over theirs
+        let mut shutdown = Ok(());
+        if let Some(partial_report) = partial_report {
+            shutdown = shutdown.and(partial_report.shutdown().await);
+        }
+        #[cfg(feature = "prometheus")]
+        if let Some(prometheus) = prometheus {
+            shutdown = shutdown.and(prometheus.shutdown().await);
+        }
+        #[cfg(feature = "control")]
+        if let Some(control) = control {
+            shutdown = shutdown.and(control.shutdown().await);
+        }
+        if let Some(status_file) = status_file {
+            shutdown = shutdown.and(status_file.shutdown().await);
+        }
+
+        let report = outcome?;
+        shutdown.map_err(Error::Io)?;
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::{
+        report::{BenchResult, IterReport},
+        runner::{IterInfo, StatelessBenchSuite},
+    };
+
+    /// A suite which counts its preflight checks, failing them if `fail` is set.
+    #[derive(Clone, Default)]
+    struct Preflight {
+        checks: Arc<AtomicU32>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl StatelessBenchSuite for Preflight {
+        async fn preflight(&self) -> anyhow::Result<()> {
+            self.checks.fetch_add(1, Ordering::Relaxed);
+            anyhow::ensure!(!self.fail, "target is down");
+            Ok(())
+        }
+
+        async fn bench(&mut self, _: &IterInfo) -> BenchResult<IterReport> {
+            Ok(IterReport::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn runs_the_preflight_check() {
+        let suite = Preflight::default();
+        let report = BenchBuilder::new().iterations(3).run(suite.clone()).await.unwrap();
+        assert_eq!(report.stats.counter.iters, 3);
+        assert_eq!(suite.checks.load(Ordering::Relaxed), 1);
+
+        let suite = Preflight { fail: true, ..Default::default() };
+        let res = BenchBuilder::new().iterations(3).run(suite.clone()).await;
+        assert!(matches!(res, Err(Error::Preflight(_))));
+
+        let report = BenchBuilder::new()
+            .iterations(3)
+            .preflight(false)
+            .run(suite.clone())
+            .await
+            .unwrap();
+        assert_eq!(report.stats.counter.iters, 3);
+        assert_eq!(suite.checks.load(Ordering::Relaxed), 1);
+    }
+
+    /// A suite whose workers fail to create their states.
+    #[derive(Clone)]
+    struct NoState;
+
+    #[async_trait]
+    impl BenchSuite for NoState {
+        type WorkerState = ();
+
+        async fn state(&self, _: u32) -> anyhow::Result<()> {
+            anyhow::bail!("no connection")
+        }
+
+        async fn bench(&mut self, _: &mut (), _: &IterInfo) -> BenchResult<IterReport> {
+            unreachable!()
+        }
+    }
+
+    #[tokio::test]
+    async fn stops_the_status_file_when_the_suite_fails() {
+        let path = std::env::temp_dir().join(format!("rlt-status-{}.json", std::process::id()));
+        let res = BenchBuilder::new().concurrency(2).status_file(&path).run(NoState).await;
+        assert!(matches!(res, Err(Error::Suite(_))));
+
+        let status: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(status["state"], "finished");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn rejects_conflicting_options() {
+        let suite = Preflight::default();
+        let builder = || BenchBuilder::new().iterations(1);
+
+        let res = builder()
+            .duration(Duration::from_secs(10))
+            .discard_first(Duration::from_secs(10))
+            .run(suite.clone())
+            .await;
+        assert!(matches!(res, Err(Error::InvalidOptions(_))));
+
+        let res = builder()
+            .report_interval(Duration::from_secs(1))
+            .run(suite.clone())
+            .await;
+        assert!(matches!(res, Err(Error::InvalidOptions(_))));
+
+        // nothing starts before the options are checked
+        assert_eq!(suite.checks.load(Ordering::Relaxed), 0);
+    }
+}
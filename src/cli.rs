@@ -46,29 +46,39 @@
 //!
 //!           When set, benchmark stops after reaching the duration.
 //!
-//!           Examples: -z 10s, -z 5m, -z 1h
+//!           A bare number is taken as seconds. Examples: -d 30, -d 10s, -d 5m, -d 1h
 //!
 //!   -r, --rate <RATE>
 //!           Rate limit for benchmarking, in iterations per second (ips)
 //!
 //!           When set, benchmark will try to run at the specified rate.
 //!
-//!   -q, --quiet
+//!   -q, --quiet...
 //!           Run benchmark in quiet mode
 //!
-//!           Implies --collector silent.
+//!           Implies --collector silent. The silent collector prints a line to stderr when the setup completes,
+//!           the benchmark starts and finishes; pass -qq to hide them as well.
 //!
 //!       --collector <COLLECTOR>
 //!           Collector for the benchmark
 //!
 //!           Possible values:
-//!           - tui:    TUI based collector
-//!           - silent: Collector that does not print anything
+//!           - tui:      TUI based collector
+//!           - silent:   Collector that does not print anything
+//!           - progress: Collector that prints a single-line progress indicator to stderr
 //!
 //!       --fps <FPS>
-//!           Refresh rate for the tui collector, in frames per second (fps)
+//!           Refresh rate for the tui and progress collectors, in frames per second (fps)
 //!
-//!           [default: 32]
+//!           If not set, the progress collector refreshes at 32 fps and the tui adapts its refresh rate to the cost
+//!           of drawing, between 2 and 32 fps.
+//!
+//!       --tui-tty <PATH>
+//!           Draw the TUI on another terminal, e.g. /dev/pts/7, and read the keys from there
+//!
+//!           Keeps stdout clean for the report, e.g. when it is piped. Find the path with `tty` on the other
+//!           terminal, and leave it idle, e.g. with `sleep infinity`, so its shell does not compete for the keys.
+//!           Implies the TUI collector unless --collector is given. Only supported on unix.
 //!
 //!   -o, --output <OUTPUT>
 //!           Output format for the report
@@ -76,16 +86,273 @@
 //!           [default: text]
 //!
 //!           Possible values:
-//!           - text: Report in plain text format
-//!           - json: Report in JSON format
+//!           - text:     Report in plain text format
+//!           - json:     Report in JSON format
+//!           - csv:      Report in CSV format, one row per run
+//!           - markdown: Report in GitHub-flavored Markdown
+//!           - prom:     Report in the Prometheus text exposition format
+//!
+//!       --json-compact
+//!           Print the JSON report on a single line
+//!
+//!           Useful for appending one report per run to a JSON Lines file.
+//!
+//!       --report-file <PATH>
+//!           Write the report to a file instead of stdout
+//!
+//!           The file is replaced atomically once the report is complete.
+//!
+//!       --report-interval <DURATION>
+//!           Rewrite the report file with the report of the run so far every interval, e.g. 5s
+//!
+//!           Only works with `--output json`. The partial reports have the schema of the final report plus
+//!           `"partial": true`, and the final report replaces them at the end.
+//!
+//!       --label <KEY=VALUE>
+//!           Label the run with a key-value pair, can be repeated
+//!
+//!           Labels are added as extra columns to the CSV report and to every sample of the Prometheus report.
+//!
+//!           Examples: --label env=staging --label commit=abc123
+//!
+//!       --prom-prefix <PREFIX>
+//!           Prefix of the metric names in the Prometheus report
+//!
+//!           [default: rlt]
+//!
+//!       --byte-units <BYTE_UNITS>
+//!           Unit convention for displaying byte sizes
+//!
+//!           [default: binary]
+//!
+//!           Possible values:
+//!           - binary:  Powers of 1024, e.g. KiB, MiB, GiB
+//!           - decimal: Powers of 1000, e.g. KB, MB, GB
+//!
+//!       --export-hdr <PATH>
+//!           Export the latency histogram to a file in the HdrHistogram interval log format
+//!
+//!       --hdr-interval-log <PATH>
+//!           Write the latencies of every second to a file in the HdrHistogram interval log format
+//!
+//!           Pauses show up as gaps between the timestamps of the intervals. Readable by the HdrHistogram
+//!           tooling, e.g. HistogramLogProcessor. Latencies are in nanoseconds.
+//!
+//!       --embed-hdr
+//!           Embed the latency histogram as base64 under `latency.hdr` in the JSON report
+//!
+//!       --merge-reports <PATH>...
+//!           Merge JSON reports of sharded runs into one report instead of running the benchmark
+//!
+//!           The reports must have been written with --embed-hdr.
+//!
+//!       --error-max-len <CHARS>
+//!           Truncate error messages to this many characters in the error distribution
+//!
+//!           [default: 200]
+//!
+//!       --error-normalize
+//!           Replace uuids, hex strings and numbers in error messages with placeholders
+//!
+//!           Groups errors which only differ in request ids, addresses and the like.
+//!
+//!       --error-max-kinds <N>
+//!           Maximum number of distinct errors in the error distribution
+//!
+//!           Further errors are counted under "(other)".
+//!
+//!           [default: 100]
+//!
+//!       --error-samples <N>
+//!           Number of verbatim sample messages kept for each distinct error
+//!
+//!           [default: 3]
+//!
+//!       --status-max-kinds <N>
+//!           Maximum number of distinct statuses in the status distribution
+//!
+//!           Further statuses are counted under "<Kind>(other)".
+//!
+//!           [default: 256]
+//!
+//!       --color <COLOR>
+//!           When to style the text report with colors
+//!
+//!           [default: auto]
+//!
+//!           Possible values:
+//!           - auto:   Style the report only when stdout is a terminal
+//!           - always: Always style the report
+//!           - never:  Never style the report
+//!
+//!       --ascii
+//!           Draw the charts of the TUI and the text report with ASCII characters only
+//!
+//!           Useful for CI log viewers and terminals without unicode support.
+//!
+//!       --stream-file <PATH>
+//!           Stream every iteration result to a file in JSON Lines format
+//!
+//!           Works alongside any collector, e.g. the TUI.
+//!
+//!       --stream-max-size <SIZE>
+//!           Rotate the stream file once it grows beyond the size, e.g. 512MB
+//!
+//!           The file is renamed to `<PATH>.1`, shifting the older segments to `<PATH>.2` and so on. An index of the
+//!           segments and their time ranges is written to `<PATH>.index.json` at the end.
+//!
+//!       --stream-keep <N>
+//!           Number of the rotated segments of the stream file to keep
+//!
+//!           [default: 5]
+//!
+//!       --status-file <PATH>
+//!           Rewrite a JSON file with the state of the run every second
+//!
+//!           The file is replaced atomically, and written a final time with `"state": "finished"` at the end.
+//!
+//!       --record-timeseries
+//!           Record per-second samples of the run into the JSON report
+//!
+//!           Takes less than 100 bytes of memory per second of the run.
+//!
+//!       --slowest <N>
+//!           Number of the slowest iterations to show in the report
+//!
+//!           [default: 0]
+//!
+//!       --redirect-is-success
+//!           Count redirect statuses as successful iterations
+//!
+//!       --per-item-latency
+//!           Also report the latency per item, i.e. the iteration latency divided by its items
+//!
+//!           Iterations without items are skipped. Useful when the iterations process batches of variable sizes.
+//!
+//!       --per-worker-latency
+//!           Also report the latency of each worker, sorted by their p99
+//!
+//!           Tells whether some workers are slower than the others, e.g. pinned to a remote NUMA node. Takes about
+//!           30 KiB of memory per worker.
+//!
+//!       --discard-first <DURATION>
+//!           Leave out the iterations of the first part of the run from the report
+//!
+//!           The TUI still shows them live. The report states how much was discarded.
+//!
+//!           Examples: --discard-first 10s
+//!
+//!       --suspect-ceiling <DURATION>
+//!           Count the latencies above this one as suspect, like zero latencies
+//!
+//!           Suspect latencies usually come from a bug or a non-monotonic time source in the bench suite. The
+//!           report states how many iterations had one.
+//!
+//!           [default: 1h]
+//!
+//!       --exclude-suspect
+//!           Leave the suspect latencies out of the latency histogram and the slowest iterations
+//!
+//!       --start-jitter <DURATION>
+//!           Delay the first iteration of each worker by a random duration below this one
+//!
+//!           Spreads the first requests of many workers, which would otherwise all start in the same millisecond.
+//!           The delays follow the setup of the workers and are part of the run, so --discard-first can leave
+//!           them out of the report.
+//!
+//!           Examples: --start-jitter 100ms
+//!
+//!       --seed <SEED>
+//!           Seed of the random choices of the run, e.g. the start jitter
+//!
+//!           Drawn from the system time by default.
+//!
+//!       --sweep-concurrency <LIST>
+//!           Run the benchmark once per concurrency in the list, and report the steps side by side
+//!
+//!           The steps are printed as a table, or as an array of reports with `--output json`.
+//!
+//!           Examples: --sweep-concurrency 1,2,4,8,16 --duration 30s
+//!
+//!       --until-stable
+//!           Stop the benchmark once the p99 latency and iteration rate are stable
+//!
+//!           The run stops after --stable-windows consecutive minutes whose p99 latency and iteration rate are
+//!           within --stable-tolerance of the previous minute. The first minute is never counted as stable, and
+//!           --duration still caps the run.
+//!
+//!       --stable-tolerance <PERCENT>
+//!           Maximum deviation from the previous minute for --until-stable, in percent
+//!
+//!           [default: 2%]
+//!
+//!       --stable-windows <N>
+//!           Number of consecutive stable minutes for --until-stable to stop after
+//!
+//!           [default: 3]
+//!
+//!       --correct-coordinated-omission [<EXPECTED_INTERVAL>]
+//!           Correct the latency histogram for coordinated omission
+//!
+//!           The latencies of the iterations which could not start while the target stalled are recorded as well,
+//!           given the expected interval between iterations. Defaults to the inverse of --rate.
+//!
+//!           Examples: --correct-coordinated-omission 10ms
+//!
+//!       --assert <EXPR>
+//!           Assert an expectation on the final report, can be repeated
+//!
+//!           The outcomes are included in the report, and the benchmark fails if any assertion does not hold.
+//!           Metrics: p<N>, avg, min, max, stdev, success, error, iters, errors, items, bytes, iters_rate, items_rate, bytes_rate.
+//!
+//!           Examples: --assert p99<250ms --assert success>=99.5% --assert iters_rate>=1000
+//!
+//!       --skip-preflight
+//!           Skip the check of the target before the benchmark starts
+//!
+//!           See `BenchSuite::preflight`.
+//!
+//!       --log-file <PATH>
+//!           Write the logs captured by `tui_tracing_subscriber_layer` to a file as well
+//!
+//!           The file is appended to unless --truncate-log-file is set.
+//!
+//!       --truncate-log-file
+//!           Truncate the log file at startup instead of appending to it
+//!
+//!       --log-buffer <N>
+//!           Number of the latest log lines kept by `tui_tracing_subscriber_layer` [default: 5000]
+//!
+//!           Older lines are dropped, which the title of the log popup reports.
+//!
+//!       --log-level <LEVEL>
+//!           Minimum level of the log lines captured by `tui_tracing_subscriber_layer` [default: trace]
+//!
+//!       --prometheus-addr <ADDR>
+//!           Expose Prometheus metrics on the given address during the benchmark
+//!
+//!           Metrics are served at `http://<ADDR>/metrics`.
+//!
+//!       --control-addr <ADDR>
+//!           Serve an HTTP control endpoint on the given address
+//!
+//!           Supports `POST /pause`, `POST /resume`, `POST /stop` and `GET /status`.
 //!
 //!   -h, --help
 //!           Print help (see a summary with '-h')
+#[cfg(any(feature = "prometheus", feature = "control"))]
+use std::net::SocketAddr;
 use std::{
-    io::stdout,
+    collections::BTreeMap,
+    fs::File,
+    io::{stdout, BufWriter, Write},
     num::{NonZeroU32, NonZeroU64, NonZeroU8},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
 };
 
+use anyhow::Context;
 use clap::{
     builder::{
         styling::{AnsiColor, Effects},
@@ -94,20 +361,20 @@ use clap::{
     Parser, ValueEnum,
 };
 use crossterm::tty::IsTty;
-use tokio::{
-    sync::{mpsc, watch},
-    time::Instant,
-};
-use tokio_util::sync::CancellationToken;
 
+#[cfg(feature = "html-report")]
+use crate::reporter::HtmlReporter;
 use crate::{
-    clock::Clock,
-    collector::{ReportCollector, SilentCollector, TuiCollector},
-    reporter::{BenchReporter, JsonReporter, TextReporter},
-    runner::{BenchOpts, BenchSuite, Runner},
+    assertion::{Assertion, AssertionResult},
+    builder::BenchBuilder,
+    error::Error,
+    report::BenchReport,
+    reporter::{BenchReporter, CsvReporter, JsonReporter, MarkdownReporter, PromReporter, TextReporter},
+    runner::{BenchSuite, ErrorOpts, StableOpts},
+    util::{write_atomic, ByteUnit},
 };
 
-#[derive(Parser, Clone, Copy, Debug)]
+#[derive(Parser, Clone, Debug)]
 #[clap(
     styles(Styles::styled()
         .header(AnsiColor::Yellow.on_default() | Effects::BOLD)
@@ -132,8 +399,8 @@ pub struct BenchCli {
     ///
     /// When set, benchmark stops after reaching the duration.
     ///
-    /// Examples: -z 10s, -z 5m, -z 1h
-    #[clap(long, short = 'd')]
+    /// A bare number is taken as seconds. Examples: -d 30, -d 10s, -d 5m, -d 1h
+    #[clap(long, short = 'd', alias = "max-duration", value_parser = parse_duration)]
     pub duration: Option<humantime::Duration>,
 
     #[cfg(feature = "rate_limit")]
@@ -145,17 +412,29 @@ pub struct BenchCli {
 
     /// Run benchmark in quiet mode
     ///
-    /// Implies --collector silent.
-    #[clap(long, short = 'q')]
-    pub quiet: bool,
+    /// Implies --collector silent. The silent collector prints a line to stderr when the setup completes,
+    /// the benchmark starts and finishes; pass -qq to hide them as well.
+    #[clap(long, short = 'q', action = clap::ArgAction::Count)]
+    pub quiet: u8,
 
     /// Collector for the benchmark
     #[clap(long, value_enum, ignore_case = true)]
     pub collector: Option<Collector>,
 
-    /// Refresh rate for the tui collector, in frames per second (fps)
-    #[clap(long, default_value = "32")]
-    pub fps: NonZeroU8,
+    /// Refresh rate for the tui and progress collectors, in frames per second (fps)
+    ///
+    /// If not set, the progress collector refreshes at 32 fps and the tui adapts its refresh rate to the cost of
+    /// drawing, between 2 and 32 fps.
+    #[clap(long)]
+    pub fps: Option<NonZeroU8>,
+
+    /// Draw the TUI on another terminal, e.g. /dev/pts/7, and read the keys from there
+    ///
+    /// Keeps stdout clean for the report, e.g. when it is piped. Find the path with `tty` on the other
+    /// terminal, and leave it idle, e.g. with `sleep infinity`, so its shell does not compete for the keys.
+    /// Implies the TUI collector unless --collector is given. Only supported on unix.
+    #[clap(long, value_name = "PATH")]
+    pub tui_tty: Option<PathBuf>,
 
     /// Quit the benchmark manually
     ///
@@ -166,17 +445,301 @@ pub struct BenchCli {
     /// Output format for the report
     #[clap(short, long, value_enum, default_value_t = ReportFormat::Text, ignore_case = true)]
     pub output: ReportFormat,
+
+    /// Print the JSON report on a single line
+    ///
+    /// Useful for appending one report per run to a JSON Lines file.
+    #[clap(long)]
+    pub json_compact: bool,
+
+    /// Write the report to a file instead of stdout
+    ///
+    /// The file is replaced atomically once the report is complete.
+    #[clap(long, value_name = "PATH")]
+    pub report_file: Option<PathBuf>,
+
+    /// Rewrite the report file with the report of the run so far every interval, e.g. 5s
+    ///
+    /// Only works with `--output json`. The partial reports have the schema of the final report plus
+    /// `"partial": true`, and the final report replaces them at the end.
+    #[clap(long, value_name = "DURATION", value_parser = parse_duration, requires = "report_file")]
+    pub report_interval: Option<humantime::Duration>,
+
+    /// Label the run with a key-value pair, can be repeated
+    ///
+    /// Labels are added as extra columns to the CSV report and to every sample of the Prometheus report.
+    ///
+    /// Examples: --label env=staging --label commit=abc123
+    #[clap(long, value_name = "KEY=VALUE", value_parser = parse_label)]
+    pub label: Vec<(String, String)>,
+
+    /// Prefix of the metric names in the Prometheus report
+    #[clap(long, value_name = "PREFIX", default_value = "rlt")]
+    pub prom_prefix: String,
+
+    /// Unit convention for displaying byte sizes
+    #[clap(long, value_enum, default_value_t = ByteUnit::Binary, ignore_case = true)]
+    pub byte_units: ByteUnit,
+
+    /// Export the latency histogram to a file in the HdrHistogram interval log format
+    #[clap(long, value_name = "PATH")]
+    pub export_hdr: Option<PathBuf>,
+
+    /// Write the latencies of every second to a file in the HdrHistogram interval log format
+    ///
+    /// Pauses show up as gaps between the timestamps of the intervals. Readable by the HdrHistogram
+    /// tooling, e.g. HistogramLogProcessor. Latencies are in nanoseconds.
+    #[clap(long, value_name = "PATH")]
+    pub hdr_interval_log: Option<PathBuf>,
+
+    /// Embed the latency histogram as base64 under `latency.hdr` in the JSON report
+    #[clap(long)]
+    pub embed_hdr: bool,
+
+    /// Merge JSON reports of sharded runs into one report instead of running the benchmark
+    ///
+    /// The reports must have been written with --embed-hdr.
+    #[clap(long, value_name = "PATH", num_args = 1..)]
+    pub merge_reports: Vec<PathBuf>,
+
+    /// Truncate error messages to this many characters in the error distribution
+    #[clap(long, value_name = "CHARS", default_value_t = 200)]
+    pub error_max_len: usize,
+
+    /// Replace uuids, hex strings and numbers in error messages with placeholders
+    ///
+    /// Groups errors which only differ in request ids, addresses and the like.
+    #[clap(long)]
+    pub error_normalize: bool,
+
+    /// Maximum number of distinct errors in the error distribution
+    ///
+    /// Further errors are counted under "(other)".
+    #[clap(long, value_name = "N", default_value_t = 100)]
+    pub error_max_kinds: usize,
+
+    /// Number of verbatim sample messages kept for each distinct error
+    #[clap(long, value_name = "N", default_value_t = 3)]
+    pub error_samples: usize,
+
+    /// Maximum number of distinct statuses in the status distribution
+    ///
+    /// Further statuses are counted under "<Kind>(other)".
+    #[clap(long, value_name = "N", default_value_t = 256)]
+    pub status_max_kinds: usize,
+
+    /// When to style the text report with colors
+    #[clap(long, value_enum, default_value_t = ColorChoice::Auto, ignore_case = true)]
+    pub color: ColorChoice,
+
+    /// Draw the charts of the TUI and the text report with ASCII characters only
+    ///
+    /// Useful for CI log viewers and terminals without unicode support.
+    #[clap(long)]
+    pub ascii: bool,
+
+    /// Stream every iteration result to a file in JSON Lines format
+    ///
+    /// Works alongside any collector, e.g. the TUI.
+    #[clap(long, value_name = "PATH")]
+    pub stream_file: Option<PathBuf>,
+
+    /// Rotate the stream file once it grows beyond the size, e.g. 512MB
+    ///
+    /// The file is renamed to `<PATH>.1`, shifting the older segments to `<PATH>.2` and so on. An index of the
+    /// segments and their time ranges is written to `<PATH>.index.json` at the end.
+    #[clap(long, value_name = "SIZE", value_parser = parse_size, requires = "stream_file")]
+    pub stream_max_size: Option<u64>,
+
+    /// Number of the rotated segments of the stream file to keep
+    #[clap(long, value_name = "N", default_value_t = 5)]
+    pub stream_keep: usize,
+
+    /// Rewrite a JSON file with the state of the run every second
+    ///
+    /// The file is replaced atomically, and written a final time with `"state": "finished"` at the end.
+    #[clap(long, value_name = "PATH")]
+    pub status_file: Option<PathBuf>,
+
+    /// Record per-second samples of the run into the JSON report
+    ///
+    /// Takes less than 100 bytes of memory per second of the run.
+    #[clap(long)]
+    pub record_timeseries: bool,
+
+    /// Number of the slowest iterations to show in the report
+    #[clap(long, value_name = "N", default_value_t = 0)]
+    pub slowest: usize,
+
+    /// Count redirect statuses as successful iterations
+    #[clap(long)]
+    pub redirect_is_success: bool,
+
+    /// Also report the latency per item, i.e. the iteration latency divided by its items
+    ///
+    /// Iterations without items are skipped. Useful when the iterations process batches of variable sizes.
+    #[clap(long)]
+    pub per_item_latency: bool,
+
+    /// Also report the latency of each worker, sorted by their p99
+    ///
+    /// Tells whether some workers are slower than the others, e.g. pinned to a remote NUMA node. Takes about
+    /// 30 KiB of memory per worker.
+    #[clap(long)]
+    pub per_worker_latency: bool,
+
+    /// Leave out the iterations of the first part of the run from the report
+    ///
+    /// The TUI still shows them live. The report states how much was discarded.
+    ///
+    /// Examples: --discard-first 10s
+    #[clap(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub discard_first: Option<humantime::Duration>,
+
+    /// Count the latencies above this one as suspect, like zero latencies
+    ///
+    /// Suspect latencies usually come from a bug or a non-monotonic time source in the bench suite. The
+    /// report states how many iterations had one.
+    #[clap(long, value_name = "DURATION", default_value = "1h", value_parser = parse_duration)]
+    pub suspect_ceiling: humantime::Duration,
+
+    /// Leave the suspect latencies out of the latency histogram and the slowest iterations
+    #[clap(long)]
+    pub exclude_suspect: bool,
+
+    /// Delay the first iteration of each worker by a random duration below this one
+    ///
+    /// Spreads the first requests of many workers, which would otherwise all start in the same millisecond.
+    /// The delays follow the setup of the workers and are part of the run, so --discard-first can leave
+    /// them out of the report.
+    ///
+    /// Examples: --start-jitter 100ms
+    #[clap(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub start_jitter: Option<humantime::Duration>,
+
+    /// Seed of the random choices of the run, e.g. the start jitter
+    ///
+    /// Drawn from the system time by default.
+    #[clap(long)]
+    pub seed: Option<u64>,
+
+    /// Run the benchmark once per concurrency in the list, and report the steps side by side
+    ///
+    /// The steps are printed as a table, or as an array of reports with `--output json`. The other output
+    /// formats are not supported.
+    ///
+    /// Examples: --sweep-concurrency 1,2,4,8,16 --duration 30s
+    #[clap(
+        long,
+        value_name = "LIST",
+        value_delimiter = ',',
+        requires = "duration",
+        conflicts_with_all = ["iterations", "merge_reports", "export_hdr", "report_interval"]
+    )]
+    pub sweep_concurrency: Vec<NonZeroU32>,
+
+    /// Stop the benchmark once the p99 latency and iteration rate are stable
+    ///
+    /// The run stops after --stable-windows consecutive minutes whose p99 latency and iteration rate are
+    /// within --stable-tolerance of the previous minute. The first minute is never counted as stable, and
+    /// --duration still caps the run.
+    #[clap(long)]
+    pub until_stable: bool,
+
+    /// Maximum deviation from the previous minute for --until-stable, in percent
+    #[clap(long, value_name = "PERCENT", default_value = "2%", value_parser = parse_percent, requires = "until_stable")]
+    pub stable_tolerance: f64,
+
+    /// Number of consecutive stable minutes for --until-stable to stop after
+    #[clap(long, value_name = "N", default_value_t = 3, requires = "until_stable")]
+    pub stable_windows: u32,
+
+    /// Correct the latency histogram for coordinated omission
+    ///
+    /// The latencies of the iterations which could not start while the target stalled are recorded as well,
+    /// given the expected interval between iterations. Defaults to the inverse of --rate.
+    ///
+    /// Examples: --correct-coordinated-omission 10ms
+    #[clap(long, value_name = "EXPECTED_INTERVAL", num_args = 0..=1, value_parser = parse_duration)]
+    pub correct_coordinated_omission: Option<Option<humantime::Duration>>,
+
+    /// Assert an expectation on the final report, can be repeated
+    ///
+    /// The outcomes are included in the report, and the benchmark fails if any assertion does not hold.
+    /// Metrics: p<N>, avg, min, max, stdev, success, error, iters, errors, items, bytes, iters_rate, items_rate, bytes_rate.
+    ///
+    /// Examples: --assert p99<250ms --assert success>=99.5% --assert iters_rate>=1000
+    #[clap(long = "assert", value_name = "EXPR", value_parser = parse_assertion)]
+    pub assertions: Vec<Assertion>,
+
+    /// Skip the check of the target before the benchmark starts
+    ///
+    /// See `BenchSuite::preflight`.
+    #[clap(long)]
+    pub skip_preflight: bool,
+
+    #[cfg(feature = "tracing")]
+    /// Write the logs captured by `tui_tracing_subscriber_layer` to a file as well
+    ///
+    /// The file is appended to unless --truncate-log-file is set.
+    #[clap(long, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+
+    #[cfg(feature = "tracing")]
+    /// Truncate the log file at startup instead of appending to it
+    #[clap(long, requires = "log_file")]
+    pub truncate_log_file: bool,
+
+    #[cfg(feature = "tracing")]
+    /// Number of the latest log lines kept by `tui_tracing_subscriber_layer` [default: 5000]
+    ///
+    /// Older lines are dropped, which the title of the log popup reports.
+    #[clap(long, value_name = "N")]
+    pub log_buffer: Option<usize>,
+
+    #[cfg(feature = "tracing")]
+    /// Minimum level of the log lines captured by `tui_tracing_subscriber_layer` [default: trace]
+    #[clap(long, value_name = "LEVEL")]
+    pub log_level: Option<log::LevelFilter>,
+
+    #[cfg(feature = "prometheus")]
+    /// Expose Prometheus metrics on the given address during the benchmark
+    ///
+    /// Metrics are served at `http://<ADDR>/metrics`.
+    #[clap(long, value_name = "ADDR")]
+    pub prometheus_addr: Option<SocketAddr>,
+
+    #[cfg(feature = "control")]
+    /// Serve an HTTP control endpoint on the given address
+    ///
+    /// Supports `POST /pause`, `POST /resume`, `POST /stop` and `GET /status`.
+    #[clap(long, value_name = "ADDR")]
+    pub control_addr: Option<SocketAddr>,
 }
 
 impl BenchCli {
-    pub(crate) fn bench_opts(&self, clock: Clock) -> BenchOpts {
-        BenchOpts {
-            clock,
-            concurrency: self.concurrency.get(),
-            iterations: self.iterations.map(|n| n.get()),
-            duration: self.duration.map(|d| d.into()),
+    /// Get the labels of the run, with later values overriding earlier ones of the same key.
+    pub fn labels(&self) -> BTreeMap<String, String> {
+        self.label.iter().cloned().collect()
+    }
+
+    /// The expected interval between iterations to correct for coordinated omission with, if enabled.
+    pub fn expected_interval(&self) -> Option<Duration> {
+        match self.correct_coordinated_omission? {
+            Some(interval) => Some(interval.into()),
             #[cfg(feature = "rate_limit")]
-            rate: self.rate,
+            None => self.rate.map(|r| Duration::from_secs_f64(1.0 / r.get() as f64)),
+            #[cfg(not(feature = "rate_limit"))]
+            None => None,
+        }
+    }
+
+    /// Whether the text report should be styled with colors.
+    pub fn color(&self) -> bool {
+        match self.color {
+            ColorChoice::Auto => stdout().is_tty(),
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
         }
     }
 
@@ -184,20 +747,78 @@ impl BenchCli {
     pub fn collector(&self) -> Collector {
         match self.collector {
             Some(collector) => collector,
-            None if self.quiet || !stdout().is_tty() => Collector::Silent,
+            None if self.tui_tty.is_some() => Collector::Tui,
+            None if self.quiet > 0 || !stdout().is_tty() => Collector::Silent,
             _ => Collector::Tui,
         }
     }
 }
 
+fn parse_percent(s: &str) -> Result<f64, String> {
+    match s.trim_end_matches('%').parse::<f64>() {
+        Ok(p) if p >= 0.0 => Ok(p / 100.0),
+        _ => Err(format!("invalid percentage `{s}`, expected e.g. 2%")),
+    }
+}
+
+/// Parse a positive duration, taking a bare number as seconds.
+fn parse_duration(s: &str) -> Result<humantime::Duration, String> {
+    let duration = match s.parse::<f64>() {
+        Ok(secs) => {
+            Duration::try_from_secs_f64(secs).map_err(|_| format!("invalid duration `{s}`, expected e.g. 30s"))?
+        }
+        Err(_) => *s
+            .parse::<humantime::Duration>()
+            .map_err(|e| format!("invalid duration `{s}`: {e}"))?,
+    };
+    match duration.is_zero() {
+        true => Err(format!("`{s}` is zero, expected a positive duration, e.g. 30s")),
+        false => Ok(duration.into()),
+    }
+}
+
+fn parse_size(s: &str) -> Result<u64, String> {
+    match byte_unit::Byte::parse_str(s, true) {
+        Ok(size) if size.as_u64() > 0 => Ok(size.as_u64()),
+        _ => Err(format!("invalid size `{s}`, expected e.g. 512MB")),
+    }
+}
+
+fn parse_assertion(s: &str) -> Result<Assertion, String> {
+    s.parse().map_err(|e: anyhow::Error| e.to_string())
+}
+
+fn parse_label(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((k, v)) if !k.is_empty() => Ok((k.to_string(), v.to_string())),
+        _ => Err(format!("invalid label `{s}`, expected KEY=VALUE")),
+    }
+}
+
 /// The type of iteration report collector.
 #[derive(Copy, Clone, Debug, ValueEnum)]
 pub enum Collector {
-    /// TUI based collector. See [`TuiCollector`].
+    /// TUI based collector. See [`TuiCollector`](crate::collector::TuiCollector).
     Tui,
 
-    /// Collector that does not print anything. See [`SilentCollector`].
+    /// Collector that does not print anything. See [`SilentCollector`](crate::collector::SilentCollector).
     Silent,
+
+    /// Collector that prints a single-line progress indicator to stderr. See [`ProgressCollector`](crate::collector::ProgressCollector).
+    Progress,
+}
+
+/// When to style the text report with colors.
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum ColorChoice {
+    /// Style the report only when stdout is a terminal.
+    Auto,
+
+    /// Always style the report.
+    Always,
+
+    /// Never style the report.
+    Never,
 }
 
 /// Benchmark report format.
@@ -208,43 +829,367 @@ pub enum ReportFormat {
 
     /// Report in JSON format. See [`JsonReporter`].
     Json,
+
+    /// Report in CSV format, one row per run. See [`CsvReporter`].
+    Csv,
+
+    /// Report in GitHub-flavored Markdown. See [`MarkdownReporter`].
+    Markdown,
+
+    /// Report in the Prometheus text exposition format. See [`PromReporter`].
+    Prom,
+
+    /// Report as a self-contained HTML page. See [`HtmlReporter`].
+    #[cfg(feature = "html-report")]
+    Html,
+}
+
+/// Load a report written by the JSON reporter.
+fn load_report(path: &Path) -> Result<BenchReport, Error> {
+    let json = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))
+        .map_err(Error::Io)?;
+    JsonReporter::parse(&json)
+        .with_context(|| format!("invalid report {}", path.display()))
+        .map_err(Error::Report)
+}
+
+/// Create the reporter of the configured output format.
+fn reporter(cli: &BenchCli) -> Arc<dyn BenchReporter + Send + Sync> {
+    match cli.output {
+        ReportFormat::Text => Arc::new(TextReporter {
+            byte_unit: cli.byte_units,
+            ascii: cli.ascii,
+            color: cli.color(),
+        }),
+        ReportFormat::Json => Arc::new(JsonReporter {
+            byte_unit: cli.byte_units,
+            embed_hdr: cli.embed_hdr,
+            compact: cli.json_compact,
+        }),
+        ReportFormat::Csv => Arc::new(CsvReporter::new(cli.labels())),
+        ReportFormat::Markdown => Arc::new(MarkdownReporter::new(cli.byte_units)),
+        ReportFormat::Prom => Arc::new(PromReporter::new(&cli.prom_prefix, cli.labels())),
+        #[cfg(feature = "html-report")]
+        ReportFormat::Html => Arc::new(HtmlReporter::new(cli.byte_units)),
+    }
+}
+
+impl From<BenchCli> for BenchBuilder {
+    fn from(cli: BenchCli) -> Self {
+        let builder = BenchBuilder::new()
+            .concurrency(cli.concurrency.get())
+            .errors(ErrorOpts {
+                max_len: cli.error_max_len,
+                normalize: cli.error_normalize,
+                max_kinds: cli.error_max_kinds,
+                samples: cli.error_samples,
+                classifier: None,
+            })
+            .status_max_kinds(cli.status_max_kinds)
+            .record_timeseries(cli.record_timeseries)
+            .slowest(cli.slowest)
+            .redirect_is_success(cli.redirect_is_success)
+            .per_item_latency(cli.per_item_latency)
+            .per_worker_latency(cli.per_worker_latency)
+            .suspect_ceiling(cli.suspect_ceiling.into())
+            .exclude_suspect(cli.exclude_suspect)
+            .collector(cli.collector())
+            .log_phases(matches!(cli.collector(), Collector::Silent) && cli.quiet < 2)
+            .auto_quit(!cli.quit_manually)
+            .byte_unit(cli.byte_units)
+            .ascii(cli.ascii)
+            .reporter(reporter(&cli))
+            .labels(cli.labels())
+            .assertions(cli.assertions.clone())
+            .preflight(!cli.skip_preflight);
+        let builder = match cli.fps {
+            Some(fps) => builder.fps(fps),
+            None => builder,
+        };
+        let builder = match &cli.tui_tty {
+            Some(path) => builder.tui_tty(path),
+            None => builder,
+        };
+        let builder = match &cli.report_file {
+            Some(path) => builder.report_file(path),
+            None => builder,
+        };
+        let builder = match cli.report_interval {
+            Some(interval) => builder.report_interval(interval.into()),
+            None => builder,
+        };
+        let builder = match cli.iterations {
+            Some(n) => builder.iterations(n.get()),
+            None => builder,
+        };
+        let builder = match cli.duration {
+            Some(d) => builder.duration(d.into()),
+            None => builder,
+        };
+        #[cfg(feature = "rate_limit")]
+        let builder = match cli.rate {
+            Some(r) => builder.rate(r),
+            None => builder,
+        };
+        let builder = match cli.discard_first {
+            Some(d) => builder.discard_first(d.into()),
+            None => builder,
+        };
+        let builder = match cli.start_jitter {
+            Some(jitter) => builder.start_jitter(jitter.into()),
+            None => builder,
+        };
+        let builder = match cli.seed {
+            Some(seed) => builder.seed(seed),
+            None => builder,
+        };
+        let builder = match cli.until_stable {
+            true => builder.until_stable(StableOpts {
+                tolerance: cli.stable_tolerance,
+                windows: cli.stable_windows,
+                ..Default::default()
+            }),
+            false => builder,
+        };
+        let builder = match cli.expected_interval() {
+            Some(interval) => builder.expected_interval(interval),
+            None => builder,
+        };
+        let builder = match &cli.stream_file {
+            Some(path) => builder.stream_file(path),
+            None => builder,
+        };
+        let builder = match cli.stream_max_size {
+            Some(size) => builder.stream_rotation(size, cli.stream_keep),
+            None => builder,
+        };
+        let builder = match &cli.hdr_interval_log {
+            Some(path) => builder.hdr_interval_log(path),
+            None => builder,
+        };
+        let builder = match &cli.status_file {
+            Some(path) => builder.status_file(path),
+            None => builder,
+        };
+        #[cfg(feature = "tracing")]
+        let builder = match &cli.log_file {
+            Some(path) => builder.log_file(path, cli.truncate_log_file),
+            None => builder,
+        };
+        #[cfg(feature = "tracing")]
+        let builder = match cli.log_buffer {
+            Some(lines) => builder.log_buffer(lines),
+            None => builder,
+        };
+        #[cfg(feature = "tracing")]
+        let builder = match cli.log_level {
+            Some(level) => builder.log_level(level),
+            None => builder,
+        };
+        #[cfg(feature = "prometheus")]
+        let builder = match cli.prometheus_addr {
+            Some(addr) => builder.prometheus_addr(addr),
+            None => builder,
+        };
+        #[cfg(feature = "control")]
+        let builder = match cli.control_addr {
+            Some(addr) => builder.control_addr(addr),
+            None => builder,
+        };
+        builder
+    }
 }
 
 /// Run the benchmark with the given CLI options and benchmark suite.
-pub async fn run<BS>(cli: BenchCli, bench_suite: BS) -> anyhow::Result<()>
+///
+/// Fails with [`Error::AssertionFailed`] if any of the assertions failed, after the report is printed.
+pub async fn run<BS>(cli: BenchCli, bench_suite: BS) -> Result<(), Error>
 where
     BS: BenchSuite + Send + Sync + 'static,
     BS::WorkerState: Send + Sync + 'static,
 {
-    let (res_tx, res_rx) = mpsc::unbounded_channel();
-    let (pause_tx, pause_rx) = watch::channel(false);
-    let cancel = CancellationToken::new();
-
-    let opts = cli.bench_opts(Clock::start_at(Instant::now()));
-    let runner = Runner::new(bench_suite, opts.clone(), res_tx, pause_rx, cancel.clone());
-
-    let mut collector: Box<dyn ReportCollector> = match cli.collector() {
-        Collector::Tui => Box::new(TuiCollector::new(
-            opts,
-            cli.fps,
-            res_rx,
-            pause_tx,
-            cancel,
-            !cli.quit_manually,
-        )?),
-        Collector::Silent => Box::new(SilentCollector::new(opts, res_rx, cancel)),
-    };
+    if let Some((first, rest)) = cli.merge_reports.split_first() {
+        let mut report = load_report(first)?;
+        for path in rest {
+            report
+                .merge(&load_report(path)?)
+                .with_context(|| format!("cannot merge {}", path.display()))
+                .map_err(Error::Report)?;
+        }
+        report.assertions = cli.assertions.iter().map(|a| a.eval(&report)).collect();
+        print_output(&cli, |w| reporter(&cli).print(w, &report))?;
+        return check_assertions(report.assertions);
+    }
 
-    let report = tokio::spawn(async move { collector.run().await });
+    if cli.report_interval.is_some() && !matches!(cli.output, ReportFormat::Json) {
+        return Err(Error::InvalidOptions("--report-interval needs --output json".into()));
+    }
+    if !cli.sweep_concurrency.is_empty() && !matches!(cli.output, ReportFormat::Text | ReportFormat::Json) {
+        return Err(Error::InvalidOptions(
+            "--sweep-concurrency needs --output text or --output json".into(),
+        ));
+    }
 
-    runner.run().await?;
+    if cli.correct_coordinated_omission.is_some() && cli.expected_interval().is_none() {
+        return Err(Error::InvalidOptions(
+            "--correct-coordinated-omission needs an expected interval unless --rate is set".into(),
+        ));
+    }
+    if !cli.sweep_concurrency.is_empty() {
+        return sweep(cli, bench_suite).await;
+    }
+
+    let export_hdr = cli.export_hdr.clone();
+    let report = BenchBuilder::from(cli).run(bench_suite).await.map_err(preflight_hint)?;
+
+    if let Some(path) = &export_hdr {
+        let export = || -> anyhow::Result<()> {
+            let mut w = BufWriter::new(File::create(path)?);
+            report.hist.serialize(&mut w, report.elapsed)?;
+            Ok(w.flush()?)
+        };
+        export().map_err(Error::Io)?;
+    }
+
+    check_assertions(report.assertions)
+}
 
-    let reporter: &dyn BenchReporter = match cli.output {
-        ReportFormat::Text => &TextReporter,
-        ReportFormat::Json => &JsonReporter,
+/// Run the benchmark once per step of a concurrency sweep and print the reports of all steps.
+///
+/// The worker states are created anew for every step, and the preflight check runs before the first step only.
+/// Assertions are evaluated against each step.
+async fn sweep<BS>(cli: BenchCli, bench_suite: BS) -> Result<(), Error>
+where
+    BS: BenchSuite + Send + Sync + 'static,
+    BS::WorkerState: Send + Sync + 'static,
+{
+    let steps = cli.sweep_concurrency.len();
+    let mut reports = Vec::with_capacity(steps);
+    for (i, concurrency) in cli.sweep_concurrency.iter().enumerate() {
+        let report = BenchBuilder::from(cli.clone())
+            .concurrency(concurrency.get())
+            .caption(format!("(sweep {}/{steps}: concurrency {concurrency})", i + 1))
+            .preflight(i == 0 && !cli.skip_preflight)
+            .print_report(false)
+            .run(bench_suite.clone())
+            .await
+            .map_err(preflight_hint)?;
+        reports.push(report);
+    }
+
+    print_output(&cli, |w| match cli.output {
+        ReportFormat::Json => JsonReporter {
+            byte_unit: cli.byte_units,
+            embed_hdr: cli.embed_hdr,
+            compact: cli.json_compact,
+        }
+        .print_sweep(w, &reports),
+        ReportFormat::Text => TextReporter {
+            byte_unit: cli.byte_units,
+            ascii: cli.ascii,
+            color: cli.color(),
+        }
+        .print_sweep(w, &reports),
+        _ => unreachable!("rejected by `run`"),
+    })?;
+
+    let assertions = reports
+        .iter()
+        .flat_map(|r| r.assertions.iter().cloned())
+        .collect::<Vec<_>>();
+    check_assertions(assertions)
+}
+
+/// Print to the report file if there is one, and to stdout otherwise.
+fn print_output(cli: &BenchCli, print: impl FnOnce(&mut dyn Write) -> anyhow::Result<()>) -> Result<(), Error> {
+    match &cli.report_file {
+        Some(path) => write_atomic(path, print),
+        None => print(&mut stdout()),
+    }
+    .map_err(Error::Io)
+}
+
+/// Point at `--skip-preflight` when the preflight check of the bench suite failed.
+fn preflight_hint(e: Error) -> Error {
+    match e {
+        Error::Preflight(e) => Error::Preflight(e.context("use --skip-preflight to run anyway")),
+        e => e,
+    }
+}
+
+/// Fail if any of the assertions did not hold, so the process exits with a non-zero code.
+fn check_assertions(assertions: Vec<AssertionResult>) -> Result<(), Error> {
+    match assertions.iter().all(|a| a.passed) {
+        true => Ok(()),
+        false => Err(Error::AssertionFailed(assertions)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::{
+        report::{BenchResult, IterReport},
+        runner::{IterInfo, StatelessBenchSuite},
     };
 
-    reporter.print(&mut stdout(), &report.await??)?;
+    #[derive(Clone)]
+    struct Down;
+
+    #[async_trait]
+    impl StatelessBenchSuite for Down {
+        async fn preflight(&self) -> anyhow::Result<()> {
+            anyhow::bail!("target is down")
+        }
+
+        async fn bench(&mut self, _: &IterInfo) -> BenchResult<IterReport> {
+            Ok(IterReport::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn hints_at_skipping_the_preflight_check() {
+        let cli = BenchCli::parse_from(["bench", "-n", "1", "--collector", "silent", "-qq"]);
+        let err = run(cli, Down).await.unwrap_err();
+        assert!(matches!(err, Error::Preflight(_)));
+        assert_eq!(err.to_string(), "preflight check failed");
 
-    Ok(())
+        let causes = anyhow::Error::from(err)
+            .chain()
+            .skip(1)
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(causes, ["use --skip-preflight to run anyway", "target is down"]);
+    }
+
+    #[test]
+    fn load_report_errors() {
+        let dir = std::env::temp_dir().join(format!("rlt-load-report-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let invalid = dir.join("invalid.json");
+        std::fs::write(&invalid, "{").unwrap();
+
+        assert!(matches!(load_report(&dir.join("missing.json")), Err(Error::Io(_))));
+        assert!(matches!(load_report(&invalid), Err(Error::Report(_))));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn parses_durations() {
+        let parse = |s| parse_duration(s).map(|d| *d);
+        assert_eq!(parse("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse("1m 30s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse("30"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse("0.25"), Ok(Duration::from_millis(250)));
+
+        assert!(parse("0").unwrap_err().contains("is zero"));
+        assert!(parse("0s").unwrap_err().contains("is zero"));
+        for s in ["1e20", "-1", "NaN", "inf"] {
+            assert!(parse(s).unwrap_err().contains("invalid duration"), "{s}");
+        }
+        assert!(parse("soon").unwrap_err().contains("invalid duration"));
+    }
 }
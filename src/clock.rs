@@ -1,14 +1,30 @@
 use std::sync::Arc;
 
 use parking_lot::Mutex;
-use tokio::time::{self, Duration, Instant};
+use tokio::{
+    sync::Notify,
+    time::{self, Duration, Instant},
+};
 
 /// A logical clock that can be paused
+///
+/// The elapsed time of the clock stands still while the benchmark is paused by its [`PauseControl`].
+///
+/// [`PauseControl`]: crate::PauseControl
 #[derive(Debug, Clone)]
 pub struct Clock {
     #[cfg(feature = "rate_limit")]
     start: Instant,
     inner: Arc<Mutex<InnerClock>>,
+    manual: Option<Arc<ManualTime>>,
+}
+
+/// A time source which only moves when advanced, see [`Clock::manual`].
+#[derive(Debug)]
+struct ManualTime {
+    start: Instant,
+    offset: Mutex<Duration>,
+    advanced: Notify,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -25,43 +41,90 @@ pub(crate) enum Status {
 }
 
 impl Clock {
+    /// Create a running clock which follows the system time from the given instant, usually `Instant::now()`.
     pub fn start_at(start: Instant) -> Self {
+        Self::with_source(start, None)
+    }
+
+    /// Create a running clock which only moves when [`advance`](Self::advance)d, e.g. for deterministic tests.
+    ///
+    /// Sleeping on the clock or its tickers waits until the clock is advanced past the deadline.
+    pub fn manual() -> Self {
+        let start = Instant::now();
+        let manual = ManualTime { start, offset: Mutex::default(), advanced: Notify::new() };
+        Self::with_source(start, Some(Arc::new(manual)))
+    }
+
+    fn with_source(start: Instant, manual: Option<Arc<ManualTime>>) -> Self {
         let inner = InnerClock { status: Status::Running(start), elapsed: Duration::default() };
 
         cfg_if::cfg_if! {
             if #[cfg(feature = "rate_limit")] {
-                Self { start, inner: Arc::new(Mutex::new(inner)) }
+                Self { start, inner: Arc::new(Mutex::new(inner)), manual }
             } else {
-                Self { inner: Arc::new(Mutex::new(inner)) }
+                Self { inner: Arc::new(Mutex::new(inner)), manual }
             }
         }
     }
 
-    pub fn resume(&mut self) {
+    /// Move the time source of a [`manual`](Self::manual) clock forward, waking the sleepers which are due.
+    ///
+    /// The elapsed time only moves while the clock is running. Has no effect on clocks which follow the system
+    /// time.
+    pub fn advance(&self, duration: Duration) {
+        if let Some(manual) = &self.manual {
+            *manual.offset.lock() += duration;
+            manual.advanced.notify_waiters();
+        }
+    }
+
+    /// The current instant of the time source.
+    fn now(&self) -> Instant {
+        match &self.manual {
+            Some(manual) => manual.start + *manual.offset.lock(),
+            None => Instant::now(),
+        }
+    }
+
+    /// Resumes the clock. Use [`PauseControl`](crate::PauseControl) to pause and resume the benchmark.
+    pub(crate) fn resume(&self) {
         let mut inner = self.inner.lock();
         if let Status::Paused = inner.status {
-            inner.status = Status::Running(Instant::now());
+            inner.status = Status::Running(self.now());
         }
     }
 
-    pub fn pause(&mut self) {
+    pub(crate) fn pause(&self) {
         let mut inner = self.inner.lock();
         if let Status::Running(checkpoint) = inner.status {
-            inner.elapsed += checkpoint.elapsed();
+            inner.elapsed += self.now() - checkpoint;
             inner.status = Status::Paused;
         }
     }
 
+    /// The elapsed time of the clock, excluding the time it was paused.
     pub fn elapsed(&self) -> Duration {
         let inner = self.inner.lock();
         match inner.status {
             Status::Paused => inner.elapsed,
-            Status::Running(checkpoint) => inner.elapsed + checkpoint.elapsed(),
+            Status::Running(checkpoint) => inner.elapsed + (self.now() - checkpoint),
         }
     }
 
+    /// Wait until the clock has moved forward by the given duration, which takes longer if it is paused.
     pub async fn sleep(&self, mut duration: Duration) {
         let wake_time = self.elapsed() + duration;
+        if let Some(manual) = &self.manual {
+            loop {
+                let advanced = manual.advanced.notified();
+                tokio::pin!(advanced);
+                advanced.as_mut().enable();
+                if self.elapsed() >= wake_time {
+                    return;
+                }
+                advanced.await;
+            }
+        }
         loop {
             time::sleep(duration).await;
             let elapsed = self.elapsed();
@@ -80,11 +143,13 @@ impl Clock {
         self.sleep(deadline - now).await;
     }
 
-    pub fn ticker(&self, duration: Duration) -> Ticker {
+    pub(crate) fn ticker(&self, duration: Duration) -> Ticker {
         Ticker::new(self.clone(), duration)
     }
 }
 
+/// The rate limiter sees the logical time, which stands still while the clock is paused, so no permits accrue
+/// during a pause and the rate resumes without a burst.
 #[cfg(feature = "rate_limit")]
 impl governor::clock::Clock for Clock {
     type Instant = std::time::Instant;
@@ -115,3 +180,89 @@ impl Ticker {
         self.next_tick += self.interval;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Whether the future completes without the clock moving any further.
+    async fn is_ready(fut: impl std::future::Future) -> bool {
+        time::timeout(Duration::ZERO, fut).await.is_ok()
+    }
+
+    #[test]
+    fn manual_clock_only_moves_when_advanced() {
+        let clock = Clock::manual();
+        assert_eq!(clock.elapsed(), Duration::ZERO);
+
+        clock.advance(Duration::from_millis(1500));
+        assert_eq!(clock.elapsed(), Duration::from_millis(1500));
+        assert_eq!(clock.clone().elapsed(), Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn paused_clock_stands_still() {
+        let clock = Clock::manual();
+        clock.advance(Duration::from_secs(1));
+        clock.pause();
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(clock.elapsed(), Duration::from_secs(1));
+
+        clock.resume();
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(clock.elapsed(), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn advance_has_no_effect_on_a_system_clock() {
+        let clock = Clock::start_at(Instant::now());
+        clock.pause();
+        clock.advance(Duration::from_secs(60));
+        assert!(clock.elapsed() < Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn sleep_waits_until_advanced_past_the_deadline() {
+        let clock = Clock::manual();
+        let sleep = clock.sleep(Duration::from_secs(1));
+        tokio::pin!(sleep);
+        assert!(!is_ready(&mut sleep).await);
+
+        clock.advance(Duration::from_millis(999));
+        assert!(!is_ready(&mut sleep).await);
+
+        clock.advance(Duration::from_millis(1));
+        assert!(is_ready(&mut sleep).await);
+    }
+
+    #[tokio::test]
+    async fn sleep_takes_longer_while_paused() {
+        let clock = Clock::manual();
+        let sleep = clock.sleep(Duration::from_secs(1));
+        tokio::pin!(sleep);
+        clock.pause();
+        clock.advance(Duration::from_secs(10));
+        assert!(!is_ready(&mut sleep).await);
+
+        clock.resume();
+        clock.advance(Duration::from_secs(1));
+        assert!(is_ready(&mut sleep).await);
+    }
+
+    #[tokio::test]
+    async fn ticker_ticks_once_per_interval() {
+        let clock = Clock::manual();
+        let mut ticker = clock.ticker(Duration::from_secs(1));
+        assert!(!is_ready(ticker.tick()).await);
+
+        // a late ticker catches up on the ticks it missed, one at a time
+        clock.advance(Duration::from_millis(3500));
+        for _ in 0..3 {
+            assert!(is_ready(ticker.tick()).await);
+        }
+        assert!(!is_ready(ticker.tick()).await);
+
+        clock.advance(Duration::from_millis(500));
+        assert!(is_ready(ticker.tick()).await);
+    }
+}
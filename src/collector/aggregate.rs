@@ -0,0 +1,592 @@
+use std::{borrow::Cow, collections::HashMap, sync::OnceLock};
+
+use anyhow::Result;
+use regex::Regex;
+use tokio::time::Duration;
+
+use crate::{
+    histogram::{HistogramConfig, LatencyHistogram},
+    report::{BenchReport, Discarded, IterReport, IterResult, RunMetadata, SuspectDurations},
+    runner::{BenchOpts, ErrorOpts},
+    stats::{Convergence, IterOutcome, IterStats, RateStats, SaturationDetector, Slowest, TimeSeries},
+    status::Status,
+};
+
+/// Key of the bucket which collects errors beyond [`ErrorOpts::max_kinds`].
+const OTHER_ERRORS: &str = "(other)";
+/// Significant digits of the per-worker histograms, which keeps them around 30 KiB each.
+const WORKER_SIGFIG: u8 = 2;
+
+/// Folds iteration results into the aggregated statistics of a benchmark report.
+///
+/// All the builtin collectors aggregate with it, and custom [`ReportCollector`](super::ReportCollector)s can
+/// use it to produce the same [`BenchReport`] from the results of a [`Runner`](crate::Runner).
+///
+/// # Examples
+///
+/// ```
+/// use rlt::{collector::ReportAggregator, BenchOpts, IterInfo, IterReport, IterResult, Status};
+/// use tokio::time::Duration;
+///
+/// let mut agg = ReportAggregator::new(&BenchOpts::default());
+/// let report = IterReport { bytes_in: 512, ..IterReport::new(Duration::from_millis(10), Status::success(200)) };
+/// let res = IterResult { info: IterInfo::new(0, 1), start: Duration::ZERO, result: Ok(report) };
+/// agg.ingest(res, Duration::from_millis(10)).unwrap();
+///
+/// let report = agg.into_report(Duration::from_secs(1), 1);
+/// assert_eq!(report.stats.counter.iters, 1);
+/// assert_eq!(report.stats.counter.bytes_in, 512);
+/// ```
+#[derive(Clone)]
+pub struct ReportAggregator {
+    pub(crate) hist: LatencyHistogram,
+    pub(crate) phase_hists: Vec<(Cow<'static, str>, LatencyHistogram)>,
+    pub(crate) item_hist: Option<LatencyHistogram>,
+    pub(crate) worker_hists: Option<Vec<LatencyHistogram>>,
+    pub(crate) stats: IterStats,
+    pub(crate) status_dist: HashMap<Status, u64>,
+    pub(crate) error_dist: HashMap<String, u64>,
+    pub(crate) error_samples: HashMap<String, Vec<String>>,
+    pub(crate) rate_stats: RateStats,
+    pub(crate) timeseries: Option<TimeSeries>,
+    pub(crate) slowest: Slowest,
+    /// Number of iterations whose status was counted under [`Status::other`].
+    pub(crate) folded_statuses: u64,
+    pub(crate) convergence: Option<Convergence>,
+    pub(crate) saturation: SaturationDetector,
+    pub(crate) suspect: SuspectDurations,
+    /// Start of the statistics.
+    origin: Duration,
+    /// The elapsed time at which the iterations stop being discarded, until it is reached.
+    discard_until: Option<Duration>,
+    /// The length of the discarded part of the statistics and their state at its end, once it is reached.
+    discarded: Option<(Duration, Box<ReportAggregator>)>,
+    opts: ErrorOpts,
+    status_max_kinds: usize,
+    redirect_is_success: bool,
+}
+
+impl ReportAggregator {
+    /// Create an aggregator for a benchmark with the given options.
+    pub fn new(opts: &BenchOpts) -> Self {
+        let hist = HistogramConfig {
+            expected_interval: opts.expected_interval,
+            ..Default::default()
+        };
+        #[cfg(feature = "rate_limit")]
+        let rate = opts.rate.map(|r| r.get() as f64);
+        #[cfg(not(feature = "rate_limit"))]
+        let rate = None;
+        Self {
+            hist: LatencyHistogram::with_config(hist),
+            phase_hists: Vec::new(),
+            item_hist: opts.per_item_latency.then(LatencyHistogram::new),
+            worker_hists: opts.per_worker_latency.then(Vec::new),
+            stats: IterStats::new(),
+            status_dist: HashMap::new(),
+            error_dist: HashMap::new(),
+            error_samples: HashMap::new(),
+            rate_stats: RateStats::default(),
+            opts: opts.errors,
+            timeseries: opts.record_timeseries.then(|| TimeSeries::new(Duration::ZERO)),
+            slowest: Slowest::new(opts.slowest),
+            folded_statuses: 0,
+            convergence: opts.until_stable.map(|o| Convergence::new(o, Duration::ZERO)),
+            saturation: SaturationDetector::new(rate, Duration::ZERO),
+            suspect: SuspectDurations {
+                zero: 0,
+                too_long: 0,
+                ceiling: opts.suspect_ceiling,
+                excluded: opts.exclude_suspect,
+            },
+            origin: Duration::ZERO,
+            discard_until: opts.discard_first,
+            discarded: None,
+            redirect_is_success: opts.redirect_is_success,
+            status_max_kinds: opts.status_max_kinds,
+        }
+    }
+
+    /// Align the per-second samples to the given elapsed time, e.g. when the statistics are reset.
+    ///
+    /// The report still takes the elapsed time of the benchmark clock, and covers the time since then.
+    pub(crate) fn since(mut self, elapsed: Duration) -> Self {
+        self.rate_stats = RateStats::new(elapsed);
+        if let Some(convergence) = &mut self.convergence {
+            *convergence = Convergence::new(convergence.opts(), elapsed);
+        }
+        self.saturation = SaturationDetector::new(self.saturation.rate(), elapsed);
+        self.origin = elapsed;
+        // a reset past the discarded part leaves nothing to discard
+        self.discard_until = self.discard_until.filter(|&until| until > elapsed);
+        if let Some(timeseries) = &mut self.timeseries {
+            *timeseries = TimeSeries::new(elapsed);
+        }
+        self
+    }
+
+    /// Ingest a single iteration result which arrived at the given elapsed time.
+    pub fn ingest(&mut self, res: IterResult, elapsed: Duration) -> Result<()> {
+        self.ingest_ref(&res, elapsed)
+    }
+
+    /// Ingest a result which is passed on afterwards, e.g. by a [`ReportSink`](super::ReportSink).
+    pub(crate) fn ingest_ref(&mut self, res: &IterResult, elapsed: Duration) -> Result<()> {
+        if let Some(until) = self.discard_until.filter(|&until| elapsed >= until) {
+            self.cut_over(until);
+        }
+        match &res.result {
+            Ok(report) => {
+                // only a folded status needs a copy of the report
+                let status = self.count_status(report.status);
+                let report = match status == report.status {
+                    true => Cow::Borrowed(report),
+                    false => Cow::Owned(IterReport { status, ..report.clone() }),
+                };
+                let excluded = self.count_suspect(report.duration);
+                if !excluded {
+                    self.hist.record(report.duration);
+                    self.record_worker(res.info.worker_id, report.duration);
+                }
+                for (name, duration) in report.phases.iter() {
+                    match self.phase_hists.iter_mut().find(|(n, _)| n == name) {
+                        Some((_, hist)) => hist.record(*duration),
+                        None => {
+                            let mut hist = LatencyHistogram::new();
+                            hist.record(*duration);
+                            self.phase_hists.push((name.clone(), hist));
+                        }
+                    }
+                }
+                if let Some(hist) = self.item_hist.as_mut().filter(|_| report.items > 0 && !excluded) {
+                    hist.record(report.duration.div_f64(report.items as f64));
+                }
+                self.stats += report.as_ref();
+                if !excluded {
+                    self.slowest.record(&res.info, &report);
+                }
+                self.rate_stats.record(elapsed);
+                if let Some(convergence) = &mut self.convergence {
+                    convergence.record(report.duration, elapsed);
+                }
+                let success = report.status.kind().is_success(self.redirect_is_success);
+                self.saturation.record(Some(report.duration), success, elapsed);
+                if let Some(timeseries) = &mut self.timeseries {
+                    timeseries.record(&report, elapsed);
+                }
+            }
+            Err(e) => {
+                if let Some(status) = e.status {
+                    self.count_status(status);
+                }
+                if let Some(duration) = e.duration.filter(|&d| !self.count_suspect(d)) {
+                    self.hist.record(duration);
+                    self.record_worker(res.info.worker_id, duration);
+                }
+                self.saturation.record(e.duration, false, elapsed);
+                if let Some(timeseries) = &mut self.timeseries {
+                    timeseries.record_error(elapsed);
+                }
+                self.ingest_error(&e.source);
+                self.stats += IterOutcome::Error;
+            }
+        }
+        Ok(())
+    }
+
+    /// Count a latency if it is suspect, and return whether it is left out of the histograms.
+    fn count_suspect(&mut self, duration: Duration) -> bool {
+        if duration.is_zero() {
+            self.suspect.zero += 1;
+        } else if duration > self.suspect.ceiling {
+            self.suspect.too_long += 1;
+        } else {
+            return false;
+        }
+        self.suspect.excluded
+    }
+
+    /// Record a latency in the histogram of the worker, if tracked.
+    fn record_worker(&mut self, worker: u32, duration: Duration) {
+        if let Some(hists) = &mut self.worker_hists {
+            let worker = worker as usize;
+            if hists.len() <= worker {
+                hists.resize_with(worker + 1, || LatencyHistogram::with_sigfig(WORKER_SIGFIG));
+            }
+            hists[worker].record(duration);
+        }
+    }
+
+    /// Count a status in the status distribution and return the status it was counted under.
+    fn count_status(&mut self, mut status: Status) -> Status {
+        if !self.status_dist.contains_key(&status) && self.status_dist.len() >= self.status_max_kinds {
+            status = Status::other(status.kind());
+            self.folded_statuses += 1;
+        }
+        *self.status_dist.entry(status).or_default() += 1;
+        status
+    }
+
+    fn ingest_error(&mut self, e: &anyhow::Error) {
+        let msg = e.to_string();
+        let mut key = self
+            .opts
+            .classifier
+            .and_then(|classify| classify(e))
+            .or_else(|| error_class(e))
+            .unwrap_or_else(|| error_key(&msg, &self.opts));
+        if !self.error_dist.contains_key(&key) && self.error_dist.len() >= self.opts.max_kinds {
+            key = OTHER_ERRORS.to_string();
+        }
+
+        let samples = self.error_samples.entry(key.clone()).or_default();
+        if samples.len() < self.opts.samples && !samples.contains(&msg) {
+            samples.push(msg);
+        }
+        *self.error_dist.entry(key).or_default() += 1;
+    }
+
+    /// Keep the state of the statistics at the end of the discarded part, to leave it out of the report.
+    ///
+    /// The per-second samples and the slowest iterations cannot be subtracted, so they start over instead.
+    fn cut_over(&mut self, until: Duration) {
+        self.discard_until = None;
+        self.discarded = Some((until - self.origin, Box::new(self.clone())));
+        self.rate_stats = RateStats::new(until);
+        if let Some(timeseries) = &mut self.timeseries {
+            *timeseries = TimeSeries::new(until);
+        }
+        self.slowest.clear();
+    }
+
+    /// Whether the run reached a steady state, see [`BenchOpts::until_stable`].
+    pub fn converged(&self) -> bool {
+        self.convergence.as_ref().is_some_and(|c| c.converged())
+    }
+
+    /// Total number of iterations which returned an error.
+    pub(crate) fn errors(&self) -> u64 {
+        self.error_dist.values().sum()
+    }
+
+    /// Remove the statistics of an earlier state of the aggregator.
+    ///
+    /// The counts saturate at zero, like those of [`Counter`](crate::stats::Counter), should the earlier state
+    /// hold more of something, e.g. of a status folded differently.
+    fn subtract(&mut self, earlier: &ReportAggregator) {
+        // the histograms share their configuration with the earlier ones, so the subtraction cannot fail
+        let _ = self.hist.subtract(&earlier.hist);
+        for (name, hist) in &earlier.phase_hists {
+            if let Some((_, ours)) = self.phase_hists.iter_mut().find(|(n, _)| n == name) {
+                let _ = ours.subtract(hist);
+            }
+        }
+        if let (Some(ours), Some(hist)) = (&mut self.item_hist, &earlier.item_hist) {
+            let _ = ours.subtract(hist);
+        }
+        if let (Some(ours), Some(hists)) = (&mut self.worker_hists, &earlier.worker_hists) {
+            for (ours, hist) in ours.iter_mut().zip(hists) {
+                let _ = ours.subtract(hist);
+            }
+        }
+        self.stats = &self.stats - &earlier.stats;
+        for (status, n) in &earlier.status_dist {
+            if let Some(count) = self.status_dist.get_mut(status) {
+                *count = count.saturating_sub(*n);
+            }
+        }
+        self.status_dist.retain(|_, n| *n > 0);
+        for (key, n) in &earlier.error_dist {
+            if let Some(count) = self.error_dist.get_mut(key) {
+                *count = count.saturating_sub(*n);
+            }
+        }
+        self.error_dist.retain(|_, n| *n > 0);
+        self.error_samples.retain(|key, _| self.error_dist.contains_key(key));
+        self.folded_statuses = self.folded_statuses.saturating_sub(earlier.folded_statuses);
+        self.suspect.zero = self.suspect.zero.saturating_sub(earlier.suspect.zero);
+        self.suspect.too_long = self.suspect.too_long.saturating_sub(earlier.suspect.too_long);
+    }
+
+    /// Consume the aggregator and generate the report of a benchmark which ran for the given elapsed time.
+    ///
+    /// The statistics of the part discarded by [`BenchOpts::discard_first`] are subtracted, if it was reached,
+    /// and its length is left out of the elapsed time of the report.
+    pub fn into_report(mut self, elapsed: Duration, concurrency: u32) -> BenchReport {
+        let converged = self.converged();
+        let saturation = self.saturation.saturation();
+        let discarded = self.discarded.take().map(|(duration, discarded)| {
+            self.subtract(&discarded);
+            Discarded {
+                duration,
+                iters: discarded.stats.counter.iters,
+                errors: discarded.stats.counter.errors,
+            }
+        });
+        let reported = elapsed
+            .saturating_sub(self.origin)
+            .saturating_sub(discarded.map_or(Duration::ZERO, |d| d.duration));
+        let Self {
+            hist,
+            phase_hists,
+            item_hist,
+            worker_hists,
+            stats,
+            status_dist,
+            error_dist,
+            error_samples,
+            mut rate_stats,
+            timeseries,
+            slowest,
+            folded_statuses,
+            suspect,
+            redirect_is_success,
+            ..
+        } = self;
+        rate_stats.advance(elapsed);
+        let timeseries = timeseries.map_or_else(Vec::new, |mut timeseries| {
+            timeseries.advance(elapsed);
+            timeseries.into_samples()
+        });
+        BenchReport {
+            concurrency,
+            hist,
+            phase_hists,
+            item_hist,
+            worker_hists,
+            stats,
+            status_dist,
+            folded_statuses,
+            error_dist,
+            error_samples,
+            rate_stats,
+            timeseries,
+            slowest: slowest.into_sorted(),
+            elapsed: reported,
+            reset_at: None,
+            paused_duration: Duration::ZERO,
+            pause_count: 0,
+            metadata: RunMetadata::default(),
+            redirect_is_success,
+            assertions: Vec::new(),
+            converged,
+            discarded,
+            saturation,
+            suspect: (suspect.total() > 0).then_some(suspect),
+            setup: None,
+            #[cfg(feature = "sysinfo")]
+            resource_usage: None,
+        }
+    }
+}
+
+/// Builtin classification of an error by the kind of the first [`std::io::Error`] in its chain.
+///
+/// Errors of the kind [`Other`](std::io::ErrorKind::Other) tell nothing beyond their message and are left
+/// unclassified.
+fn error_class(e: &anyhow::Error) -> Option<String> {
+    e.chain()
+        .find_map(|e| e.downcast_ref::<std::io::Error>())
+        .map(|e| e.kind())
+        .filter(|&kind| kind != std::io::ErrorKind::Other)
+        .map(|kind| format!("io: {kind}"))
+}
+
+/// Derive the key of an unclassified error message in the error distribution.
+fn error_key(msg: &str, opts: &ErrorOpts) -> String {
+    static VOLATILE: OnceLock<Regex> = OnceLock::new();
+
+    let msg = match opts.normalize {
+        true => VOLATILE
+            .get_or_init(|| {
+                Regex::new(concat!(
+                    r"(?<uuid>\b[[:xdigit:]]{8}-[[:xdigit:]]{4}-[[:xdigit:]]{4}-[[:xdigit:]]{4}-[[:xdigit:]]{12}\b)",
+                    r"|(?<hex>\b0x[[:xdigit:]]+\b",
+                    r"|\b[[:xdigit:]]*([0-9][[:xdigit:]]*[a-fA-F]|[a-fA-F][[:xdigit:]]*[0-9])[[:xdigit:]]*\b)",
+                    r"|(?<num>[0-9]+)",
+                ))
+                .expect("invalid error normalization regex")
+            })
+            .replace_all(msg, |caps: &regex::Captures| {
+                if caps.name("uuid").is_some() {
+                    "<uuid>"
+                } else if caps.name("hex").is_some() {
+                    "<hex>"
+                } else {
+                    "<n>"
+                }
+            }),
+        false => msg.into(),
+    };
+
+    match msg.char_indices().nth(opts.max_len) {
+        Some((i, _)) => format!("{}…", &msg[..i]),
+        None => msg.into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{report::Saturation, runner::IterInfo};
+
+    /// A successful iteration of the given duration, which started at the given elapsed time.
+    fn iteration(duration: Duration, start: Duration) -> IterResult {
+        let report = IterReport::new(duration, Status::success(200));
+        IterResult { info: IterInfo::new(0, 1), start, result: Ok(report) }
+    }
+
+    fn ingest_latencies(agg: &mut ReportAggregator, latencies: &[Duration]) {
+        for &latency in latencies {
+            agg.ingest(iteration(latency, Duration::ZERO), Duration::ZERO).unwrap();
+        }
+    }
+
+    #[test]
+    fn zero_latencies_are_clamped_and_counted_as_suspect() {
+        let mut agg = ReportAggregator::new(&BenchOpts::default());
+        ingest_latencies(&mut agg, &[Duration::ZERO, Duration::ZERO, Duration::from_millis(1)]);
+        let report = agg.into_report(Duration::from_secs(1), 1);
+
+        assert_eq!(report.hist.count(), 3);
+        assert_eq!(report.hist.zero_count(), 2);
+        assert_eq!(report.hist.min(), Duration::from_nanos(1));
+        let suspect = report.suspect.unwrap();
+        assert_eq!((suspect.zero, suspect.too_long, suspect.total()), (2, 0, 2));
+        assert!(!suspect.excluded);
+        assert_eq!(report.stats.counter.iters, 3);
+    }
+
+    #[test]
+    fn suspect_latencies_are_left_out_if_excluded() {
+        let opts = BenchOpts {
+            suspect_ceiling: Duration::from_secs(1),
+            exclude_suspect: true,
+            ..Default::default()
+        };
+        let mut agg = ReportAggregator::new(&opts);
+        let latencies = [
+            Duration::ZERO,
+            Duration::from_secs(2),
+            Duration::from_millis(1),
+            Duration::from_secs(1),
+        ];
+        ingest_latencies(&mut agg, &latencies);
+        let report = agg.into_report(Duration::from_secs(1), 1);
+
+        // the ceiling itself is not suspect
+        assert_eq!(report.hist.count(), 2);
+        assert_eq!(report.hist.zero_count(), 0);
+        assert!(report.hist.max() < Duration::from_millis(1001));
+        let suspect = report.suspect.unwrap();
+        assert_eq!((suspect.zero, suspect.too_long), (1, 1));
+        // the iterations still count
+        assert_eq!(report.stats.counter.iters, 4);
+    }
+
+    #[test]
+    fn no_suspect_latencies() {
+        let mut agg = ReportAggregator::new(&BenchOpts::default());
+        ingest_latencies(&mut agg, &[Duration::from_millis(1)]);
+        assert!(agg.into_report(Duration::from_secs(1), 1).suspect.is_none());
+    }
+
+    /// Ingest a stream of 100 iterations per second of the given latency, each arriving as it finishes.
+    fn ingest_stream(agg: &mut ReportAggregator, secs: std::ops::Range<u64>, latency: Duration, status: Status) {
+        for i in secs.start * 100..secs.end * 100 {
+            let start = Duration::from_millis(i * 10);
+            let report = IterReport::new(latency, status);
+            let res = IterResult { info: IterInfo::new(0, 1), start, result: Ok(report) };
+            agg.ingest(res, start + latency).unwrap();
+        }
+    }
+
+    #[test]
+    fn saturation_of_a_report_stream() {
+        let mut agg = ReportAggregator::new(&BenchOpts::default());
+        ingest_stream(&mut agg, 0..5, Duration::from_millis(10), Status::success(200));
+        ingest_stream(&mut agg, 5..11, Duration::from_millis(30), Status::success(200));
+        let report = agg.into_report(Duration::from_secs(11), 1);
+        assert!(matches!(report.saturation, Some(Saturation::LatencyGrowth { .. })));
+    }
+
+    #[test]
+    fn no_saturation_while_the_target_fails() {
+        let mut agg = ReportAggregator::new(&BenchOpts::default());
+        ingest_stream(&mut agg, 0..5, Duration::from_millis(10), Status::success(200));
+        ingest_stream(&mut agg, 5..11, Duration::from_millis(30), Status::server_error(503));
+        assert_eq!(agg.into_report(Duration::from_secs(11), 1).saturation, None);
+    }
+
+    #[cfg(feature = "rate_limit")]
+    #[test]
+    fn saturation_below_the_rate_limit() {
+        let opts = BenchOpts {
+            rate: Some(nonzero_ext::nonzero!(200u32)),
+            ..Default::default()
+        };
+        let mut agg = ReportAggregator::new(&opts);
+        ingest_stream(&mut agg, 0..11, Duration::from_millis(1), Status::success(200));
+        let saturation = agg.into_report(Duration::from_secs(11), 1).saturation;
+        assert_eq!(saturation, Some(Saturation::RateLag { achieved: 100.0, target: 200.0 }));
+    }
+
+    #[test]
+    fn per_second_samples_after_a_discard() {
+        let opts = BenchOpts {
+            discard_first: Some(Duration::from_secs(10)),
+            record_timeseries: true,
+            ..Default::default()
+        };
+        let mut agg = ReportAggregator::new(&opts);
+        ingest_stream(&mut agg, 0..30, Duration::from_millis(1), Status::success(200));
+        let report = agg.into_report(Duration::from_secs(30), 1);
+
+        assert_eq!(report.elapsed, Duration::from_secs(20));
+        assert_eq!(report.stats.counter.iters, 2000);
+        assert_eq!(report.rate_stats.samples(), 20);
+        assert_eq!(report.rate_stats.mean(), Some(100.0));
+        assert_eq!(report.rate_stats.stdev(), Some(0.0));
+        assert_eq!(report.timeseries.len(), 20);
+        assert!(report.timeseries.iter().all(|s| s.iters == 100));
+    }
+
+    #[test]
+    fn per_second_samples_after_a_reset() {
+        let opts = BenchOpts { record_timeseries: true, ..Default::default() };
+        // the statistics are reset after 10 seconds, the way the TUI does
+        let mut agg = ReportAggregator::new(&opts).since(Duration::from_secs(10));
+        ingest_stream(&mut agg, 10..25, Duration::from_millis(1), Status::success(200));
+        let report = agg.into_report(Duration::from_secs(25), 1);
+
+        assert_eq!(report.elapsed, Duration::from_secs(15));
+        assert_eq!(report.stats.counter.iters, 1500);
+        assert_eq!(report.rate_stats.samples(), 15);
+        assert_eq!(report.timeseries.len(), 15);
+    }
+
+    #[test]
+    fn subtracting_a_larger_state_saturates() {
+        let opts = BenchOpts { status_max_kinds: 1, ..Default::default() };
+        let mut later = ReportAggregator::new(&opts);
+        ingest_latencies(&mut later, &[Duration::ZERO]);
+        // more of everything than the later state, including a status folded for being beyond the first kind
+        let mut earlier = later.clone();
+        ingest_latencies(&mut earlier, &[Duration::ZERO, Duration::ZERO]);
+        let error = crate::report::IterError::from(anyhow::anyhow!("failed"));
+        let error = crate::report::IterError { status: Some(Status::server_error(500)), ..error };
+        let failed = IterResult {
+            info: IterInfo::new(0, 1),
+            start: Duration::ZERO,
+            result: Err(error),
+        };
+        earlier.ingest(failed, Duration::ZERO).unwrap();
+        assert_eq!(earlier.folded_statuses, 1);
+
+        later.subtract(&earlier);
+        assert!(later.status_dist.is_empty());
+        assert!(later.error_dist.is_empty());
+        assert_eq!((later.folded_statuses, later.suspect.zero), (0, 0));
+        assert_eq!(later.stats.counter.iters, 0);
+    }
+}
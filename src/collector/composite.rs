@@ -0,0 +1,58 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use super::{ReportCollector, ReportSink};
+use crate::report::{BenchReport, IterResult};
+
+/// A report collector which fans iteration results out to multiple sinks.
+///
+/// Every result is observed by the sinks first and then forwarded to the primary collector, whose
+/// [`BenchReport`] is returned.
+pub struct CompositeCollector {
+    primary: Box<dyn ReportCollector>,
+    sinks: Vec<Box<dyn ReportSink>>,
+    res_rx: UnboundedReceiver<IterResult>,
+    primary_tx: Option<UnboundedSender<IterResult>>,
+}
+
+impl CompositeCollector {
+    /// Create a new composite report collector.
+    ///
+    /// The `primary` function is called with the receiver the primary collector should consume.
+    pub fn new<F>(res_rx: UnboundedReceiver<IterResult>, sinks: Vec<Box<dyn ReportSink>>, primary: F) -> Result<Self>
+    where
+        F: FnOnce(UnboundedReceiver<IterResult>) -> Result<Box<dyn ReportCollector>>,
+    {
+        let (primary_tx, primary_rx) = mpsc::unbounded_channel();
+        let primary = primary(primary_rx)?;
+        Ok(Self { primary, sinks, res_rx, primary_tx: Some(primary_tx) })
+    }
+}
+
+#[async_trait]
+impl ReportCollector for CompositeCollector {
+    async fn run(&mut self) -> Result<BenchReport> {
+        let Self { primary, sinks, res_rx, primary_tx } = self;
+        let primary_tx = primary_tx.take();
+
+        let forward = async move {
+            let mut result = Ok(());
+            while let Some(res) = res_rx.recv().await {
+                if result.is_ok() {
+                    result = sinks.iter_mut().try_for_each(|sink| sink.on_report(&res));
+                }
+                if let Some(tx) = &primary_tx {
+                    // safe to ignore the error which means the primary collector is dropped
+                    let _ = tx.send(res);
+                }
+            }
+            drop(primary_tx);
+            result.and_then(|_| sinks.iter_mut().try_for_each(|sink| sink.finish()))
+        };
+
+        let (report, forwarded) = tokio::join!(primary.run(), forward);
+        forwarded?;
+        report
+    }
+}
@@ -0,0 +1,99 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    time::{Duration, Instant, SystemTime},
+};
+
+use anyhow::Result;
+
+use super::ReportSink;
+use crate::{
+    clock::Clock,
+    histogram::{HistogramConfig, LatencyHistogram},
+    report::IterResult,
+    runner::BenchOpts,
+};
+
+/// Length of the intervals of the log.
+const INTERVAL: Duration = Duration::from_secs(1);
+
+/// A report sink which writes the latencies of every second to a file in the HdrHistogram interval log format,
+/// as read by the HdrHistogram tooling, e.g. `HistogramLogProcessor` and the interval log plotters.
+///
+/// The intervals follow the wall clock from the creation of the sink, so the pauses of the benchmark show up as
+/// gaps between the timestamps of the intervals. Seconds without any latency are left out. Every interval is
+/// flushed once it ends, and the last one, which may be shorter, when the benchmark finishes or is cancelled.
+///
+/// The iterations left out of the report by [`BenchOpts::discard_first`] are left out of the log as well.
+/// Latencies are in nanoseconds.
+pub struct HdrLogSink {
+    clock: Clock,
+    writer: BufWriter<File>,
+    start: Instant,
+    discard_until: Option<Duration>,
+    config: HistogramConfig,
+    /// Start of the current interval, relative to the start of the log.
+    interval: Duration,
+    hist: LatencyHistogram,
+}
+
+impl HdrLogSink {
+    /// Create a new interval log sink for a benchmark with the given options, truncating the file if it exists.
+    pub fn create(path: impl AsRef<Path>, opts: &BenchOpts) -> Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        LatencyHistogram::begin_interval_log(&mut writer, SystemTime::now())?;
+        writer.flush()?;
+        let config = HistogramConfig {
+            expected_interval: opts.expected_interval,
+            ..Default::default()
+        };
+        Ok(Self {
+            clock: opts.clock.clone(),
+            writer,
+            start: Instant::now(),
+            discard_until: opts.discard_first,
+            config,
+            interval: Duration::ZERO,
+            hist: LatencyHistogram::with_config(config),
+        })
+    }
+
+    /// Write the current interval, if it has any latency, with the given length.
+    fn write_interval(&mut self, duration: Duration) -> Result<()> {
+        if !self.hist.is_empty() {
+            self.hist.write_interval(&mut self.writer, self.interval, duration)?;
+            self.writer.flush()?;
+            self.hist = LatencyHistogram::with_config(self.config);
+        }
+        Ok(())
+    }
+}
+
+impl ReportSink for HdrLogSink {
+    fn on_report(&mut self, res: &IterResult) -> Result<()> {
+        if self.discard_until.is_some_and(|until| self.clock.elapsed() < until) {
+            return Ok(());
+        }
+        let now = self.start.elapsed();
+        if now >= self.interval + INTERVAL {
+            self.write_interval(INTERVAL)?;
+            // skip the seconds without any latency, e.g. while paused
+            self.interval = Duration::from_secs(now.as_secs());
+        }
+        let latency = match &res.result {
+            Ok(report) => Some(report.duration),
+            Err(e) => e.duration,
+        };
+        if let Some(latency) = latency {
+            self.hist.record(latency);
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let duration = self.start.elapsed().saturating_sub(self.interval).min(INTERVAL);
+        self.write_interval(duration)?;
+        Ok(self.writer.flush()?)
+    }
+}
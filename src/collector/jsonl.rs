@@ -0,0 +1,339 @@
+use std::{
+    ffi::OsString,
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::ReportSink;
+use crate::{clock::Clock, report::IterResult};
+
+/// A report sink which streams every iteration result to a file in JSON Lines format.
+///
+/// Each line is a JSON object with the elapsed time of the benchmark (`elapsed`, in seconds) and either the
+/// iteration report (`duration`, `status`, `bytes`, `bytes_in`, `bytes_out`, `items`) or the `error` message. The
+/// `bytes` are the total, including the received and sent ones.
+///
+/// With [`rotate`](Self::rotate), the file is renamed to `<path>.1` once it grows beyond a size, shifting the
+/// older segments to `<path>.2` and so on, and a fresh file is started at the path. The renames happen between
+/// lines, so every segment is valid JSON Lines at any time. At the end, an index of the segments and their time
+/// ranges is written to `<path>.index.json`. [`read_stream`] reads the records back in order.
+pub struct JsonLinesSink {
+    clock: Clock,
+    path: PathBuf,
+    writer: BufWriter<File>,
+    rotation: Option<Rotation>,
+    /// Size of the active file, in bytes.
+    size: u64,
+    /// The segments kept, the active one first.
+    segments: Vec<Segment>,
+}
+
+/// When the stream file is rotated.
+#[derive(Clone, Copy, Debug)]
+struct Rotation {
+    max_size: u64,
+    keep: usize,
+}
+
+/// A segment of a rotated stream file, as listed by its index.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+struct Segment {
+    /// Elapsed time of the first and last record of the segment, in seconds.
+    from: Option<f64>,
+    to: Option<f64>,
+    lines: u64,
+}
+
+/// The index of a rotated stream file.
+#[derive(Serialize, Deserialize)]
+struct Index {
+    /// The segments, the oldest first.
+    segments: Vec<IndexEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IndexEntry {
+    path: PathBuf,
+    #[serde(flatten)]
+    segment: Segment,
+}
+
+impl JsonLinesSink {
+    /// Create a new JSON Lines sink writing to the given path, truncating the file if it exists.
+    pub fn create(path: impl AsRef<Path>, clock: Clock) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let writer = BufWriter::new(File::create(&path)?);
+        Ok(Self {
+            clock,
+            path,
+            writer,
+            rotation: None,
+            size: 0,
+            segments: vec![Segment::default()],
+        })
+    }
+
+    /// Rotate the file once it grows beyond `max_size` bytes, keeping up to `keep` rotated segments besides the
+    /// active one.
+    ///
+    /// The segments and the index left by a previous run at the same path are removed.
+    pub fn rotate(mut self, max_size: u64, keep: usize) -> Result<Self> {
+        for path in (1..).map(|n| segment_path(&self.path, n)).take_while(|p| p.exists()) {
+            std::fs::remove_file(path)?;
+        }
+        match std::fs::remove_file(index_path(&self.path)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => (),
+        }
+        self.rotation = Some(Rotation { max_size, keep });
+        Ok(self)
+    }
+
+    /// Move the active file to the first segment, shifting the older ones, and start a new active file.
+    fn rotate_now(&mut self, keep: usize) -> Result<()> {
+        self.writer.flush()?;
+        if keep == 0 {
+            self.segments.truncate(1);
+        } else {
+            self.segments.truncate(keep);
+            for n in (1..self.segments.len()).rev() {
+                std::fs::rename(segment_path(&self.path, n), segment_path(&self.path, n + 1))?;
+            }
+            std::fs::rename(&self.path, segment_path(&self.path, 1))?;
+        }
+        self.writer = BufWriter::new(File::create(&self.path)?);
+        self.size = 0;
+        self.segments.insert(0, Segment::default());
+        self.segments.truncate(keep + 1);
+        Ok(())
+    }
+
+    /// Write the index of the segments next to the file, replacing it atomically.
+    fn write_index(&self) -> Result<()> {
+        let segments = (0..self.segments.len())
+            .rev()
+            .map(|n| IndexEntry {
+                path: file_name(&segment_path(&self.path, n)),
+                segment: self.segments[n],
+            })
+            .collect();
+        let index = index_path(&self.path);
+        let mut tmp = index.clone().into_os_string();
+        tmp.push(".tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(&Index { segments })?)?;
+        std::fs::rename(&tmp, &index)?;
+        Ok(())
+    }
+}
+
+/// Path of the given segment of a stream file, the active one being 0.
+fn segment_path(path: &Path, n: usize) -> PathBuf {
+    match n {
+        0 => path.to_path_buf(),
+        n => {
+            let mut path = OsString::from(path);
+            path.push(format!(".{n}"));
+            path.into()
+        }
+    }
+}
+
+fn index_path(path: &Path) -> PathBuf {
+    let mut path = OsString::from(path);
+    path.push(".index.json");
+    path.into()
+}
+
+/// The file name of a segment, as listed in the index, which lives in the same directory.
+fn file_name(path: &Path) -> PathBuf {
+    path.file_name().map_or_else(|| path.to_path_buf(), PathBuf::from)
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Line {
+    Report {
+        elapsed: f64,
+        worker: u32,
+        duration: f64,
+        status: String,
+        bytes: u64,
+        bytes_in: u64,
+        bytes_out: u64,
+        items: u64,
+    },
+    Error {
+        elapsed: f64,
+        worker: u32,
+        error: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        status: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        duration: Option<f64>,
+    },
+}
+
+impl ReportSink for JsonLinesSink {
+    fn on_report(&mut self, res: &IterResult) -> Result<()> {
+        let elapsed = self.clock.elapsed().as_secs_f64();
+        let worker = res.info.worker_id;
+        let line = match &res.result {
+            Ok(r) => Line::Report {
+                elapsed,
+                worker,
+                duration: r.duration.as_secs_f64(),
+                status: r.status.to_string(),
+                bytes: r.total_bytes(),
+                bytes_in: r.bytes_in,
+                bytes_out: r.bytes_out,
+                items: r.items,
+            },
+            Err(e) => Line::Error {
+                elapsed,
+                worker,
+                error: e.to_string(),
+                status: e.status.map(|s| s.to_string()),
+                duration: e.duration.map(|d| d.as_secs_f64()),
+            },
+        };
+        if let Some(rotation) = self.rotation.filter(|r| self.size >= r.max_size) {
+            self.rotate_now(rotation.keep)?;
+        }
+        let mut buf = serde_json::to_vec(&line)?;
+        buf.push(b'\n');
+        self.writer.write_all(&buf)?;
+
+        self.size += buf.len() as u64;
+        let segment = &mut self.segments[0];
+        segment.from.get_or_insert(elapsed);
+        segment.to = Some(elapsed);
+        segment.lines += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        if self.rotation.is_some() {
+            self.write_index()?;
+        }
+        Ok(())
+    }
+}
+
+/// A record of a stream file written by [`JsonLinesSink`].
+#[derive(Clone, Debug, Deserialize)]
+pub struct StreamRecord {
+    /// Elapsed time of the benchmark when the result arrived, in seconds.
+    pub elapsed: f64,
+    /// The id of the worker which ran the iteration.
+    pub worker: u32,
+    /// The duration of the iteration in seconds, if known.
+    pub duration: Option<f64>,
+    /// The status of the iteration, if known.
+    pub status: Option<String>,
+    /// The total reported bytes of a successful iteration, including the received and sent ones.
+    pub bytes: Option<u64>,
+    /// The reported bytes received in a successful iteration, missing in streams of older versions.
+    pub bytes_in: Option<u64>,
+    /// The reported bytes sent in a successful iteration, missing in streams of older versions.
+    pub bytes_out: Option<u64>,
+    /// The reported items of a successful iteration.
+    pub items: Option<u64>,
+    /// The error message of a failed iteration.
+    pub error: Option<String>,
+}
+
+/// Read the records of a stream file written by [`JsonLinesSink`], including its rotated segments, the oldest
+/// first.
+///
+/// The segments are taken from the index of a finished run if there is one, and from the rotated files next to
+/// the path otherwise, e.g. while the run is still going.
+pub fn read_stream(path: impl AsRef<Path>) -> Result<Vec<StreamRecord>> {
+    let path = path.as_ref();
+    let segments = match std::fs::read(index_path(path)) {
+        Ok(index) => {
+            let index: Index = serde_json::from_slice(&index).context("invalid index")?;
+            let dir = path.parent().unwrap_or(Path::new(""));
+            index.segments.into_iter().map(|e| dir.join(e.path)).collect()
+        }
+        Err(_) => {
+            let rotated = (1..).map(|n| segment_path(path, n)).take_while(|p| p.exists()).count();
+            (0..=rotated).rev().map(|n| segment_path(path, n)).collect::<Vec<_>>()
+        }
+    };
+
+    let mut records = Vec::new();
+    for segment in segments {
+        let file = File::open(&segment).with_context(|| format!("failed to open {}", segment.display()))?;
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let record = serde_json::from_str(&line)
+                .with_context(|| format!("invalid record at {}:{}", segment.display(), i + 1))?;
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{report::IterReport, runner::IterInfo, status::Status};
+
+    #[test]
+    fn reads_back_every_line_of_a_rotated_stream() {
+        let dir = std::env::temp_dir().join(format!("rlt-stream-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("stream.jsonl");
+
+        let clock = Clock::manual();
+        let mut sink = JsonLinesSink::create(&path, clock.clone())
+            .unwrap()
+            .rotate(500, 100)
+            .unwrap();
+        for i in 0..50 {
+            clock.advance(Duration::from_millis(100));
+            let result = match i % 10 {
+                0 => Err(anyhow::anyhow!("connection reset").into()),
+                _ => Ok(IterReport {
+                    items: 1,
+                    ..IterReport::new(Duration::from_millis(i), Status::success(200))
+                }),
+            };
+            let info = IterInfo::new(i as u32 % 4, 4);
+            sink.on_report(&IterResult { info, start: clock.elapsed(), result })
+                .unwrap();
+        }
+        sink.finish().unwrap();
+
+        let index: Index = serde_json::from_slice(&std::fs::read(index_path(&path)).unwrap()).unwrap();
+        assert!(index.segments.len() > 2, "{} segments", index.segments.len());
+        assert_eq!(index.segments.last().unwrap().path, Path::new("stream.jsonl"));
+        assert_eq!(index.segments.iter().map(|e| e.segment.lines).sum::<u64>(), 50);
+
+        let records = read_stream(&path).unwrap();
+        assert_eq!(records.len(), 50);
+        for (i, record) in records.iter().enumerate() {
+            assert!((record.elapsed - (i + 1) as f64 * 0.1).abs() < 1e-9, "{record:?}");
+            assert_eq!(record.worker, i as u32 % 4);
+            match i % 10 {
+                0 => assert_eq!(record.error.as_deref(), Some("connection reset")),
+                _ => assert_eq!((record.items, record.error.as_deref()), (Some(1), None)),
+            }
+        }
+
+        // the rotated files are found without the index too
+        std::fs::remove_file(index_path(&path)).unwrap();
+        assert_eq!(read_stream(&path).unwrap().len(), 50);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,17 +1,62 @@
 //! This module defines a trait for collecting iteration results.
+mod aggregate;
+mod composite;
+mod hdrlog;
+mod jsonl;
+mod progress;
 mod silent;
-mod tui;
+pub(crate) mod tui;
 
+use anyhow::Result;
 use async_trait::async_trait;
 
+pub use aggregate::ReportAggregator;
+pub use composite::CompositeCollector;
+pub use hdrlog::HdrLogSink;
+pub use jsonl::{read_stream, JsonLinesSink, StreamRecord};
+pub use progress::ProgressCollector;
 pub use silent::SilentCollector;
 pub use tui::TuiCollector;
 
-use crate::report::BenchReport;
+use crate::report::{BenchReport, IterResult};
 
 /// A trait for collecting iteration results.
 #[async_trait]
+#[allow(clippy::double_must_use)]
 pub trait ReportCollector: Send + Sync {
     /// Run the collector and generate a benchmark report.
     async fn run(&mut self) -> anyhow::Result<BenchReport>;
 }
+
+/// A trait for observing iteration results alongside a [`ReportCollector`].
+///
+/// Sinks do not own the report stream, so several of them can be composed with a single
+/// collector by [`CompositeCollector`].
+pub trait ReportSink: Send + Sync {
+    /// Observe a single iteration result.
+    fn on_report(&mut self, res: &IterResult) -> Result<()>;
+
+    /// Called once after the last iteration result has been observed.
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Completes when the process receives a shutdown signal.
+///
+/// This is Ctrl-C on all platforms, plus SIGTERM and SIGHUP on unix.
+pub(crate) async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        if let (Ok(mut term), Ok(mut hup)) = (signal(SignalKind::terminate()), signal(SignalKind::hangup())) {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => (),
+                _ = term.recv() => (),
+                _ = hup.recv() => (),
+            }
+            return;
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+}
@@ -0,0 +1,121 @@
+use std::{
+    io::{self, IsTerminal, Write},
+    num::NonZeroU8,
+};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::{sync::mpsc::UnboundedReceiver, time::Duration};
+use tokio_util::sync::CancellationToken;
+
+use super::aggregate::ReportAggregator;
+use crate::{
+    report::{BenchReport, IterResult},
+    runner::{BenchOpts, CancelReason},
+    stats::RotateDiffWindowGroup,
+};
+
+const SECOND: Duration = Duration::from_secs(1);
+
+/// A report collector that prints a single-line progress indicator to stderr.
+///
+/// When stderr is a terminal the line is updated in place, otherwise a plain line is printed at
+/// most once per second.
+pub struct ProgressCollector {
+    bench_opts: BenchOpts,
+    fps: NonZeroU8,
+    res_rx: UnboundedReceiver<IterResult>,
+    cancel: CancellationToken,
+}
+
+impl ProgressCollector {
+    /// Create a new progress report collector.
+    pub fn new(
+        bench_opts: BenchOpts,
+        fps: NonZeroU8,
+        res_rx: UnboundedReceiver<IterResult>,
+        cancel: CancellationToken,
+    ) -> Self {
+        Self { bench_opts, fps, res_rx, cancel }
+    }
+
+    fn render(&self, agg: &ReportAggregator, latest_stats: &RotateDiffWindowGroup) -> String {
+        let elapsed = self.bench_opts.clock.elapsed();
+        let iters = agg.stats.counter.iters;
+        let (last_sec, duration) = latest_stats.stats_last_sec();
+        let rate = last_sec.counter.iters as f64 / duration.as_secs_f64();
+
+        let progress = match self.bench_opts.progress(iters, elapsed) {
+            Some(ratio) => format!("[{:>5.1}%] ", 100.0 * ratio),
+            None => String::new(),
+        };
+        let elapsed = humantime::Duration::from(Duration::from_secs(elapsed.as_secs()));
+        let rate = if rate.is_finite() { rate } else { 0.0 };
+        format!(
+            "{progress}{elapsed} | {iters} iters | {rate:.2} iters/s | {} errors",
+            agg.errors()
+        )
+    }
+}
+
+#[async_trait]
+impl super::ReportCollector for ProgressCollector {
+    async fn run(&mut self) -> Result<BenchReport> {
+        let mut agg = ReportAggregator::new(&self.bench_opts);
+        let clock = self.bench_opts.clock.clone();
+        let is_tty = io::stderr().is_terminal();
+
+        let mut latest_stats = RotateDiffWindowGroup::new(self.fps.into());
+        let mut latest_stats_ticker = clock.ticker(SECOND / self.fps.get() as u32);
+
+        let interval = match is_tty {
+            true => SECOND / self.fps.get() as u32,
+            false => SECOND,
+        };
+        let mut ui_ticker = tokio::time::interval(interval);
+        ui_ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        let mut stderr = io::stderr();
+        let mut cancelled_by = None;
+        let shutdown = super::shutdown_signal();
+        tokio::pin!(shutdown);
+        loop {
+            tokio::select! {
+                biased;
+                _ = &mut shutdown, if !self.cancel.is_cancelled() => {
+                    cancelled_by = Some(CancelReason::Signal);
+                    self.cancel.cancel();
+                }
+                _ = ui_ticker.tick() => {
+                    let line = self.render(&agg, &latest_stats);
+                    match is_tty {
+                        true => write!(stderr, "\r{line}\x1b[K")?,
+                        false => writeln!(stderr, "{line}")?,
+                    }
+                }
+                _ = latest_stats_ticker.tick() => latest_stats.rotate(&agg.stats),
+                r = self.res_rx.recv() => match r {
+                    Some(r) => {
+                        agg.ingest(r, clock.elapsed())?;
+                        if agg.converged() {
+                            self.cancel.cancel();
+                        }
+                    }
+                    None => break,
+                },
+            }
+        }
+
+        let line = self.render(&agg, &latest_stats);
+        match is_tty {
+            true => writeln!(stderr, "\r{line}\x1b[K")?,
+            false => writeln!(stderr, "{line}")?,
+        }
+
+        let elapsed = self.bench_opts.clock.elapsed();
+        let concurrency = self.bench_opts.concurrency;
+        let mut report = agg.into_report(elapsed, concurrency);
+        report.metadata.cancelled_by = cancelled_by;
+        Ok(report)
+    }
+}
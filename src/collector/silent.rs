@@ -1,31 +1,23 @@
-use std::collections::HashMap;
-
-use anyhow::Result;
 use async_trait::async_trait;
 use tokio::sync::mpsc::UnboundedReceiver;
 use tokio_util::sync::CancellationToken;
 
+use super::aggregate::ReportAggregator;
 use crate::{
-    histogram::LatencyHistogram,
-    report::{BenchReport, IterReport},
-    runner::BenchOpts,
-    stats::IterStats,
+    report::{BenchReport, IterResult},
+    runner::{BenchOpts, CancelReason},
 };
 
 /// A silent report collector that does not print anything.
 pub struct SilentCollector {
     bench_opts: BenchOpts,
-    res_rx: UnboundedReceiver<Result<IterReport>>,
+    res_rx: UnboundedReceiver<IterResult>,
     cancel: CancellationToken,
 }
 
 impl SilentCollector {
     /// Create a new silent report collector.
-    pub fn new(
-        bench_opts: BenchOpts,
-        res_rx: UnboundedReceiver<Result<IterReport>>,
-        cancel: CancellationToken,
-    ) -> Self {
+    pub fn new(bench_opts: BenchOpts, res_rx: UnboundedReceiver<IterResult>, cancel: CancellationToken) -> Self {
         Self { bench_opts, res_rx, cancel }
     }
 }
@@ -33,22 +25,25 @@ impl SilentCollector {
 #[async_trait]
 impl super::ReportCollector for SilentCollector {
     async fn run(&mut self) -> anyhow::Result<BenchReport> {
-        let mut hist = LatencyHistogram::new();
-        let mut stats = IterStats::new();
-        let mut status_dist = HashMap::default();
-        let mut error_dist = HashMap::default();
+        let mut agg = ReportAggregator::new(&self.bench_opts);
 
+        let mut cancelled_by = None;
+        let shutdown = super::shutdown_signal();
+        tokio::pin!(shutdown);
         loop {
             tokio::select! {
                 biased;
-                _ = tokio::signal::ctrl_c() => self.cancel.cancel(),
+                _ = &mut shutdown, if !self.cancel.is_cancelled() => {
+                    cancelled_by = Some(CancelReason::Signal);
+                    self.cancel.cancel();
+                }
                 r = self.res_rx.recv() => match r {
-                    Some(Ok(report)) => {
-                        *status_dist.entry(report.status).or_default() += 1;
-                        hist.record(report.duration)?;
-                        stats += &report;
+                    Some(r) => {
+                        agg.ingest(r, self.bench_opts.clock.elapsed())?;
+                        if agg.converged() {
+                            self.cancel.cancel();
+                        }
                     }
-                    Some(Err(e)) => *error_dist.entry(e.to_string()).or_default() += 1,
                     None => break,
                 },
             }
@@ -56,6 +51,8 @@ impl super::ReportCollector for SilentCollector {
 
         let elapsed = self.bench_opts.clock.elapsed();
         let concurrency = self.bench_opts.concurrency;
-        Ok(BenchReport { concurrency, hist, stats, status_dist, error_dist, elapsed })
+        let mut report = agg.into_report(elapsed, concurrency);
+        report.metadata.cancelled_by = cancelled_by;
+        Ok(report)
     }
 }
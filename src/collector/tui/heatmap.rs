@@ -0,0 +1,113 @@
+use std::collections::VecDeque;
+
+use super::*;
+
+/// Number of seconds kept for the heatmap.
+const HEATMAP_SECONDS: usize = 300;
+
+/// Colors used for the cells, from the lowest to the highest count.
+const PALETTE: [Color; 5] = [Color::Blue, Color::Cyan, Color::Green, Color::Yellow, Color::Red];
+
+/// Width of the latency labels on the left of the heatmap.
+const LABEL_WIDTH: u16 = 8;
+
+pub(crate) struct HeatmapState {
+    pub(crate) display: bool,
+    /// Latency histograms of the last seconds, the current one first.
+    hists: VecDeque<LatencyHistogram>,
+}
+
+impl HeatmapState {
+    pub(crate) fn new() -> Self {
+        Self {
+            display: false,
+            hists: VecDeque::from([LatencyHistogram::new()]),
+        }
+    }
+
+    pub(crate) fn record(&mut self, latency: Duration) {
+        self.hists[0].record(latency);
+    }
+
+    /// Start a new one second column.
+    pub(crate) fn rotate(&mut self) {
+        self.hists.push_front(LatencyHistogram::new());
+        self.hists.truncate(HEATMAP_SECONDS);
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.hists = VecDeque::from([LatencyHistogram::new()]);
+    }
+}
+
+/// Render the latency distribution over time, one column per second with log-scaled latency buckets.
+pub(crate) fn render_heatmap(frame: &mut Frame, area: Rect, state: &HeatmapState, opts: DisplayOpts) {
+    let non_empty = || state.hists.iter().filter(|h| !h.is_empty());
+    let lo = non_empty()
+        .map(|h| h.min())
+        .min()
+        .unwrap_or_default()
+        .max(Duration::from_nanos(1));
+    let hi = non_empty().map(|h| h.max()).max().unwrap_or_default().max(lo * 2);
+    let u = hi.appropriate_unit();
+
+    let block = Block::new()
+        .title(Title::from(Line::from(vec![
+            "Latency heatmap (".into(),
+            u.to_string().yellow().bold(),
+            ")".into(),
+        ])))
+        .borders(Borders::ALL);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if non_empty().next().is_none() || inner.height < 3 || inner.width <= LABEL_WIDTH {
+        return;
+    }
+
+    let [plot, legend] = Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+    let rows = plot.height as usize;
+    let cols = ((plot.width - LABEL_WIDTH) as usize).min(state.hists.len());
+
+    // bucket boundaries, log-scaled between the lowest and the highest observed latency
+    let ratio = hi.as_secs_f64() / lo.as_secs_f64();
+    let edges = (0..=rows)
+        .map(|i| lo.mul_f64(ratio.powf(i as f64 / rows as f64)))
+        .collect_vec();
+
+    // counts[col][row], with the oldest second on the left and the lowest latency at the bottom
+    let counts = state
+        .hists
+        .iter()
+        .take(cols)
+        .rev()
+        .map(|h| (0..rows).map(|r| h.count_between(edges[r], edges[r + 1])).collect_vec())
+        .collect_vec();
+    let max = counts.iter().flatten().copied().max().unwrap_or(0).max(1);
+
+    let lines = (0..rows)
+        .rev()
+        .map(|r| {
+            let label = match r {
+                _ if r == rows - 1 => format!("{:>7.2} ", edges[rows].as_f64(u)),
+                _ if r == rows / 2 => format!("{:>7.2} ", edges[r].as_f64(u)),
+                0 => format!("{:>7.2} ", edges[0].as_f64(u)),
+                _ => " ".repeat(LABEL_WIDTH as usize),
+            };
+            let mut spans = vec![label.cyan()];
+            spans.extend(counts.iter().map(|col| match col[r] {
+                0 => " ".into(),
+                n => {
+                    let level = (n * PALETTE.len() as u64).div_ceil(max) as usize;
+                    opts.cell().fg(PALETTE[level.clamp(1, PALETTE.len()) - 1])
+                }
+            }));
+            Line::from(spans)
+        })
+        .collect_vec();
+    frame.render_widget(Paragraph::new(lines), plot);
+
+    let mut spans = vec![" ".repeat(LABEL_WIDTH as usize).into(), "1 ".into()];
+    spans.extend(PALETTE.iter().map(|&c| opts.cell().fg(c)));
+    spans.push(format!(" {max} iters per cell").into());
+    frame.render_widget(Line::from(spans), legend);
+}
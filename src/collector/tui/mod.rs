@@ -0,0 +1,1577 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use itertools::Itertools;
+use nonzero_ext::nonzero;
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Margin, Rect},
+    style::{Color, Style, Stylize},
+    symbols,
+    text::{Line, Span},
+    widgets::{
+        block::Title, Bar, BarChart, BarGroup, Block, Borders, Clear, Gauge, Padding, Paragraph, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Sparkline, Wrap,
+    },
+    Frame,
+};
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::File,
+    io::{BufWriter, Write},
+    num::NonZeroU8,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+use tokio::{
+    sync::{mpsc, watch},
+    task::JoinHandle,
+    time::{Instant, MissedTickBehavior},
+};
+use tokio_util::sync::CancellationToken;
+
+mod heatmap;
+mod slowest;
+mod terminal;
+#[cfg(feature = "tracing")]
+mod tui_log;
+mod workers;
+
+#[cfg(feature = "tracing")]
+pub use tui_log::{init_tracing, tracing_subscriber_layer, TuiTracingLayer, DEFAULT_LOG_BUFFER};
+#[cfg(feature = "tracing")]
+pub(crate) use tui_log::{set_capture as set_log_capture, LogFile};
+
+use heatmap::HeatmapState;
+use terminal::Terminal;
+use workers::WorkersState;
+
+use crate::{
+    collector::{aggregate::ReportAggregator, ReportCollector},
+    duration::DurationExt,
+    histogram::{LatencyHistogram, PERCENTAGES},
+    pause::PauseControl,
+    report::{BenchReport, IterResult, SetupStats},
+    reporter::BenchReporter,
+    runner::{BenchOpts, Bound, CancelReason, InFlight},
+    stats::{Counter, IterStats, LatencyWindowGroup, RotateDiffWindowGroup, RotateWindow, RotateWindowGroup},
+    status::{Status, StatusKind},
+    util::{ByteUnit, IntoAdjustedByte, TryIntoAdjustedByte},
+};
+
+const SECOND: Duration = Duration::from_secs(1);
+const NOTICE_TIMEOUT: Duration = Duration::from_secs(3);
+/// Minimum terminal size to render the dashboard.
+const MIN_WIDTH: u16 = 50;
+const MIN_HEIGHT: u16 = 18;
+/// The adaptive refresh rate backs off while drawing takes more than this share of a frame...
+const DRAW_BUDGET: f64 = 0.2;
+/// ...down to this refresh interval, i.e. 2 fps.
+const MAX_FRAME_INTERVAL: Duration = Duration::from_millis(500);
+/// Number of lines to scroll the distribution panels by with the page keys.
+const DIST_PAGE: usize = 5;
+/// The achieved rate is considered lagging when it stays below this ratio of the rate limit...
+#[cfg(feature = "rate_limit")]
+const RATE_LAG_RATIO: f64 = 0.9;
+/// ...for at least this long.
+#[cfg(feature = "rate_limit")]
+const RATE_LAG_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// A report collector with real-time TUI support.
+pub struct TuiCollector {
+    /// The benchmark options.
+    pub bench_opts: BenchOpts,
+    /// Refresh rate for the tui collector, in frames per second (fps)
+    pub fps: NonZeroU8,
+    /// Treat [`fps`](Self::fps) as the maximum refresh rate and back off while drawing is expensive, so the TUI
+    /// does not compete with the workers for the CPU.
+    pub adaptive_fps: bool,
+    /// The receiver for iteration reports.
+    pub res_rx: mpsc::UnboundedReceiver<IterResult>,
+    /// The pause control of the benchmark runner.
+    pub pause: Arc<PauseControl>,
+    /// The cancellation token for the benchmark runner.
+    pub cancel: CancellationToken,
+    /// Whether to quit the benchmark automatically when finished.
+    pub auto_quit: bool,
+    /// The unit convention used to display byte sizes.
+    pub byte_unit: ByteUnit,
+    /// Draw the charts with ASCII characters only.
+    pub ascii: bool,
+    /// The reporter used to write snapshots of the current statistics.
+    pub reporter: Arc<dyn BenchReporter + Send + Sync>,
+    /// Caption shown next to the title of the progress block, e.g. the current step of a sweep.
+    pub caption: Option<String>,
+    /// Draw on the terminal device at this path, e.g. `/dev/pts/7`, and read the keys from it, instead of the
+    /// terminal of the process.
+    pub tty: Option<PathBuf>,
+    /// The setup of the workers, shown on the bottom border of the progress block until all of them finished.
+    pub(crate) setup: Option<watch::Receiver<SetupStats>>,
+    /// The iterations in flight, shown next to the title of the progress block while the benchmark runs.
+    pub(crate) in_flight: Option<InFlight>,
+    /// The latest resource usage of the process, shown on the bottom border of the progress block.
+    #[cfg(feature = "sysinfo")]
+    pub(crate) resources: Option<crate::resource::LatestSample>,
+
+    /// The internal state of the TUI collector.
+    state: TuiCollectorState,
+}
+
+struct TuiCollectorState {
+    tm_win: TimeWindow,
+    finished: bool,
+    /// What cancelled the benchmark from the TUI, if anything did.
+    cancelled_by: Option<CancelReason>,
+    /// Set by the input handler when the user asks to reset the statistics.
+    reset_requested: bool,
+    /// The elapsed time of the benchmark when the statistics were last reset.
+    reset_at: Option<Duration>,
+    /// Set by the input handler when the user asks for a snapshot report.
+    snapshot_requested: bool,
+    /// The in-flight snapshot write, if any.
+    snapshot: Option<JoinHandle<Result<PathBuf>>>,
+    /// A transient message shown in place of the tips.
+    notice: Option<(String, Instant)>,
+    /// The per-worker statistics panel.
+    workers: WorkersState,
+    /// Whether the slowest iterations panel is shown.
+    slowest: bool,
+    /// The latency heatmap, shown in place of the latency histogram.
+    heatmap: HeatmapState,
+    /// The metric plotted by the iteration histogram.
+    iter_metric: IterMetric,
+    /// Whether the iteration histogram scales the bars logarithmically.
+    iter_log_scale: bool,
+    /// The phase shown by the latency histogram, 1-based, or the whole iteration if 0. The per-item latency
+    /// follows the phases, if tracked.
+    latency_phase: usize,
+    /// The distribution panel which receives the scroll keys, if any.
+    focus: Option<DistPanel>,
+    /// The status kind shown by the status distribution panel, or all kinds if `None`.
+    status_filter: Option<StatusKind>,
+    status_offset: usize,
+    error_offset: usize,
+    /// Since when the achieved rate has been falling behind the rate limit.
+    #[cfg(feature = "rate_limit")]
+    rate_lag_since: Option<Instant>,
+    #[cfg(feature = "tracing")]
+    log: tui_log::LogState,
+}
+
+impl TuiCollector {
+    /// Create a new TUI report collector.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        bench_opts: BenchOpts,
+        fps: NonZeroU8,
+        res_rx: mpsc::UnboundedReceiver<IterResult>,
+        pause: Arc<PauseControl>,
+        cancel: CancellationToken,
+        auto_quit: bool,
+        byte_unit: ByteUnit,
+        ascii: bool,
+        reporter: Arc<dyn BenchReporter + Send + Sync>,
+    ) -> Result<Self> {
+        let state = TuiCollectorState {
+            tm_win: TimeWindow::Second,
+            finished: false,
+            cancelled_by: None,
+            reset_requested: false,
+            reset_at: None,
+            snapshot_requested: false,
+            snapshot: None,
+            notice: None,
+            workers: WorkersState::new(bench_opts.concurrency),
+            slowest: false,
+            heatmap: HeatmapState::new(),
+            iter_metric: IterMetric::Iters,
+            iter_log_scale: false,
+            latency_phase: 0,
+            focus: None,
+            status_filter: None,
+            status_offset: 0,
+            error_offset: 0,
+            #[cfg(feature = "rate_limit")]
+            rate_lag_since: None,
+            #[cfg(feature = "tracing")]
+            log: tui_log::LogState::from_env()?,
+        };
+        Ok(Self {
+            bench_opts,
+            fps,
+            adaptive_fps: false,
+            res_rx,
+            pause,
+            cancel,
+            auto_quit,
+            byte_unit,
+            ascii,
+            reporter,
+            caption: None,
+            tty: None,
+            setup: None,
+            in_flight: None,
+            #[cfg(feature = "sysinfo")]
+            resources: None,
+            state,
+        })
+    }
+}
+
+impl TuiCollector {
+    fn display_opts(&self) -> DisplayOpts {
+        DisplayOpts {
+            byte_unit: self.byte_unit,
+            ascii: self.ascii,
+            redirect_is_success: self.bench_opts.redirect_is_success,
+        }
+    }
+}
+
+#[async_trait]
+impl ReportCollector for TuiCollector {
+    async fn run(&mut self) -> Result<BenchReport> {
+        let mut agg = ReportAggregator::new(&self.bench_opts);
+        self.collect(&mut agg).await?;
+
+        let reset_at = self.state.reset_at;
+        let mut report = agg.into_report(self.bench_opts.clock.elapsed(), self.bench_opts.concurrency);
+        report.reset_at = reset_at;
+        report.paused_duration = self.pause.paused_duration();
+        report.pause_count = self.pause.pause_count();
+        report.metadata.cancelled_by = self.state.cancelled_by;
+        Ok(report)
+    }
+}
+
+impl TuiCollector {
+    async fn collect(&mut self, agg: &mut ReportAggregator) -> Result<()> {
+        let clock = self.bench_opts.clock.clone();
+        let mut terminal = Terminal::new(self.tty.as_deref())?;
+
+        let mut latest_iters = RotateWindowGroup::new(nonzero!(60usize), clock.elapsed());
+        let mut latest_latency = LatencyWindowGroup::new();
+        let mut latest_iters_ticker = clock.ticker(SECOND);
+
+        let mut latest_stats = RotateDiffWindowGroup::new(self.fps.into());
+        let mut latest_stats_ticker = clock.ticker(SECOND / self.fps.get() as u32);
+
+        // total iterations of the run, which survives the statistics resets
+        let mut iters_done = 0;
+
+        let mut frame_rate = FrameRate::new(self.fps, self.adaptive_fps);
+        let mut ui_ticker = tokio::time::interval(frame_rate.interval);
+        ui_ticker.set_missed_tick_behavior(MissedTickBehavior::Burst);
+
+        let shutdown = super::shutdown_signal();
+        tokio::pin!(shutdown);
+
+        loop {
+            if self.state.finished {
+                if self.auto_quit {
+                    return Ok(());
+                }
+                ui_ticker.tick().await;
+            } else {
+                loop {
+                    tokio::select! {
+                        biased;
+                        _ = ui_ticker.tick() => break,
+                        _ = &mut shutdown, if !self.cancel.is_cancelled() => {
+                            // drain the remaining reports and quit once the runner stops
+                            self.auto_quit = true;
+                            self.state.cancelled_by = Some(CancelReason::Signal);
+                            self.cancel.cancel();
+                        }
+                        _ = latest_stats_ticker.tick() => {
+                            latest_stats.rotate(&agg.stats);
+                            continue;
+                        }
+                        _ = latest_iters_ticker.tick() => {
+                            latest_iters.rotate(clock.elapsed());
+                            latest_latency.rotate();
+                            self.state.workers.rotate();
+                            self.state.heatmap.rotate();
+                            continue;
+                        }
+                        r = self.res_rx.recv() => match r {
+                            Some(res) => {
+                                latest_iters.push(&res);
+                                if let Ok(report) = &res.result {
+                                    latest_latency.record(report.duration);
+                                    self.state.heatmap.record(report.duration);
+                                    iters_done += 1;
+                                }
+                                self.state.workers.push(&res);
+                                agg.ingest(res, clock.elapsed())?;
+                                if agg.converged() {
+                                    self.cancel.cancel();
+                                }
+                            }
+                            None => {
+                                clock.pause();
+                                self.state.finished = true;
+                                break;
+                            }
+                        }
+                    };
+                }
+            }
+
+            let elapsed = clock.elapsed();
+            if self.handle_event(&mut terminal, elapsed).await? {
+                return Ok(());
+            }
+
+            if std::mem::take(&mut self.state.reset_requested) {
+                *agg = ReportAggregator::new(&self.bench_opts).since(elapsed);
+                latest_iters = RotateWindowGroup::new(nonzero!(60usize), elapsed);
+                latest_latency = LatencyWindowGroup::new();
+                latest_stats = RotateDiffWindowGroup::new(self.fps.into());
+                self.state.workers.reset();
+                self.state.heatmap.reset();
+                self.state.reset_at = Some(elapsed);
+            }
+            // the overall stats only cover the time since the last reset
+            let since_reset = elapsed - self.state.reset_at.unwrap_or_default();
+
+            if std::mem::take(&mut self.state.snapshot_requested) && self.state.snapshot.is_none() {
+                let mut report = agg.clone().into_report(elapsed, self.bench_opts.concurrency);
+                report.reset_at = self.state.reset_at;
+                report.paused_duration = self.pause.paused_duration();
+                report.pause_count = self.pause.pause_count();
+                self.state.snapshot = Some(write_snapshot(self.reporter.clone(), report));
+            }
+            if let Some(snapshot) = self.state.snapshot.take_if(|h| h.is_finished()) {
+                let notice = match snapshot.await.map_err(anyhow::Error::from).and_then(|r| r) {
+                    Ok(path) => format!("Snapshot saved to {}", path.display()),
+                    Err(e) => format!("Failed to save snapshot: {e}"),
+                };
+                self.state.notice = Some((notice, Instant::now()));
+            }
+            let notice = self.state.notice.as_ref().filter(|(_, t)| t.elapsed() < NOTICE_TIMEOUT);
+
+            #[cfg(feature = "rate_limit")]
+            let rate_limit = self.bench_opts.rate.map(|limit| {
+                let (stats, duration) = latest_stats.stats_last_sec();
+                let achieved = stats.counter.iters as f64 / duration.as_secs_f64();
+                let lagging = achieved < limit.get() as f64 * RATE_LAG_RATIO;
+                match self.state.rate_lag_since {
+                    _ if !lagging || self.pause.is_paused() || self.state.finished => self.state.rate_lag_since = None,
+                    None => self.state.rate_lag_since = Some(Instant::now()),
+                    Some(_) => (),
+                }
+                let lagging = self
+                    .state
+                    .rate_lag_since
+                    .is_some_and(|t| t.elapsed() >= RATE_LAG_TIMEOUT);
+                (achieved, limit, lagging)
+            });
+
+            let opts = self.display_opts();
+            if self.state.latency_phase > agg.phase_hists.len() + agg.item_hist.is_some() as usize {
+                self.state.latency_phase = 0;
+            }
+            let latency = match (self.state.latency_phase, &agg.item_hist) {
+                (0, _) => (None, &agg.hist),
+                (i, Some(hist)) if i > agg.phase_hists.len() => (Some("per item"), hist),
+                (i, _) => (Some(agg.phase_hists[i - 1].0.as_ref()), &agg.phase_hists[i - 1].1),
+            };
+            let bounds = self.bench_opts.duration.is_some() as u16 + self.bench_opts.iterations.is_some() as u16;
+            let fps = frame_rate.adaptive.then(|| frame_rate.fps());
+            let draw_start = Instant::now();
+            terminal.draw(|f| {
+                let Some(layout) = DashboardLayout::new(f.size(), agg.error_dist.len(), bounds) else {
+                    render_too_small(f);
+                    return;
+                };
+
+                let finished = self.state.finished;
+                let rate = {
+                    let (stats, duration) = latest_stats.stats_last_10sec();
+                    stats.counter.iters as f64 / duration.as_secs_f64()
+                };
+                render_process_gauge(
+                    f,
+                    layout.progress,
+                    iters_done,
+                    elapsed,
+                    rate,
+                    &self.bench_opts,
+                    &self.pause,
+                    finished,
+                    self.caption.as_deref(),
+                    self.in_flight.as_ref().filter(|_| !finished).map(InFlight::get),
+                );
+                #[cfg(feature = "rate_limit")]
+                if let Some((achieved, limit, lagging)) = rate_limit {
+                    render_rate_limit(f, layout.progress, achieved, limit, lagging);
+                }
+                if let Some(saturation) = agg.saturation.saturation() {
+                    render_saturation(f, layout.progress, saturation);
+                }
+                if let Some(setup) = self.setup.as_ref().map(|s| *s.borrow()) {
+                    if setup.workers < self.bench_opts.concurrency && !finished {
+                        render_setup(f, layout.progress, setup, self.bench_opts.concurrency);
+                    }
+                }
+                #[cfg(feature = "sysinfo")]
+                if let Some(sample) = self.resources.as_ref().and_then(|r| *r.lock()) {
+                    render_resource_usage(f, layout.progress, sample, opts.byte_unit);
+                }
+                render_stats_overall(f, layout.stats_overall, &agg.stats, since_reset, opts, layout.compact);
+                let history = layout.sparklines.then_some(&latest_iters);
+                render_stats_timewin(
+                    f,
+                    layout.stats_timewin,
+                    &latest_stats,
+                    history,
+                    &latest_latency,
+                    self.state.tm_win,
+                    opts,
+                    layout.compact,
+                );
+                let focus = self.state.focus;
+                if let Some(area) = layout.status_dist {
+                    render_status_dist(
+                        f,
+                        area,
+                        &agg.status_dist,
+                        self.state.status_filter,
+                        &mut self.state.status_offset,
+                        focus == Some(DistPanel::Status),
+                    );
+                }
+                render_error_dist(
+                    f,
+                    layout.error_dist,
+                    &agg.error_dist,
+                    &mut self.state.error_offset,
+                    focus == Some(DistPanel::Errors),
+                );
+                if let Some(area) = layout.iter_hist {
+                    let (tw, metric, log_scale) =
+                        (self.state.tm_win, self.state.iter_metric, self.state.iter_log_scale);
+                    render_iter_hist(f, area, &latest_iters, tw, metric, log_scale, opts);
+                }
+                match self.state.heatmap.display {
+                    true => heatmap::render_heatmap(f, layout.latency, &self.state.heatmap, opts),
+                    false => render_latency_hist(f, layout.latency, latency.1, latency.0, agg.suspect.total(), 7, opts),
+                }
+                render_tips(f, layout.tips, notice.map(|(msg, _)| msg.as_str()), fps);
+
+                workers::render_workers(f, &mut self.state.workers);
+                if self.state.slowest {
+                    slowest::render_slowest(f, &agg.slowest, self.bench_opts.slowest > 0);
+                }
+                #[cfg(feature = "tracing")]
+                tui_log::render_logs(f, &self.state.log);
+            })?;
+            if frame_rate.record(draw_start.elapsed()) {
+                ui_ticker = tokio::time::interval_at(Instant::now() + frame_rate.interval, frame_rate.interval);
+                ui_ticker.set_missed_tick_behavior(MissedTickBehavior::Burst);
+            }
+        }
+    }
+
+    /// Handle the user input events. Returns `true` if the collector should quit.
+    async fn handle_event(&mut self, terminal: &mut Terminal, elapsed: Duration) -> Result<bool> {
+        while let Some(event) = terminal.poll_event()? {
+            use KeyCode::*;
+            if let Event::Key(KeyEvent { code, modifiers, .. }) = event {
+                match (code, modifiers) {
+                    #[cfg(feature = "tracing")]
+                    (code, KeyModifiers::NONE | KeyModifiers::SHIFT) if self.state.log.editing() => {
+                        self.state.log.edit(code)
+                    }
+                    (Char('+'), _) => {
+                        self.state.tm_win = self.state.tm_win.prev();
+                    }
+                    (Char('-'), _) => {
+                        self.state.tm_win = self.state.tm_win.next();
+                    }
+                    (Char('a'), _) => {
+                        self.state.tm_win = *TimeWindow::variants()
+                            .iter()
+                            .rfind(|&&ts| elapsed > ts.into())
+                            .unwrap_or(&TimeWindow::Second)
+                    }
+                    (Char('q'), _) | (Char('c'), KeyModifiers::CONTROL) => {
+                        if !self.state.finished && !self.cancel.is_cancelled() {
+                            self.state.cancelled_by = Some(CancelReason::User);
+                        }
+                        self.cancel.cancel();
+                        return Ok(true);
+                    }
+                    (Char('p') | Pause, _) if !self.state.finished => self.pause.toggle(),
+                    (Char('r'), _) if !self.state.finished => self.state.reset_requested = true,
+                    (Char('s'), _) => self.state.snapshot_requested = true,
+                    (Char('w'), _) => self.state.workers.display = !self.state.workers.display,
+                    (Char('o'), _) => self.state.slowest = !self.state.slowest,
+                    (Char('h'), _) => self.state.heatmap.display = !self.state.heatmap.display,
+                    (Char('m'), _) => self.state.iter_metric = self.state.iter_metric.next(),
+                    (Char('y'), _) => self.state.iter_log_scale = !self.state.iter_log_scale,
+                    (Char('t'), _) => self.state.latency_phase += 1,
+                    (Char('c'), _) => {
+                        self.state.status_filter = next_status_filter(self.state.status_filter);
+                        self.state.status_offset = 0;
+                    }
+                    (Tab, _) => {
+                        self.state.focus = match self.state.focus {
+                            None => Some(DistPanel::Status),
+                            Some(DistPanel::Status) => Some(DistPanel::Errors),
+                            Some(DistPanel::Errors) => None,
+                        }
+                    }
+                    #[cfg(feature = "tracing")]
+                    (Char('l'), _) => self.state.log.display = !self.state.log.display,
+                    #[cfg(feature = "tracing")]
+                    (code, _) if self.state.log.display => {
+                        use tui_logger::TuiWidgetEvent::*;
+                        let log = &mut self.state.log;
+                        match code {
+                            Char('/') => log.start_search(),
+                            Char(c @ '0'..='5') => log.set_level(c),
+                            Esc if log.clear_search() => (),
+                            Char(' ') => log.inner.transition(HideKey),
+                            PageDown | Char('f') => log.inner.transition(NextPageKey),
+                            PageUp | Char('b') => log.inner.transition(PrevPageKey),
+                            Up => log.inner.transition(UpKey),
+                            Down => log.inner.transition(DownKey),
+                            Left => log.inner.transition(LeftKey),
+                            Right => log.inner.transition(RightKey),
+                            Enter => log.inner.transition(FocusKey),
+                            Esc => log.inner.transition(EscapeKey),
+                            _ => (),
+                        }
+                    }
+                    (code, _) if self.state.workers.display => match code {
+                        Up | Char('k') => self.state.workers.scroll(-1),
+                        Down | Char('j') => self.state.workers.scroll(1),
+                        PageUp | Char('b') => self.state.workers.scroll_page(-1),
+                        PageDown | Char('f') => self.state.workers.scroll_page(1),
+                        _ => (),
+                    },
+                    (code, _) if self.state.focus.is_some() => {
+                        let offset = match self.state.focus {
+                            Some(DistPanel::Status) => &mut self.state.status_offset,
+                            _ => &mut self.state.error_offset,
+                        };
+                        match code {
+                            Up | Char('k') => *offset = offset.saturating_sub(1),
+                            Down | Char('j') => *offset += 1,
+                            PageUp | Char('b') => *offset = offset.saturating_sub(DIST_PAGE),
+                            PageDown | Char('f') => *offset += DIST_PAGE,
+                            Home => *offset = 0,
+                            Esc => self.state.focus = None,
+                            _ => (),
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// The refresh interval of the TUI, which adapts to the cost of drawing if enabled.
+struct FrameRate {
+    /// The interval of the configured refresh rate, which is also the shortest one.
+    nominal: Duration,
+    interval: Duration,
+    adaptive: bool,
+    /// Exponential moving average of the time to draw a frame.
+    draw_time: Duration,
+}
+
+impl FrameRate {
+    fn new(fps: NonZeroU8, adaptive: bool) -> Self {
+        let nominal = SECOND / fps.get() as u32;
+        Self {
+            nominal,
+            interval: nominal,
+            adaptive,
+            draw_time: Duration::ZERO,
+        }
+    }
+
+    /// The current refresh rate, in frames per second.
+    fn fps(&self) -> f64 {
+        1.0 / self.interval.as_secs_f64()
+    }
+
+    /// Account the time a frame took to draw. Returns `true` if the refresh interval changed.
+    ///
+    /// The interval doubles while drawing takes more than the budget of a frame and halves while it takes less
+    /// than half of it, so it settles instead of flipping between two rates.
+    fn record(&mut self, draw_time: Duration) -> bool {
+        if !self.adaptive {
+            return false;
+        }
+        self.draw_time = (self.draw_time * 4 + draw_time) / 5;
+        let share = self.draw_time.as_secs_f64() / self.interval.as_secs_f64();
+        let interval = if share > DRAW_BUDGET {
+            (self.interval * 2).min(MAX_FRAME_INTERVAL.max(self.nominal))
+        } else if share < DRAW_BUDGET / 2.0 {
+            (self.interval / 2).max(self.nominal)
+        } else {
+            self.interval
+        };
+        std::mem::replace(&mut self.interval, interval) != interval
+    }
+}
+
+/// Areas of the dashboard panels. Lower priority panels are dropped on small terminals.
+struct DashboardLayout {
+    stats_timewin: Rect,
+    stats_overall: Rect,
+    status_dist: Option<Rect>,
+    error_dist: Rect,
+    iter_hist: Option<Rect>,
+    latency: Rect,
+    progress: Rect,
+    tips: Rect,
+    /// Whether the rolling stats have room for the sparklines under the rates.
+    sparklines: bool,
+    /// Whether the stats blocks are too narrow to put the rates beside the counters.
+    compact: bool,
+}
+
+impl DashboardLayout {
+    /// Lay out the dashboard in the given area, or `None` if the area is too small for it.
+    ///
+    /// The progress block gets a row for each of the given number of bounds of the run.
+    fn new(area: Rect, error_kinds: usize, bounds: u16) -> Option<Self> {
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            return None;
+        }
+
+        // drop the iteration histogram first and then the status distribution as the terminal shrinks
+        let iter_hist = area.width >= 120 && area.height >= 24;
+        let status_dist = area.width >= 100;
+        // the stats blocks switch to a single column when they are too narrow for two
+        let compact = area.width < if status_dist { 120 } else { 80 };
+        let sparklines = !compact && area.height >= 30;
+        let stats_height = if compact || sparklines { 8 } else { 5 };
+        // a gauge for each bound of the run
+        let progress_height = 2 + bounds.max(1);
+        let tips_height = 1;
+        // keep some rows for the histograms before showing the errors
+        let error_rows = (area.height - stats_height - progress_height - tips_height).saturating_sub(8);
+        let error_dist_height = match (error_kinds as u16).min(5).min(error_rows) {
+            0 => 0,
+            rows => rows + 2,
+        };
+        let [stats, error_dist, hists, progress, tips] = Layout::vertical([
+            Constraint::Length(stats_height),
+            Constraint::Length(error_dist_height),
+            Constraint::Fill(1),
+            Constraint::Length(progress_height),
+            Constraint::Length(tips_height),
+        ])
+        .areas(area);
+
+        let (stats_timewin, stats_overall, status_dist) = if status_dist {
+            let [timewin, overall, status] = Layout::horizontal([Constraint::Ratio(1, 3); 3]).areas(stats);
+            (timewin, overall, Some(status))
+        } else {
+            let [timewin, overall] = Layout::horizontal([Constraint::Ratio(1, 2); 2]).areas(stats);
+            (timewin, overall, None)
+        };
+
+        let (iter_hist, latency) = if iter_hist {
+            let [iter_hist, latency] = Layout::horizontal([Constraint::Percentage(50); 2]).areas(hists);
+            (Some(iter_hist), latency)
+        } else {
+            (None, hists)
+        };
+
+        Some(Self {
+            stats_timewin,
+            stats_overall,
+            status_dist,
+            error_dist,
+            iter_hist,
+            latency,
+            progress,
+            tips,
+            sparklines,
+            compact,
+        })
+    }
+}
+
+fn render_too_small(frame: &mut Frame) {
+    let area = frame.size();
+    let msg = Paragraph::new(vec![
+        Line::from("Terminal too small".red().bold()),
+        Line::from(format!(
+            "need ≥ {MIN_WIDTH}x{MIN_HEIGHT}, got {}x{}",
+            area.width, area.height
+        )),
+    ])
+    .centered()
+    .wrap(Wrap { trim: true });
+    let [area] = Layout::vertical([Constraint::Length(2)]).flex(Flex::Center).areas(area);
+    frame.render_widget(msg, area);
+}
+
+/// Write the snapshot report to a timestamped file in the current directory.
+fn write_snapshot(reporter: Arc<dyn BenchReporter + Send + Sync>, report: BenchReport) -> JoinHandle<Result<PathBuf>> {
+    tokio::task::spawn_blocking(move || {
+        let ts = humantime::format_rfc3339_seconds(SystemTime::now()).to_string();
+        let path = PathBuf::from(format!(
+            "rlt-snapshot-{}.{}",
+            ts.replace([':', '-'], ""),
+            reporter.file_extension()
+        ));
+        let mut w = BufWriter::new(File::create(&path)?);
+        reporter.print(&mut w, &report)?;
+        w.flush()?;
+        Ok(path)
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_stats_timewin(
+    frame: &mut Frame,
+    area: Rect,
+    stats: &RotateDiffWindowGroup,
+    history: Option<&RotateWindowGroup>,
+    latency: &LatencyWindowGroup,
+    tw: TimeWindow,
+    opts: DisplayOpts,
+    compact: bool,
+) {
+    let (stats, duration) = match tw {
+        TimeWindow::Second => stats.stats_last_sec(),
+        TimeWindow::TenSec => stats.stats_last_10sec(),
+        TimeWindow::Minute => stats.stats_last_min(),
+        TimeWindow::TenMin => stats.stats_last_10min(),
+    };
+    let history = history.map(|h| tw.window(h));
+
+    render_stats(
+        frame,
+        area,
+        Title::from(Line::from(vec![
+            "Stats for ".into(),
+            format!("last {}", tw).yellow().bold(),
+        ])),
+        &stats,
+        duration,
+        history,
+        opts,
+        compact,
+    );
+
+    // latency summary of the window on the right of the top border
+    let hist = latency.latency_last(tw as u64);
+    if !hist.is_empty() {
+        let u = hist.median().appropriate_unit();
+        let title = Title::from(Line::from(vec![
+            " p50 ".into(),
+            format!("{:.2}{u}", hist.median().as_f64(u)).green().bold(),
+            " p99 ".into(),
+            format!("{:.2}{u} ", hist.value_at_quantile(0.99).as_f64(u))
+                .green()
+                .bold(),
+        ]))
+        .alignment(Alignment::Right);
+        frame.render_widget(Block::new().title(title), area.inner(Margin::new(1, 0)));
+    }
+}
+
+fn render_stats_overall(
+    frame: &mut Frame,
+    area: Rect,
+    stats: &IterStats,
+    elapsed: Duration,
+    opts: DisplayOpts,
+    compact: bool,
+) {
+    render_stats(frame, area, "Stats overall".into(), stats, elapsed, None, opts, compact);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_stats(
+    frame: &mut Frame,
+    area: Rect,
+    title: Title,
+    stats: &IterStats,
+    elapsed: Duration,
+    history: Option<&RotateWindow>,
+    opts: DisplayOpts,
+    compact: bool,
+) {
+    let error_ratio = if stats.total() == 0 {
+        None
+    } else {
+        Some(stats.error_ratio(opts.redirect_is_success))
+    };
+    let error_rate = match elapsed.is_zero() {
+        true => "--".to_string(),
+        false => format!("{:.2}/s", stats.counter.errors as f64 / elapsed.as_secs_f64()),
+    };
+    let block = Block::new()
+        .title(title)
+        .title_bottom(Line::from(vec![
+            " Errors: ".into(),
+            render_error_ratio(error_ratio),
+            " (".into(),
+            error_rate.into(),
+            ") ".into(),
+        ]))
+        .borders(Borders::ALL);
+    let counter = &stats.counter;
+
+    let inner = block.inner(area);
+    let [lhs, rhs] = match compact {
+        true => Layout::vertical([Constraint::Length(3), Constraint::Length(3)]).areas(inner),
+        false => Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(inner),
+    };
+
+    let stats_counter = render_stats_counter(counter, opts.byte_unit);
+    let stats_rate = render_stats_rate(counter, elapsed, opts.byte_unit);
+
+    frame.render_widget(stats_counter, lhs);
+    match history {
+        Some(history) => {
+            let rows = Layout::vertical([Constraint::Length(1); 6]).split(rhs);
+            let width = rhs.width as usize;
+            let metrics: [fn(&Counter) -> u64; 3] = [|c| c.iters, |c| c.items, |c| c.bytes];
+            for (i, (line, metric)) in stats_rate.into_iter().zip(metrics).enumerate() {
+                let samples = history.history().map(|s| metric(&s.counter)).collect_vec();
+                let samples = &samples[samples.len().saturating_sub(width)..];
+                frame.render_widget(Paragraph::new(line), rows[2 * i]);
+                frame.render_widget(
+                    Sparkline::default()
+                        .data(samples)
+                        .bar_set(opts.bar_set())
+                        .style(Color::Green),
+                    rows[2 * i + 1],
+                );
+            }
+        }
+        None => frame.render_widget(Paragraph::new(stats_rate), rhs),
+    }
+    frame.render_widget(block, area);
+}
+
+/// Colored consistently with the success ratio of the text reporter.
+fn render_error_ratio(error_ratio: Option<f64>) -> Span<'static> {
+    let Some(ratio) = error_ratio else {
+        return "--".into();
+    };
+    let text = format!("{:.2}%", 100.0 * ratio);
+    if ratio <= 0.0 {
+        text.green().bold()
+    } else if ratio <= 0.01 {
+        text.yellow().bold()
+    } else {
+        text.red().bold()
+    }
+}
+
+fn render_stats_counter(counter: &Counter, byte_unit: ByteUnit) -> Paragraph<'static> {
+    let mut lines = vec![
+        Line::from(vec!["Items: ".into(), counter.items.to_string().green()]),
+        Line::from(vec!["Iters: ".into(), counter.iters.to_string().green()]),
+        Line::from(vec![
+            "Bytes: ".into(),
+            format!("{:.2}", counter.bytes.adjusted(byte_unit)).green(),
+        ]),
+    ];
+    if counter.has_directions() {
+        lines.push(Line::from(vec![
+            "  In: ".into(),
+            format!("{:.2}", counter.bytes_in.adjusted(byte_unit)).green(),
+        ]));
+        lines.push(Line::from(vec![
+            " Out: ".into(),
+            format!("{:.2}", counter.bytes_out.adjusted(byte_unit)).green(),
+        ]));
+    }
+    Paragraph::new(lines).block(Block::new().borders(Borders::NONE))
+}
+
+fn render_stats_rate(counter: &Counter, elapsed: Duration, byte_unit: ByteUnit) -> Vec<Line<'static>> {
+    let secs = elapsed.as_secs_f64();
+    let rate = |bytes: u64| match (bytes as f64 / secs).adjusted(byte_unit) {
+        Ok(bps) => format!("{:.2}/s", bps),
+        Err(_) => "NaN B/s".to_string(),
+    };
+    let mut lines = vec![
+        Line::from(format!("{:.2} iters/s", counter.iters as f64 / secs).green()),
+        Line::from(format!("{:.2} items/s", counter.items as f64 / secs).green()),
+        Line::from(rate(counter.bytes).green()),
+    ];
+    // only shown by the panel without history, which has room for them
+    if counter.has_directions() {
+        lines.push(Line::from(format!("{} in", rate(counter.bytes_in)).green()));
+        lines.push(Line::from(format!("{} out", rate(counter.bytes_out)).green()));
+    }
+    lines
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_process_gauge(
+    frame: &mut Frame,
+    area: Rect,
+    iters_done: u64,
+    elapsed: Duration,
+    rate: f64,
+    opts: &BenchOpts,
+    pause: &PauseControl,
+    finished: bool,
+    caption: Option<&str>,
+    in_flight: Option<u32>,
+) {
+    let rounded = |duration: Duration| humantime::Duration::from(Duration::from_secs(duration.as_secs_f64() as u64));
+    let time_progress = |duration: &Duration| {
+        (
+            (elapsed.as_secs_f64() / duration.as_secs_f64()).clamp(0.0, 1.0),
+            format!("{} / {}", rounded(elapsed), rounded(*duration)),
+        )
+    };
+    let iter_progress = |iters: &u64| {
+        (
+            (iters_done as f64 / *iters as f64).clamp(0.0, 1.0),
+            format!("{} / {}", iters_done, iters),
+        )
+    };
+
+    // with both bounds set, each gets a gauge of its own and the one predicted to end the run is highlighted
+    let first = opts.first_bound(iters_done, elapsed, rate);
+    let mut gauges = match opts {
+        BenchOpts { duration: None, iterations: None, .. } => vec![(0.0, "INFINITE".to_string(), true)],
+        BenchOpts { duration: Some(duration), iterations: None, .. } => {
+            let (progress, label) = time_progress(duration);
+            vec![(progress, label, true)]
+        }
+        BenchOpts { duration: None, iterations: Some(iters), .. } => {
+            let (progress, label) = iter_progress(iters);
+            vec![(progress, label, true)]
+        }
+        BenchOpts { duration: Some(duration), iterations: Some(iters), .. } => {
+            let (time_ratio, time_label) = time_progress(duration);
+            let (iter_ratio, iter_label) = iter_progress(iters);
+            vec![
+                (
+                    time_ratio,
+                    format!("time: {time_label}"),
+                    first == Some(Bound::Duration),
+                ),
+                (
+                    iter_ratio,
+                    format!("iters: {iter_label}"),
+                    first == Some(Bound::Iterations),
+                ),
+            ]
+        }
+    };
+
+    let paused = pause.is_paused();
+    let paused_summary = || match pause.pause_count() {
+        0 => String::new(),
+        n => format!(" | paused {} ({n}×)", rounded(pause.paused_duration())),
+    };
+    let (suffix, style) = match (finished, paused) {
+        (true, _) => (
+            format!(" (FINISHED){}", paused_summary()),
+            Style::new().fg(Color::Yellow),
+        ),
+        (_, true) => (format!(" (PAUSED){}", paused_summary()), Style::new().fg(Color::Yellow)),
+        (false, false) => {
+            let mut suffix = String::new();
+            if opts.duration.is_some() || opts.iterations.is_some() {
+                let eta = opts.eta(iters_done, elapsed, rate);
+                suffix = format!(" | ETA {}", eta.map_or("--".into(), |eta| rounded(eta).to_string()));
+            }
+            (suffix, Style::new().fg(Color::Cyan))
+        }
+    };
+    if let Some((_, label, _)) = gauges.iter_mut().find(|(_, _, highlighted)| *highlighted) {
+        label.push_str(&suffix);
+    }
+
+    let mut title = match caption {
+        Some(caption) => Line::from(vec!["Progress ".into(), caption.yellow().bold()]),
+        None => Line::from("Progress"),
+    };
+    if let Some(in_flight) = in_flight {
+        title.spans.extend([
+            " | in-flight: ".into(),
+            format!("{in_flight}/{}", opts.concurrency).cyan().bold(),
+        ]);
+    }
+    let block = Block::new().title(title).borders(Borders::ALL);
+    let rows = Layout::vertical(vec![Constraint::Length(1); gauges.len()]).split(block.inner(area));
+    frame.render_widget(block, area);
+    for ((progress, label, highlighted), row) in gauges.into_iter().zip(rows.iter()) {
+        let guage = Gauge::default()
+            .gauge_style(if highlighted {
+                style
+            } else {
+                Style::new().fg(Color::DarkGray)
+            })
+            .label(label)
+            .ratio(progress);
+        frame.render_widget(guage, *row);
+    }
+}
+
+/// Render the achieved rate against the configured rate limit on the border of the progress block.
+#[cfg(feature = "rate_limit")]
+fn render_rate_limit(frame: &mut Frame, area: Rect, achieved: f64, limit: std::num::NonZeroU32, lagging: bool) {
+    let achieved = format!("{:.1}", if achieved.is_finite() { achieved } else { 0.0 });
+    let line = Line::from(vec![
+        " rate: ".into(),
+        if lagging {
+            achieved.red().bold()
+        } else {
+            achieved.green().bold()
+        },
+        format!("/{} ips ", limit).into(),
+    ])
+    .right_aligned();
+    let area = Rect { height: 1, ..area.inner(Margin::new(1, 0)) };
+    frame.render_widget(line, area);
+}
+
+/// Render the warning about a saturated load generator on the bottom border of the progress block.
+fn render_saturation(frame: &mut Frame, area: Rect, saturation: crate::Saturation) {
+    let line = Line::from(format!(" ⚠ {saturation} ").yellow().bold());
+    let area = area.inner(Margin::new(1, 0));
+    let area = Rect { y: area.bottom().saturating_sub(1), height: 1, ..area };
+    frame.render_widget(line, area);
+}
+
+/// Render the progress of the setup of the workers on the bottom border of the progress block.
+fn render_setup(frame: &mut Frame, area: Rect, setup: SetupStats, workers: u32) {
+    let mut spans = vec![
+        " setup: ".into(),
+        format!("{}/{workers}", setup.workers).cyan().bold(),
+        " workers".into(),
+    ];
+    if setup.workers > 0 {
+        spans.extend([
+            ", slowest ".into(),
+            format!("{:.1}s", setup.max.as_secs_f64()).cyan().bold(),
+        ]);
+    }
+    spans.push(" ".into());
+    let area = area.inner(Margin::new(1, 0));
+    let area = Rect { y: area.bottom().saturating_sub(1), height: 1, ..area };
+    frame.render_widget(Line::from(spans), area);
+}
+
+/// Render the latest resource usage of the process on the bottom border of the progress block.
+#[cfg(feature = "sysinfo")]
+fn render_resource_usage(frame: &mut Frame, area: Rect, sample: crate::ResourceSample, byte_unit: ByteUnit) {
+    let line = Line::from(vec![
+        " cpu: ".into(),
+        format!("{:.1}%", sample.cpu).green().bold(),
+        " rss: ".into(),
+        format!("{:.1} ", sample.rss.adjusted(byte_unit)).green().bold(),
+    ])
+    .right_aligned();
+    let area = area.inner(Margin::new(1, 0));
+    let area = Rect { y: area.bottom().saturating_sub(1), height: 1, ..area };
+    frame.render_widget(line, area);
+}
+
+fn render_status_dist(
+    frame: &mut Frame,
+    area: Rect,
+    status_dist: &HashMap<Status, u64>,
+    filter: Option<StatusKind>,
+    offset: &mut usize,
+    focused: bool,
+) {
+    let (shown, hidden): (Vec<_>, Vec<_>) = status_dist
+        .iter()
+        .partition(|(status, _)| filter.is_none_or(|kind| status.kind() == kind));
+    let mut dist = shown
+        .into_iter()
+        .sorted_by_key(|(&status, &cnt)| (std::cmp::Reverse(cnt), status))
+        .map(|(status, cnt)| {
+            let s = format!("{} {} iters", status, cnt);
+            let s = match status.kind() {
+                StatusKind::Success => s.green(),
+                StatusKind::Informational => s.blue(),
+                StatusKind::Redirect => s.cyan(),
+                StatusKind::ClientError => s.yellow(),
+                StatusKind::ServerError => s.red(),
+                StatusKind::Error => s.magenta(),
+            };
+            Line::from(s)
+        })
+        .collect_vec();
+    if !hidden.is_empty() {
+        let iters: u64 = hidden.iter().map(|(_, &cnt)| cnt).sum();
+        dist.push(Line::from(
+            format!("+{} hidden ({} iters)", hidden.len(), iters).dark_gray(),
+        ));
+    }
+    let title = match filter {
+        Some(kind) => format!("Status distribution [{kind}]"),
+        None => "Status distribution".to_string(),
+    };
+    render_scrollable_list(frame, area, &title, dist, offset, focused);
+}
+
+fn render_error_dist(
+    frame: &mut Frame,
+    area: Rect,
+    error_dist: &HashMap<String, u64>,
+    offset: &mut usize,
+    focused: bool,
+) {
+    if error_dist.is_empty() {
+        return;
+    }
+
+    let dist = error_dist
+        .iter()
+        .sorted_by_key(|(err, &cnt)| (std::cmp::Reverse(cnt), *err))
+        .map(|(err, cnt)| Line::from(format!("[{cnt}] {err}")))
+        .collect_vec();
+    render_scrollable_list(frame, area, "Error distribution", dist, offset, focused);
+}
+
+/// Render the lines in a bordered block, starting from `offset` which is clamped to the last page.
+fn render_scrollable_list(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    lines: Vec<Line>,
+    offset: &mut usize,
+    focused: bool,
+) {
+    let total = lines.len();
+    let visible = area.height.saturating_sub(2) as usize;
+    *offset = (*offset).min(total.saturating_sub(visible));
+
+    let mut block = Block::new().title(title).borders(Borders::ALL);
+    if focused {
+        block = block.border_style(Style::new().fg(Color::Yellow));
+    }
+    if total > visible {
+        let footer = format!(
+            " showing {}-{} of {} ",
+            *offset + 1,
+            (*offset + visible).min(total),
+            total
+        );
+        block = block.title_bottom(Line::from(footer).right_aligned());
+    }
+
+    let lines = lines.into_iter().skip(*offset).take(visible).collect_vec();
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+
+    if total > visible {
+        let mut scrollbar = ScrollbarState::new(total - visible).position(*offset);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight),
+            area.inner(Margin::new(0, 1)),
+            &mut scrollbar,
+        );
+    }
+}
+
+fn render_iter_hist(
+    frame: &mut Frame,
+    area: Rect,
+    rwg: &RotateWindowGroup,
+    tw: TimeWindow,
+    metric: IterMetric,
+    log_scale: bool,
+    opts: DisplayOpts,
+) {
+    let win = tw.window(rwg);
+    let values = win.iter().map(|w| metric.value(&w.counter)).collect_vec();
+    let texts = values.iter().map(|&v| metric.format(v, opts.byte_unit)).collect_vec();
+    let max = values.iter().copied().max().unwrap_or(0);
+    let cols = texts.iter().map(|s| s.chars().count()).max().unwrap_or(0);
+    let labels = (0..values.len())
+        .map(|i| {
+            let mut s = tw.format(i);
+            if cols > s.len() {
+                for _ in 0..cols - s.len() {
+                    s.push(' ');
+                }
+            }
+            s
+        })
+        .collect_vec();
+
+    let bar_width = labels
+        .iter()
+        .map(|s| s.chars().count())
+        .max()
+        .map(|w| w + 2)
+        .unwrap_or(1) as u16;
+    let bars = labels
+        .into_iter()
+        .zip(values)
+        .zip(texts)
+        .enumerate()
+        .map(|(i, ((label, value), text))| {
+            // the bar heights are relative, so the logarithm is scaled up to keep its precision in integers
+            let height = match log_scale {
+                true => ((value as f64).ln_1p() * 1000.0) as u64,
+                false => value,
+            };
+            let bar = Bar::default().label(label.into()).value(height).text_value(text);
+            // the current bucket is still filling up
+            match i {
+                0 => bar.style(Style::default().fg(Color::DarkGray)),
+                _ => bar,
+            }
+        })
+        .collect_vec();
+    let scale = if log_scale { " [log]" } else { "" };
+    let chart = BarChart::default()
+        .block(
+            Block::new()
+                .title(format!("{} histogram{scale}", metric))
+                .title(
+                    Title::from(format!(" max: {} ", metric.format(max, opts.byte_unit))).alignment(Alignment::Right),
+                )
+                .borders(Borders::ALL),
+        )
+        .data(BarGroup::default().bars(&bars))
+        .bar_set(opts.bar_set())
+        .bar_style(Style::default().fg(Color::Green))
+        .label_style(Style::default().fg(Color::Cyan))
+        .bar_width(bar_width);
+    frame.render_widget(chart, area);
+}
+
+fn render_latency_hist(
+    frame: &mut Frame,
+    area: Rect,
+    hist: &LatencyHistogram,
+    phase: Option<&str>,
+    suspect: u64,
+    histo_width: usize,
+    opts: DisplayOpts,
+) {
+    // time unit for the histogram
+    let u = hist.median().appropriate_unit();
+
+    let quantiles = hist
+        .quantiles()
+        .map(|(d, n)| (d.as_f64(u).to_string(), n))
+        .collect_vec();
+
+    let data: Vec<(&str, u64)> = quantiles.iter().map(|(d, n)| (d.as_str(), *n)).collect();
+    let mut block = Block::new()
+        .title(Title::from(Line::from(vec![
+            "Latency histogram ".into(),
+            phase.map(|p| format!("[{p}] ")).unwrap_or_default().into(),
+            "(".into(),
+            u.to_string().yellow().bold(),
+            ")".into(),
+        ])))
+        .borders(Borders::ALL);
+    if suspect > 0 {
+        block = block.title(Title::from(format!(" {suspect} suspect ").yellow()).alignment(Alignment::Right));
+    }
+    let chart = BarChart::default()
+        .block(block)
+        .data(&data)
+        .bar_set(opts.bar_set())
+        .bar_style(Style::default().fg(Color::Green))
+        .label_style(Style::default().fg(Color::Cyan))
+        .bar_width(histo_width as u16);
+    frame.render_widget(chart, area);
+
+    if hist.is_empty() {
+        return;
+    }
+
+    let area = area.inner(Margin::new(1, 1));
+    let area = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).split(area)[1];
+
+    // max width of the formatted duration
+    let w = format!("{:.2}", hist.max().as_f64(u)).len();
+    #[rustfmt::skip]
+    let mut content = vec![
+        Line::from(vec!["Avg: ".blue(),      format!("{: >w$.2}", hist.mean().as_f64(u)).green()]),
+        Line::from(vec!["Min: ".cyan(),      format!("{: >w$.2}", hist.min().as_f64(u)).green()]),
+        Line::from(vec!["Med: ".yellow(),    format!("{: >w$.2}", hist.median().as_f64(u)).green()]),
+        Line::from(vec!["Max: ".red(),       format!("{: >w$.2}", hist.max().as_f64(u)).green()]),
+        Line::from(vec!["Stdev: ".magenta(), format!("{: >w$.2}", hist.stdev().as_f64(u)).green()]),
+    ];
+    // drop the percentiles, and then the whole overlay, when the panel is too short for them
+    if area.height as usize >= content.len() + 1 + PERCENTAGES.len() {
+        content.push(Line::default());
+        // annotate the percentiles which are not backed by enough samples yet
+        content.extend(hist.percentiles(PERCENTAGES).map(|(p, d)| {
+            let approx = if hist.is_reliable_percentile(p) { " " } else { "~" };
+            Line::from(vec![
+                format!("P{:.2}%: ", p).cyan(),
+                approx.dark_gray(),
+                format!("{: >w$.2}", d.as_f64(u)).green(),
+            ])
+        }));
+    } else if (area.height as usize) < content.len() {
+        return;
+    }
+    let width = content.iter().map(|s| s.width()).max().unwrap_or(0) + 2;
+    if width > area.width as usize {
+        return;
+    }
+    let area = Rect {
+        x: area.x + area.width - width as u16,
+        y: area.y,
+        width: width as u16,
+        height: content.len() as u16,
+    };
+    let block = Block::default().padding(Padding::right(2)).borders(Borders::NONE);
+    let paragraph = Paragraph::new(content).block(block).right_aligned();
+
+    frame.render_widget(Clear, area); //clears out the background
+    frame.render_widget(paragraph, area);
+}
+
+fn gen_tips<'a>(tips: impl IntoIterator<Item = (&'a str, &'a str)>) -> Line<'a> {
+    #[allow(unstable_name_collisions)]
+    tips.into_iter()
+        .map(|(key, tip)| vec![key.bold().yellow().italic(), ": ".into(), tip.italic()])
+        .intersperse(vec![", ".into()])
+        .flatten()
+        .collect_vec()
+        .into()
+}
+
+fn render_tips(frame: &mut Frame, area: Rect, notice: Option<&str>, fps: Option<f64>) {
+    // the effective refresh rate of the adaptive mode, kept clear of the tips
+    let area = match fps {
+        Some(fps) => {
+            let fps = Line::from(format!("{fps:.0} fps").dark_gray());
+            let [fps_area, rest] =
+                Layout::horizontal([Constraint::Length(fps.width() as u16 + 2), Constraint::Fill(1)]).areas(area);
+            frame.render_widget(fps, fps_area.inner(Margin::new(1, 0)));
+            rest
+        }
+        None => area,
+    };
+
+    if let Some(notice) = notice {
+        let notice = Line::from(notice.green().italic()).right_aligned();
+        frame.render_widget(notice, area.inner(Margin::new(1, 0)));
+        return;
+    }
+
+    let tips = gen_tips([
+        ("+/-", "Zoom in/out"),
+        ("a", "Auto time window"),
+        #[cfg(feature = "tracing")]
+        ("l", "Logs window"),
+        ("p", "Pause"),
+        ("r", "Reset stats"),
+        ("s", "Snapshot"),
+        ("w", "Workers"),
+        ("o", "Slowest"),
+        ("h", "Heatmap"),
+        ("m", "Histogram metric"),
+        ("y", "Log scale"),
+        ("t", "Latency phase"),
+        ("c", "Status filter"),
+        ("Tab", "Focus panel"),
+        ("q", "Quit"),
+    ])
+    .right_aligned();
+    frame.render_widget(tips, area.inner(Margin::new(1, 0)));
+}
+
+pub(crate) fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::vertical([
+        Constraint::Percentage((100 - percent_y) / 2),
+        Constraint::Percentage(percent_y),
+        Constraint::Percentage((100 - percent_y) / 2),
+    ])
+    .split(r);
+
+    Layout::horizontal([
+        Constraint::Percentage((100 - percent_x) / 2),
+        Constraint::Percentage(percent_x),
+        Constraint::Percentage((100 - percent_x) / 2),
+    ])
+    .split(popup_layout[1])[1]
+}
+
+/// Options affecting how the values and charts are drawn.
+#[derive(Clone, Copy)]
+struct DisplayOpts {
+    byte_unit: ByteUnit,
+    ascii: bool,
+    redirect_is_success: bool,
+}
+
+/// Bar symbols for terminals which cannot render the unicode block elements.
+const ASCII_BAR_SET: symbols::bar::Set = symbols::bar::Set {
+    full: "#",
+    seven_eighths: "#",
+    three_quarters: "#",
+    five_eighths: "=",
+    half: "=",
+    three_eighths: "=",
+    one_quarter: "-",
+    one_eighth: "-",
+    empty: " ",
+};
+
+impl DisplayOpts {
+    fn bar_set(self) -> symbols::bar::Set {
+        match self.ascii {
+            true => ASCII_BAR_SET,
+            false => symbols::bar::NINE_LEVELS,
+        }
+    }
+
+    /// The symbol of a filled cell, e.g. in the heatmap.
+    fn cell(self) -> &'static str {
+        match self.ascii {
+            true => "#",
+            false => symbols::block::FULL,
+        }
+    }
+}
+
+/// The metric plotted by the iteration histogram.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum IterMetric {
+    Iters,
+    Items,
+    Bytes,
+    Errors,
+}
+
+impl IterMetric {
+    fn next(self) -> Self {
+        match self {
+            IterMetric::Iters => IterMetric::Items,
+            IterMetric::Items => IterMetric::Bytes,
+            IterMetric::Bytes => IterMetric::Errors,
+            IterMetric::Errors => IterMetric::Iters,
+        }
+    }
+
+    fn value(self, counter: &Counter) -> u64 {
+        match self {
+            IterMetric::Iters => counter.iters,
+            IterMetric::Items => counter.items,
+            IterMetric::Bytes => counter.bytes,
+            IterMetric::Errors => counter.errors,
+        }
+    }
+
+    fn format(self, value: u64, byte_unit: ByteUnit) -> String {
+        match self {
+            IterMetric::Bytes => format!("{:.1}", value.adjusted(byte_unit)),
+            _ => value.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for IterMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IterMetric::Iters => write!(f, "Iteration"),
+            IterMetric::Items => write!(f, "Item"),
+            IterMetric::Bytes => write!(f, "Byte"),
+            IterMetric::Errors => write!(f, "Error"),
+        }
+    }
+}
+
+/// Cycle the status distribution filter: all, then each status kind in turn.
+fn next_status_filter(filter: Option<StatusKind>) -> Option<StatusKind> {
+    match filter {
+        None => Some(StatusKind::Success),
+        Some(StatusKind::Success) => Some(StatusKind::Informational),
+        Some(StatusKind::Informational) => Some(StatusKind::Redirect),
+        Some(StatusKind::Redirect) => Some(StatusKind::ClientError),
+        Some(StatusKind::ClientError) => Some(StatusKind::ServerError),
+        Some(StatusKind::ServerError) => Some(StatusKind::Error),
+        Some(StatusKind::Error) => None,
+    }
+}
+
+/// The distribution panels which can be focused for scrolling.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum DistPanel {
+    Status,
+    Errors,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TimeWindow {
+    Second = 1,
+    TenSec = 10,
+    Minute = 60,
+    TenMin = 600,
+}
+
+impl TimeWindow {
+    fn variants() -> &'static [TimeWindow] {
+        use TimeWindow::*;
+        &[Second, TenSec, Minute, TenMin]
+    }
+}
+
+impl fmt::Display for TimeWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", humantime::format_duration(Duration::from(*self)))
+    }
+}
+
+impl From<TimeWindow> for Duration {
+    fn from(tw: TimeWindow) -> Self {
+        Duration::from_secs(tw as u64)
+    }
+}
+
+impl TimeWindow {
+    fn window<'a>(&self, rwg: &'a RotateWindowGroup) -> &'a RotateWindow {
+        match self {
+            TimeWindow::Second => &rwg.stats_by_sec,
+            TimeWindow::TenSec => &rwg.stats_by_10sec,
+            TimeWindow::Minute => &rwg.stats_by_min,
+            TimeWindow::TenMin => &rwg.stats_by_10min,
+        }
+    }
+
+    pub fn format(&self, n: usize) -> String {
+        match self {
+            TimeWindow::Second => format!("{}s", n),
+            TimeWindow::TenSec => format!("{}s", 10 * n),
+            TimeWindow::Minute => format!("{}m", n),
+            TimeWindow::TenMin => format!("{}m", 10 * n),
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            TimeWindow::Second => TimeWindow::TenSec,
+            TimeWindow::TenSec => TimeWindow::Minute,
+            TimeWindow::Minute => TimeWindow::TenMin,
+            TimeWindow::TenMin => TimeWindow::TenMin,
+        }
+    }
+
+    pub fn prev(&self) -> Self {
+        match self {
+            TimeWindow::Second => TimeWindow::Second,
+            TimeWindow::TenSec => TimeWindow::Second,
+            TimeWindow::Minute => TimeWindow::TenSec,
+            TimeWindow::TenMin => TimeWindow::Minute,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use ratatui::widgets::{Row, Table};
+
+use super::*;
+use crate::stats::Slowest;
+
+pub(crate) fn render_slowest(frame: &mut Frame, slowest: &Slowest, enabled: bool) {
+    let area = centered_rect(60, 50, frame.size());
+    let block = Block::new().title("Slowest iterations").borders(Borders::ALL);
+    frame.render_widget(Clear, area);
+
+    if !enabled {
+        let hint = Paragraph::new("Run with --slowest <N> to track the slowest iterations".dark_gray())
+            .centered()
+            .block(block);
+        frame.render_widget(hint, area);
+        return;
+    }
+
+    // borders and the header row
+    let visible = area.height.saturating_sub(3) as usize;
+    let header = Row::new(["Latency", "Worker", "Seq", "Status", "Context"])
+        .bold()
+        .yellow();
+    let rows = slowest.sorted().into_iter().take(visible).map(|it| {
+        let u = it.duration.appropriate_unit();
+        Row::new([
+            format!("{:.2}{u}", it.duration.as_f64(u)),
+            it.worker_id.to_string(),
+            it.worker_seq.to_string(),
+            it.status.to_string(),
+            it.context.unwrap_or_default(),
+        ])
+    });
+    let widths = [
+        Constraint::Length(10),
+        Constraint::Length(8),
+        Constraint::Length(10),
+        Constraint::Length(18),
+        Constraint::Fill(1),
+    ];
+    let table = Table::new(rows, widths).header(header).block(block);
+    frame.render_widget(table, area);
+}
@@ -0,0 +1,352 @@
+use anyhow::Result;
+use crossterm::{cursor, event::Event, terminal, ExecutableCommand};
+use ratatui::{backend::CrosstermBackend, CompletedFrame, Frame};
+use std::{io, path::Path, time::Duration};
+
+#[cfg(unix)]
+use device::Device;
+
+/// The terminal the TUI is drawn on and reads its input from.
+pub(crate) struct Terminal {
+    inner: Inner,
+}
+
+enum Inner {
+    /// The terminal of the process, through stdout.
+    Stdout(ratatui::Terminal<CrosstermBackend<io::Stdout>>),
+    /// Another terminal device, e.g. `/dev/pts/7`, which leaves stdout to the report.
+    #[cfg(unix)]
+    Device(ratatui::Terminal<device::DeviceBackend>),
+}
+
+impl Terminal {
+    /// Set up the terminal of the process, or the terminal device at the given path.
+    pub(crate) fn new(tty: Option<&Path>) -> Result<Self> {
+        let inner = match tty {
+            None => {
+                crossterm::terminal::enable_raw_mode()?;
+                io::stdout().execute(crossterm::cursor::Hide)?;
+                io::stdout().execute(crossterm::terminal::EnterAlternateScreen)?;
+                Inner::Stdout(ratatui::Terminal::new(CrosstermBackend::new(io::stdout()))?)
+            }
+            #[cfg(unix)]
+            Some(path) => {
+                let mut backend = Device::open(path)?.into_backend()?;
+                backend.execute(cursor::Hide)?;
+                backend.execute(terminal::EnterAlternateScreen)?;
+                Inner::Device(ratatui::Terminal::new(backend)?)
+            }
+            #[cfg(not(unix))]
+            Some(_) => anyhow::bail!("drawing the TUI on another terminal is only supported on unix"),
+        };
+        Ok(Self { inner })
+    }
+
+    pub(crate) fn draw<F>(&mut self, f: F) -> io::Result<CompletedFrame<'_>>
+    where
+        F: FnOnce(&mut Frame),
+    {
+        match &mut self.inner {
+            Inner::Stdout(terminal) => terminal.draw(f),
+            #[cfg(unix)]
+            Inner::Device(terminal) => terminal.draw(f),
+        }
+    }
+
+    /// The next input event of the terminal, if there is one pending.
+    pub(crate) fn poll_event(&mut self) -> io::Result<Option<Event>> {
+        match &mut self.inner {
+            Inner::Stdout(_) => match crossterm::event::poll(Duration::from_secs(0))? {
+                true => crossterm::event::read().map(Some),
+                false => Ok(None),
+            },
+            #[cfg(unix)]
+            Inner::Device(terminal) => Ok(terminal.backend().device.poll_event()),
+        }
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        match &mut self.inner {
+            Inner::Stdout(_) => {
+                std::io::stdout().execute(terminal::LeaveAlternateScreen).unwrap();
+                std::io::stdout().execute(cursor::Show).unwrap();
+                crossterm::terminal::disable_raw_mode().unwrap();
+            }
+            // the device restores its settings once it is dropped
+            #[cfg(unix)]
+            Inner::Device(terminal) => {
+                let _ = terminal.backend_mut().execute(terminal::LeaveAlternateScreen);
+                let _ = terminal.backend_mut().execute(cursor::Show);
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+mod device {
+    use std::{
+        fs::{File, OpenOptions},
+        io::{self, Read, Write},
+        mem::MaybeUninit,
+        os::fd::AsRawFd,
+        path::Path,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            mpsc, Arc,
+        },
+    };
+
+    use anyhow::{bail, Context, Result};
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{
+        backend::{Backend, ClearType, CrosstermBackend, WindowSize},
+        buffer::Cell,
+        layout::{Rect, Size},
+    };
+
+    /// How long the input reader waits for input before it checks whether the device was dropped.
+    const INPUT_POLL_MS: libc::c_int = 100;
+
+    /// A terminal device other than the one of the process, switched to raw mode while it is open.
+    ///
+    /// The keys typed into the device are read by a thread of its own, as the input of crossterm is bound to
+    /// the terminal of the process.
+    pub(crate) struct Device {
+        file: File,
+        /// The settings of the device before it was switched to raw mode, restored when it is dropped.
+        termios: libc::termios,
+        events: mpsc::Receiver<Event>,
+        closed: Arc<AtomicBool>,
+    }
+
+    impl Device {
+        /// Open the terminal device at the given path and switch it to raw mode.
+        pub(crate) fn open(path: &Path) -> Result<Self> {
+            let file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(path)
+                .with_context(|| format!("failed to open {}", path.display()))?;
+            let fd = file.as_raw_fd();
+            // SAFETY: the descriptor is open for the lifetime of `file`
+            if unsafe { libc::isatty(fd) } != 1 {
+                bail!("{} is not a terminal", path.display());
+            }
+
+            let mut termios = MaybeUninit::<libc::termios>::uninit();
+            // SAFETY: `tcgetattr` initializes the settings when it succeeds
+            let termios = unsafe {
+                if libc::tcgetattr(fd, termios.as_mut_ptr()) != 0 {
+                    return Err(io::Error::last_os_error().into());
+                }
+                termios.assume_init()
+            };
+            let mut raw = termios;
+            // SAFETY: `raw` is a valid set of settings
+            unsafe {
+                libc::cfmakeraw(&mut raw);
+                if libc::tcsetattr(fd, libc::TCSANOW, &raw) != 0 {
+                    return Err(io::Error::last_os_error().into());
+                }
+            }
+
+            let (tx, events) = mpsc::channel();
+            let closed = Arc::new(AtomicBool::new(false));
+            let input = file.try_clone()?;
+            std::thread::spawn({
+                let closed = closed.clone();
+                move || read_input(input, &closed, &tx)
+            });
+            Ok(Self { file, termios, events, closed })
+        }
+
+        /// Draw on the device through a crossterm backend, with the size of the device.
+        pub(crate) fn into_backend(self) -> io::Result<DeviceBackend> {
+            let out = CrosstermBackend::new(self.file.try_clone()?);
+            Ok(DeviceBackend { out, device: self })
+        }
+
+        pub(crate) fn poll_event(&self) -> Option<Event> {
+            self.events.try_recv().ok()
+        }
+
+        fn size(&self) -> io::Result<libc::winsize> {
+            let mut size = MaybeUninit::<libc::winsize>::uninit();
+            // SAFETY: `TIOCGWINSZ` fills in the size when it succeeds
+            unsafe {
+                if libc::ioctl(self.file.as_raw_fd(), libc::TIOCGWINSZ, size.as_mut_ptr()) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(size.assume_init())
+            }
+        }
+    }
+
+    impl Drop for Device {
+        fn drop(&mut self) {
+            self.closed.store(true, Ordering::Relaxed);
+            // SAFETY: the settings were read from the same device
+            unsafe { libc::tcsetattr(self.file.as_raw_fd(), libc::TCSANOW, &self.termios) };
+        }
+    }
+
+    /// Forward the keys typed into the device until it is dropped.
+    ///
+    /// The reader polls with a timeout rather than blocking in a read, so it stops in time to leave the input
+    /// to the next TUI on the same device, e.g. in the next step of a sweep.
+    fn read_input(mut input: File, closed: &AtomicBool, tx: &mpsc::Sender<Event>) {
+        let mut buf = [0u8; 64];
+        while !closed.load(Ordering::Relaxed) {
+            let mut fd = libc::pollfd { fd: input.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+            // SAFETY: `fd` is a single valid entry
+            match unsafe { libc::poll(&mut fd, 1, INPUT_POLL_MS) } {
+                0 => continue,
+                n if n < 0 => return,
+                _ => (),
+            }
+            let n = match input.read(&mut buf) {
+                Ok(0) | Err(_) => return,
+                Ok(n) => n,
+            };
+            for key in parse_keys(&buf[..n]) {
+                if tx.send(Event::Key(key)).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Decode the keys in the bytes read from a terminal in raw mode.
+    ///
+    /// Covers the keys bound by the TUI: characters, control keys, and the escape sequences of the arrow,
+    /// paging, home and end keys. An escape byte which does not start a known sequence is the escape key.
+    fn parse_keys(mut bytes: &[u8]) -> Vec<KeyEvent> {
+        use KeyCode::*;
+        let mut keys = Vec::new();
+        while let Some(&b) = bytes.first() {
+            let (code, modifiers, len) = match b {
+                0x1b => match escape_sequence(&bytes[1..]) {
+                    Some((code, len)) => (code, KeyModifiers::NONE, len + 1),
+                    None => (Esc, KeyModifiers::NONE, 1),
+                },
+                b'\r' | b'\n' => (Enter, KeyModifiers::NONE, 1),
+                b'\t' => (Tab, KeyModifiers::NONE, 1),
+                0x7f | 0x08 => (Backspace, KeyModifiers::NONE, 1),
+                0x01..=0x1a => (Char((b'a' + b - 1) as char), KeyModifiers::CONTROL, 1),
+                _ => {
+                    let len = match b {
+                        0xf0.. => 4,
+                        0xe0.. => 3,
+                        0xc0.. => 2,
+                        _ => 1,
+                    };
+                    match std::str::from_utf8(&bytes[..len.min(bytes.len())]).map(|s| s.chars().next()) {
+                        Ok(Some(c)) if c.is_uppercase() => (Char(c), KeyModifiers::SHIFT, len),
+                        Ok(Some(c)) => (Char(c), KeyModifiers::NONE, len),
+                        // skip anything else, e.g. a character split across reads
+                        _ => {
+                            bytes = &bytes[1..];
+                            continue;
+                        }
+                    }
+                }
+            };
+            keys.push(KeyEvent::new(code, modifiers));
+            bytes = &bytes[len..];
+        }
+        keys
+    }
+
+    /// The key of the escape sequence at the start of the bytes after the escape byte, and its length.
+    fn escape_sequence(bytes: &[u8]) -> Option<(KeyCode, usize)> {
+        use KeyCode::*;
+        match bytes {
+            [b'[' | b'O', b'A', ..] => Some((Up, 2)),
+            [b'[' | b'O', b'B', ..] => Some((Down, 2)),
+            [b'[' | b'O', b'C', ..] => Some((Right, 2)),
+            [b'[' | b'O', b'D', ..] => Some((Left, 2)),
+            [b'[' | b'O', b'H', ..] => Some((Home, 2)),
+            [b'[' | b'O', b'F', ..] => Some((End, 2)),
+            [b'[', b'Z', ..] => Some((BackTab, 2)),
+            [b'[', b'1' | b'7', b'~', ..] => Some((Home, 3)),
+            [b'[', b'4' | b'8', b'~', ..] => Some((End, 3)),
+            [b'[', b'3', b'~', ..] => Some((Delete, 3)),
+            [b'[', b'5', b'~', ..] => Some((PageUp, 3)),
+            [b'[', b'6', b'~', ..] => Some((PageDown, 3)),
+            _ => None,
+        }
+    }
+
+    /// A crossterm backend which draws on a [`Device`], and takes the size from it rather than from the
+    /// terminal of the process.
+    pub(crate) struct DeviceBackend {
+        out: CrosstermBackend<File>,
+        pub(crate) device: Device,
+    }
+
+    impl Write for DeviceBackend {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.out.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Write::flush(&mut self.out)
+        }
+    }
+
+    impl Backend for DeviceBackend {
+        fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+        where
+            I: Iterator<Item = (u16, u16, &'a Cell)>,
+        {
+            self.out.draw(content)
+        }
+
+        fn hide_cursor(&mut self) -> io::Result<()> {
+            self.out.hide_cursor()
+        }
+
+        fn show_cursor(&mut self) -> io::Result<()> {
+            self.out.show_cursor()
+        }
+
+        /// Only used by inline viewports, and crossterm would ask the terminal of the process.
+        fn get_cursor(&mut self) -> io::Result<(u16, u16)> {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "cursor position of a terminal device",
+            ))
+        }
+
+        fn set_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+            self.out.set_cursor(x, y)
+        }
+
+        fn clear(&mut self) -> io::Result<()> {
+            self.out.clear()
+        }
+
+        fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+            self.out.clear_region(clear_type)
+        }
+
+        fn size(&self) -> io::Result<Rect> {
+            let size = self.device.size()?;
+            Ok(Rect::new(0, 0, size.ws_col, size.ws_row))
+        }
+
+        fn window_size(&mut self) -> io::Result<WindowSize> {
+            let size = self.device.size()?;
+            Ok(WindowSize {
+                columns_rows: Size { width: size.ws_col, height: size.ws_row },
+                pixels: Size { width: size.ws_xpixel, height: size.ws_ypixel },
+            })
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Backend::flush(&mut self.out)
+        }
+    }
+}
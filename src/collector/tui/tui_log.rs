@@ -0,0 +1,477 @@
+use super::*;
+
+use std::{
+    collections::VecDeque,
+    fs::OpenOptions,
+    path::Path,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+    time::SystemTime,
+};
+
+use crate::cli::Collector;
+use crossterm::tty::IsTty;
+use log::LevelFilter;
+use parking_lot::Mutex;
+use ratatui::layout::Direction;
+use tracing::{field::Field, Level, Subscriber};
+use tracing_subscriber::{
+    fmt::MakeWriter,
+    layer::{Context, SubscriberExt},
+    util::SubscriberInitExt,
+    EnvFilter, Layer,
+};
+
+use tui_logger::{TuiLoggerLevelOutput, TuiLoggerSmartWidget, TuiTracingSubscriberLayer, TuiWidgetState};
+
+/// Default maximum number of captured log lines, see [`TuiTracingLayer::with_buffer`].
+pub const DEFAULT_LOG_BUFFER: usize = 5000;
+
+/// Maximum number of captured log lines.
+static MAX_RECORDS: AtomicUsize = AtomicUsize::new(DEFAULT_LOG_BUFFER);
+
+/// Minimum level of the captured log lines.
+static CAPTURE_LEVEL: Mutex<LevelFilter> = parking_lot::const_mutex(LevelFilter::Trace);
+
+/// Number of log lines captured during the run, including the dropped ones.
+static CAPTURED: AtomicU64 = AtomicU64::new(0);
+
+/// A copy of the recent log lines, since the `tui_logger` buffer cannot be searched.
+static RECORDS: Mutex<VecDeque<LogRecord>> = parking_lot::const_mutex(VecDeque::new());
+
+/// The file the log lines are also written to, if any. See [`LogFile`].
+static LOG_FILE: Mutex<Option<BufWriter<File>>> = parking_lot::const_mutex(None);
+
+struct LogRecord {
+    level: Level,
+    target: String,
+    msg: String,
+}
+
+/// A tracing layer which feeds the log popup of the TUI collector.
+pub struct TuiTracingLayer(TuiTracingSubscriberLayer);
+
+/// Create a tracing layer which feeds the log popup of the TUI collector.
+///
+/// The layer captures all levels and keeps the latest [`DEFAULT_LOG_BUFFER`] lines unless configured otherwise.
+pub fn tracing_subscriber_layer() -> TuiTracingLayer {
+    TuiTracingLayer(tui_logger::tracing_subscriber_layer())
+}
+
+/// Install the global tracing subscriber for the given collector, filtered by the `RUST_LOG` environment variable.
+///
+/// With the TUI collector the logs are captured by [`tracing_subscriber_layer`] and shown in the log popup, as
+/// writing them to the terminal would garble the TUI. The other collectors write them to stderr.
+///
+/// Fails if a global subscriber is already installed.
+pub fn init_tracing(collector: Collector) -> Result<()> {
+    let ansi = std::io::stderr().is_tty();
+    subscriber(collector, EnvFilter::from_default_env(), std::io::stderr, ansi).try_init()?;
+    Ok(())
+}
+
+/// The subscriber installed by [`init_tracing`], which writes the logs of the non-TUI collectors to `writer`.
+fn subscriber<W>(collector: Collector, filter: EnvFilter, writer: W, ansi: bool) -> impl Subscriber + Send + Sync
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let (tui, fmt) = match collector {
+        Collector::Tui => (Some(tracing_subscriber_layer()), None),
+        Collector::Silent | Collector::Progress => (
+            None,
+            Some(tracing_subscriber::fmt::layer().with_writer(writer).with_ansi(ansi)),
+        ),
+    };
+    tracing_subscriber::registry().with(filter).with(tui).with(fmt)
+}
+
+impl TuiTracingLayer {
+    /// Keep the latest `lines` log lines of the run and drop the older ones.
+    ///
+    /// This bounds the lines which can be searched and filtered in the log popup. The unfiltered view of the
+    /// popup is backed by the buffer of `tui_logger`, which keeps up to 10000 lines on its own.
+    pub fn with_buffer(self, lines: usize) -> Self {
+        set_capture(Some(lines), None);
+        self
+    }
+
+    /// Capture the log lines of the given level and above only.
+    pub fn with_level(self, level: LevelFilter) -> Self {
+        set_capture(None, Some(level));
+        self
+    }
+}
+
+/// Set the maximum number of captured log lines and their minimum level, keeping the current ones if `None`.
+pub(crate) fn set_capture(buffer: Option<usize>, level: Option<LevelFilter>) {
+    if let Some(buffer) = buffer {
+        MAX_RECORDS.store(buffer, Ordering::Relaxed);
+        tui_logger::set_hot_buffer_depth(buffer.max(1));
+    }
+    if let Some(level) = level {
+        *CAPTURE_LEVEL.lock() = level;
+        tui_logger::set_default_level(level);
+    }
+}
+
+impl<S: Subscriber> Layer<S> for TuiTracingLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+        if log_level(*event.metadata().level()) > *CAPTURE_LEVEL.lock() {
+            return;
+        }
+
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let record = LogRecord {
+            level: *event.metadata().level(),
+            target: event.metadata().target().to_string(),
+            msg: visitor.0,
+        };
+
+        if let Some(w) = LOG_FILE.lock().as_mut() {
+            let _ = writeln!(
+                w,
+                "{} {:>5} {}: {}",
+                humantime::format_rfc3339_millis(SystemTime::now()),
+                record.level,
+                record.target,
+                record.msg
+            );
+        }
+
+        CAPTURED.fetch_add(1, Ordering::Relaxed);
+        let max = MAX_RECORDS.load(Ordering::Relaxed);
+        let mut records = RECORDS.lock();
+        while !records.is_empty() && records.len() >= max {
+            records.pop_front();
+        }
+        if max > 0 {
+            records.push_back(record);
+        }
+        drop(records);
+
+        self.0.on_event(event, ctx);
+    }
+}
+
+/// A guard which tees the log lines to a file until it is dropped.
+///
+/// The file is flushed and closed on drop, so the logs survive the TUI.
+pub(crate) struct LogFile(());
+
+impl LogFile {
+    /// Start writing the log lines to the file, appending to it unless `truncate` is set.
+    pub(crate) fn open(path: &Path, truncate: bool) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(!truncate)
+            .truncate(truncate)
+            .open(path)?;
+        *LOG_FILE.lock() = Some(BufWriter::new(file));
+        Ok(Self(()))
+    }
+}
+
+impl Drop for LogFile {
+    fn drop(&mut self) {
+        if let Some(mut w) = LOG_FILE.lock().take() {
+            let _ = w.flush();
+        }
+    }
+}
+
+fn log_level(level: Level) -> log::Level {
+    match level {
+        Level::ERROR => log::Level::Error,
+        Level::WARN => log::Level::Warn,
+        Level::INFO => log::Level::Info,
+        Level::DEBUG => log::Level::Debug,
+        Level::TRACE => log::Level::Trace,
+    }
+}
+
+/// The title of the log popup, with the number of captured and dropped lines.
+fn log_title(kind: &str) -> String {
+    let captured = CAPTURED.load(Ordering::Relaxed);
+    let dropped = captured.saturating_sub(RECORDS.lock().len() as u64);
+    format!("Logs{kind}: {captured} messages ({dropped} dropped)")
+}
+
+#[derive(Default)]
+struct MessageVisitor(String);
+
+impl tracing::field::Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        use std::fmt::Write;
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+        let _ = match field.name() {
+            "message" => write!(self.0, "{value:?}"),
+            name => write!(self.0, "{name}: {value:?}"),
+        };
+    }
+}
+
+pub(crate) struct LogState {
+    pub(crate) inner: TuiWidgetState,
+    pub(crate) display: bool,
+    /// The substring the displayed lines are filtered by, if any.
+    search: Option<String>,
+    /// Whether the keys are typed into the search input.
+    editing: bool,
+    /// The minimum level of the displayed lines, if set with the number keys.
+    level: Option<LevelFilter>,
+}
+
+impl LogState {
+    pub(crate) fn from_env() -> Result<Self> {
+        tui_logger::set_default_level(*CAPTURE_LEVEL.lock());
+        let state = TuiWidgetState::new().set_default_display_level(LevelFilter::Info);
+        Ok(Self {
+            inner: state,
+            display: false,
+            search: None,
+            editing: false,
+            level: None,
+        })
+    }
+
+    /// Whether the search input is active and should receive all keys.
+    pub(crate) fn editing(&self) -> bool {
+        self.display && self.editing
+    }
+
+    /// Handle a key of the search input.
+    pub(crate) fn edit(&mut self, code: KeyCode) {
+        let search = self.search.get_or_insert_with(String::new);
+        match code {
+            KeyCode::Char(c) => search.push(c),
+            KeyCode::Backspace => {
+                search.pop();
+            }
+            KeyCode::Enter => self.editing = false,
+            KeyCode::Esc => {
+                self.clear_search();
+            }
+            _ => (),
+        }
+    }
+
+    /// Open the search input.
+    pub(crate) fn start_search(&mut self) {
+        self.editing = true;
+        self.search.get_or_insert_with(String::new);
+    }
+
+    /// Clear the search. Returns `false` if there was none.
+    pub(crate) fn clear_search(&mut self) -> bool {
+        self.editing = false;
+        self.search.take().is_some()
+    }
+
+    /// Set the minimum display level from the number keys `1` (error) to `5` (trace), or `0` for all.
+    pub(crate) fn set_level(&mut self, key: char) {
+        self.level = match key {
+            '0' => None,
+            '1' => Some(LevelFilter::Error),
+            '2' => Some(LevelFilter::Warn),
+            '3' => Some(LevelFilter::Info),
+            '4' => Some(LevelFilter::Debug),
+            '5' => Some(LevelFilter::Trace),
+            _ => self.level,
+        };
+    }
+
+    fn filtered(&self) -> bool {
+        self.level.is_some() || self.search.as_ref().is_some_and(|s| !s.is_empty())
+    }
+
+    fn matches(&self, record: &LogRecord) -> bool {
+        self.level.is_none_or(|l| log_level(record.level) <= l)
+            && self
+                .search
+                .as_ref()
+                .is_none_or(|s| record.msg.contains(s.as_str()) || record.target.contains(s.as_str()))
+    }
+}
+
+pub(crate) fn render_logs(frame: &mut Frame, state: &LogState) {
+    if !state.display {
+        return;
+    }
+
+    let area = centered_rect(80, 80, frame.size());
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(100), Constraint::Min(1)])
+        .split(area.inner(Margin::new(1, 1)));
+
+    frame.render_widget(Clear, area);
+    let matched = match state.filtered() {
+        true => Some(render_filtered_logs(frame, rows[0], state)),
+        false => {
+            frame.render_widget(log_widget(state), rows[0]);
+            None
+        }
+    };
+    render_log_footer(frame, rows[1], state, matched);
+}
+
+fn log_widget(state: &LogState) -> TuiLoggerSmartWidget<'_> {
+    TuiLoggerSmartWidget::default()
+        .style_error(Style::default().fg(Color::Red))
+        .style_debug(Style::default().fg(Color::Green))
+        .style_warn(Style::default().fg(Color::Yellow))
+        .style_trace(Style::default().fg(Color::Magenta))
+        .style_info(Style::default().fg(Color::Cyan))
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .output_separator('|')
+        .output_level(Some(TuiLoggerLevelOutput::Abbreviated))
+        .output_target(true)
+        .output_file(true)
+        .output_line(true)
+        .title_log(log_title(""))
+        .title_target("Selector")
+        .state(&state.inner)
+}
+
+/// Render the most recent lines which pass the filters. Returns the number of matched lines.
+fn render_filtered_logs(frame: &mut Frame, area: Rect, state: &LogState) -> usize {
+    let block = Block::new()
+        .title(log_title(" (filtered)"))
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded);
+    let visible = block.inner(area).height as usize;
+
+    let records = RECORDS.lock();
+    let matched = records.iter().filter(|r| state.matches(r)).collect_vec();
+    let lines = matched[matched.len().saturating_sub(visible)..]
+        .iter()
+        .map(|r| {
+            let (abbr, color) = match r.level {
+                Level::ERROR => ("E", Color::Red),
+                Level::WARN => ("W", Color::Yellow),
+                Level::INFO => ("I", Color::Cyan),
+                Level::DEBUG => ("D", Color::Green),
+                Level::TRACE => ("T", Color::Magenta),
+            };
+            Line::from(format!("{abbr}|{}|{}", r.target, r.msg)).fg(color)
+        })
+        .collect_vec();
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+    matched.len()
+}
+
+fn render_log_footer(frame: &mut Frame, area: Rect, state: &LogState, matched: Option<usize>) {
+    let mut spans = vec![];
+    if let Some(level) = state.level {
+        spans.extend(["level: ".into(), level.to_string().yellow().bold(), " ".into()]);
+    }
+    if let Some(search) = &state.search {
+        spans.extend(["/".into(), search.clone().yellow().bold()]);
+        if state.editing {
+            spans.push("▏".into());
+        }
+        spans.push(" ".into());
+    }
+    if let Some(matched) = matched {
+        spans.push(format!("({matched} matches)").italic());
+    }
+
+    let tips = match state.editing {
+        true => gen_tips([("Enter", "Confirm"), ("Esc", "Clear search")]),
+        false if state.filtered() => gen_tips([("/", "Search"), ("0-5", "Min level"), ("Esc", "Clear search")]),
+        false => gen_tips([
+            ("/", "Search"),
+            ("0-5", "Min level"),
+            ("Enter", "Focus target"),
+            ("↑/↓", "Select target"),
+            ("←/→", "Display level"),
+            ("f/b", "Scroll"),
+            ("Esc", "Cancel scroll"),
+            ("Space", "Hide selector"),
+        ]),
+    };
+
+    let area = area.inner(Margin::new(1, 0));
+    frame.render_widget(Line::from(spans), area);
+    frame.render_widget(tips.right_aligned(), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, sync::Arc};
+
+    use super::*;
+
+    /// A log writer whose output can be inspected.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Buffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().clone()).unwrap()
+        }
+    }
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl MakeWriter<'_> for Buffer {
+        type Writer = Self;
+
+        fn make_writer(&self) -> Self {
+            self.clone()
+        }
+    }
+
+    /// Log a line of each level with the subscriber for `collector`, filtered at the info level.
+    fn log(collector: Collector, buf: &Buffer, msg: &str) {
+        let subscriber = subscriber(collector, EnvFilter::new("info"), buf.clone(), false);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("{msg} info");
+            tracing::debug!("{msg} debug");
+        });
+    }
+
+    fn captured(msg: &str) -> Vec<String> {
+        RECORDS
+            .lock()
+            .iter()
+            .filter(|r| r.msg.starts_with(msg))
+            .map(|r| r.msg.clone())
+            .collect()
+    }
+
+    #[test]
+    fn tui_captures_the_logs() {
+        let buf = Buffer::default();
+        log(Collector::Tui, &buf, "to the tui");
+
+        assert_eq!(captured("to the tui"), ["to the tui info"]);
+        assert_eq!(buf.contents(), "");
+    }
+
+    #[test]
+    fn other_collectors_write_the_logs() {
+        for collector in [Collector::Silent, Collector::Progress] {
+            let buf = Buffer::default();
+            let msg = format!("to the writer of {collector:?}");
+            log(collector, &buf, &msg);
+
+            let out = buf.contents();
+            assert_eq!(out.lines().count(), 1, "{out}");
+            assert!(out.contains(" INFO ") && out.contains(&format!("{msg} info")), "{out}");
+            assert!(captured(&msg).is_empty());
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use ratatui::widgets::{Row, Table};
+
+use super::*;
+
+/// Statistics of a single worker.
+#[derive(Default, Clone, Copy)]
+pub(crate) struct WorkerStats {
+    counter: Counter,
+    /// Iterations at the last rotation, used to derive the rate of the last second.
+    last_iters: u64,
+    last_rate: u64,
+}
+
+pub(crate) struct WorkersState {
+    pub(crate) display: bool,
+    offset: usize,
+    /// Number of rows shown at the last render.
+    page: usize,
+    workers: Vec<WorkerStats>,
+}
+
+impl WorkersState {
+    pub(crate) fn new(concurrency: u32) -> Self {
+        Self {
+            display: false,
+            offset: 0,
+            page: 0,
+            workers: vec![WorkerStats::default(); concurrency as usize],
+        }
+    }
+
+    /// Account an iteration result to the worker which produced it.
+    pub(crate) fn push(&mut self, res: &IterResult) {
+        let id = res.info.worker_id as usize;
+        if id >= self.workers.len() {
+            self.workers.resize(id + 1, WorkerStats::default());
+        }
+        let worker = &mut self.workers[id];
+        match &res.result {
+            Ok(report) => worker.counter += report,
+            Err(_) => worker.counter.errors += 1,
+        }
+    }
+
+    /// Close the current one second window of every worker.
+    pub(crate) fn rotate(&mut self) {
+        for worker in &mut self.workers {
+            worker.last_rate = worker.counter.iters - worker.last_iters;
+            worker.last_iters = worker.counter.iters;
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.workers.fill(WorkerStats::default());
+    }
+
+    pub(crate) fn scroll(&mut self, delta: isize) {
+        self.offset = self.offset.saturating_add_signed(delta);
+    }
+
+    pub(crate) fn scroll_page(&mut self, pages: isize) {
+        self.scroll(pages * self.page.max(1) as isize);
+    }
+}
+
+pub(crate) fn render_workers(frame: &mut Frame, state: &mut WorkersState) {
+    if !state.display {
+        return;
+    }
+
+    let area = centered_rect(60, 80, frame.size());
+    // borders and the header row
+    let visible = area.height.saturating_sub(3) as usize;
+    let total = state.workers.len();
+    state.offset = state.offset.min(total.saturating_sub(visible));
+    state.page = visible;
+
+    let header = Row::new(["Worker", "Iters", "Errors", "Iters/s"]).bold().yellow();
+    let rows = state
+        .workers
+        .iter()
+        .enumerate()
+        .skip(state.offset)
+        .take(visible)
+        .map(|(id, w)| {
+            let row = Row::new([
+                id.to_string(),
+                w.counter.iters.to_string(),
+                w.counter.errors.to_string(),
+                w.last_rate.to_string(),
+            ]);
+            match w.counter.errors {
+                0 => row,
+                _ => row.red(),
+            }
+        });
+    let widths = [Constraint::Fill(1); 4];
+    let title = Line::from(vec![
+        "Workers ".into(),
+        format!(
+            "({}-{} of {})",
+            (state.offset + 1).min(total),
+            (state.offset + visible).min(total),
+            total
+        )
+        .yellow()
+        .bold(),
+    ]);
+    let table = Table::new(rows, widths).header(header).block(
+        Block::new()
+            .title(title)
+            .title_bottom(gen_tips([("↑/↓", "Scroll"), ("f/b", "Page")]).right_aligned())
+            .borders(Borders::ALL),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(table, area);
+
+    if total > visible {
+        let mut scrollbar = ScrollbarState::new(total.saturating_sub(visible)).position(state.offset);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight),
+            area.inner(Margin::new(0, 1)),
+            &mut scrollbar,
+        );
+    }
+}
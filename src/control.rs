@@ -0,0 +1,174 @@
+//! HTTP endpoint for controlling a running benchmark.
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use anyhow::Result;
+use hyper::{Method, StatusCode};
+use serde::Serialize;
+use tokio::{net::TcpListener, task::JoinHandle};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    clock::Clock,
+    collector::ReportSink,
+    pause::PauseControl,
+    report::IterResult,
+    runner::InFlight,
+    server::{self, HttpResponse},
+    status::StatusKind,
+};
+
+/// Live counters of the benchmark, updated from the iteration report stream.
+#[derive(Default)]
+struct Progress {
+    iters: AtomicU64,
+    success: AtomicU64,
+    errors: AtomicU64,
+    finished: AtomicBool,
+    /// Whether `POST /stop` cancelled the benchmark.
+    stopped: AtomicBool,
+}
+
+/// A report sink which feeds the counters served by `GET /status`.
+pub(crate) struct ProgressSink(Arc<Progress>);
+
+impl ReportSink for ProgressSink {
+    fn on_report(&mut self, res: &IterResult) -> Result<()> {
+        match &res.result {
+            Ok(report) => {
+                self.0.iters.fetch_add(1, Ordering::Relaxed);
+                if report.status.kind() == StatusKind::Success {
+                    self.0.success.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            Err(_) => {
+                self.0.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.0.finished.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct Status {
+    phase: &'static str,
+    elapsed: f64,
+    iters: u64,
+    errors: u64,
+    success_ratio: f64,
+    in_flight: u32,
+}
+
+struct Control {
+    clock: Clock,
+    pause: Arc<PauseControl>,
+    cancel: CancellationToken,
+    in_flight: InFlight,
+    progress: Arc<Progress>,
+}
+
+impl Control {
+    fn status(&self) -> HttpResponse {
+        let p = &self.progress;
+        let iters = p.iters.load(Ordering::Relaxed);
+        let success = p.success.load(Ordering::Relaxed);
+        let phase = if p.finished.load(Ordering::Relaxed) {
+            "finished"
+        } else if self.cancel.is_cancelled() {
+            "stopping"
+        } else if self.pause.is_paused() {
+            "paused"
+        } else {
+            "running"
+        };
+        let status = Status {
+            phase,
+            elapsed: self.clock.elapsed().as_secs_f64(),
+            iters,
+            errors: p.errors.load(Ordering::Relaxed),
+            success_ratio: if iters == 0 { 0.0 } else { success as f64 / iters as f64 },
+            in_flight: self.in_flight.get(),
+        };
+        match serde_json::to_string(&status) {
+            Ok(body) => server::response(StatusCode::OK, "application/json", body + "\n"),
+            Err(e) => server::response(StatusCode::INTERNAL_SERVER_ERROR, "text/plain", e.to_string()),
+        }
+    }
+
+    fn handle(&self, method: &Method, path: &str) -> HttpResponse {
+        let finished = self.progress.finished.load(Ordering::Relaxed);
+        match (method, path) {
+            (&Method::GET, "/status") => return self.status(),
+            (&Method::POST, "/pause") if !finished => self.pause.pause(),
+            (&Method::POST, "/resume") if !finished => self.pause.resume(),
+            (&Method::POST, "/stop") => {
+                if !finished && !self.cancel.is_cancelled() {
+                    self.progress.stopped.store(true, Ordering::Relaxed);
+                }
+                self.cancel.cancel();
+            }
+            (&Method::POST, "/pause" | "/resume") => (),
+            _ => return server::not_found(),
+        }
+        self.status()
+    }
+}
+
+/// A running control endpoint.
+pub(crate) struct ControlServer {
+    cancel: CancellationToken,
+    handle: JoinHandle<()>,
+    progress: Arc<Progress>,
+}
+
+impl ControlServer {
+    /// Bind the control endpoint on the given address.
+    ///
+    /// Returns the server and a sink which feeds the progress reported by `GET /status`.
+    pub(crate) async fn start(
+        addr: SocketAddr,
+        clock: Clock,
+        pause: Arc<PauseControl>,
+        bench_cancel: CancellationToken,
+        in_flight: InFlight,
+    ) -> Result<(Self, ProgressSink)> {
+        let listener = TcpListener::bind(addr).await?;
+        let progress = Arc::new(Progress::default());
+        let sink = ProgressSink(progress.clone());
+        let control = Control {
+            clock,
+            pause,
+            cancel: bench_cancel,
+            in_flight,
+            progress: progress.clone(),
+        };
+
+        let cancel = CancellationToken::new();
+        let handle = tokio::spawn(server::serve(listener, cancel.clone(), move |req| {
+            control.handle(req.method(), req.uri().path())
+        }));
+
+        Ok((Self { cancel, handle, progress }, sink))
+    }
+
+    /// Returns true if the benchmark was cancelled by `POST /stop`.
+    pub(crate) fn stopped(&self) -> bool {
+        self.progress.stopped.load(Ordering::Relaxed)
+    }
+
+    /// Stop serving and wait for the in-flight connections to finish.
+    pub(crate) async fn shutdown(self) -> Result<()> {
+        self.cancel.cancel();
+        Ok(self.handle.await?)
+    }
+}
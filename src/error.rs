@@ -0,0 +1,85 @@
+//! This module defines the error returned by the entry points of the crate.
+use std::fmt;
+
+use crate::assertion::AssertionResult;
+
+/// The error of a benchmark run, returned by [`BenchBuilder::run`](crate::BenchBuilder::run) and
+/// [`cli::run`](crate::cli::run).
+///
+/// The variants tell apart the parts of the run which failed, e.g. to map them to exit codes:
+///
+/// ```no_run
+/// # use rlt::{cli::BenchCli, Error, StatelessBenchSuite};
+/// # async fn run(cli: BenchCli, suite: impl StatelessBenchSuite + Clone + Send + Sync + 'static) {
+/// let code = match rlt::cli::run(cli, suite).await {
+///     Ok(()) => 0,
+///     Err(Error::AssertionFailed(_)) => 1,
+///     Err(e) => {
+///         eprintln!("Error: {e:?}");
+///         2
+///     }
+/// };
+/// std::process::exit(code);
+/// # }
+/// ```
+///
+/// Errors of the bench suite convert from [`anyhow::Error`] into [`Error::Suite`], and the error converts into
+/// [`anyhow::Error`] in turn, keeping the chain of its causes.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The options of the benchmark are invalid, e.g. conflicting command line arguments.
+    InvalidOptions(String),
+    /// The [preflight check](crate::BenchSuite::preflight) of the bench suite failed.
+    Preflight(anyhow::Error),
+    /// The bench suite failed, e.g. to create the state or to set up a worker.
+    Suite(anyhow::Error),
+    /// The collector failed, e.g. the TUI could not set up the terminal.
+    Collector(anyhow::Error),
+    /// Reading or writing a file or socket besides the ones of the bench suite failed, e.g. the stream file, a
+    /// report or the Prometheus endpoint.
+    Io(anyhow::Error),
+    /// A report could not be loaded or merged, e.g. a report passed to `--merge-reports` is not a valid report or
+    /// was recorded with other histogram settings than the others.
+    Report(anyhow::Error),
+    /// Some of the assertions failed. Holds the results of all the assertions.
+    AssertionFailed(Vec<AssertionResult>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidOptions(msg) => write!(f, "{msg}"),
+            Error::Preflight(_) => write!(f, "preflight check failed"),
+            Error::Suite(e) | Error::Collector(e) | Error::Io(e) | Error::Report(e) => write!(f, "{e}"),
+            Error::AssertionFailed(assertions) => {
+                let failed = assertions.iter().filter(|a| !a.passed).collect::<Vec<_>>();
+                write!(f, "{} of {} assertions failed: ", failed.len(), assertions.len())?;
+                for (i, a) in failed.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", a.assertion)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Preflight(e) => Some(e.as_ref()),
+            // the message is the one of the wrapped error, so skip to its source
+            Error::Suite(e) | Error::Collector(e) | Error::Io(e) | Error::Report(e) => e.source(),
+            Error::InvalidOptions(_) | Error::AssertionFailed(_) => None,
+        }
+    }
+}
+
+impl From<anyhow::Error> for Error {
+    fn from(e: anyhow::Error) -> Self {
+        Error::Suite(e)
+    }
+}
@@ -1,24 +1,274 @@
 //! A simple wrapper around [`hdrhistogram::Histogram`] for latency measurements.
-use std::time::Duration;
+use std::{
+    io::Write,
+    time::{Duration, SystemTime},
+};
 
-use hdrhistogram::{Histogram, RecordError};
+use anyhow::anyhow;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hdrhistogram::{
+    errors::AdditionError,
+    serialization::{
+        interval_log::{IntervalLogIterator, IntervalLogWriterBuilder, LogEntry},
+        Deserializer, Serializer, V2DeflateSerializer,
+    },
+    Histogram,
+};
 
-pub(crate) const PERCENTAGES: &[f64] = &[10.0, 25.0, 50.0, 75.0, 90.0, 95.0, 99.0, 99.9, 99.99];
+/// The percentages at which latencies are shown in the reports.
+pub const PERCENTAGES: &[f64] = &[10.0, 25.0, 50.0, 75.0, 90.0, 95.0, 99.0, 99.9, 99.99];
+
+/// Number of the samples a quantile needs beyond it to be reliable, e.g. 1000 samples for p99.
+const TAIL_SAMPLES: f64 = 10.0;
+
+/// What to do with latencies beyond the trackable range of a [`LatencyHistogram`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Grow the histogram to track any latency.
+    #[default]
+    Resize,
+    /// Clip latencies at [`HistogramConfig::max`] and count them, see [`LatencyHistogram::saturated_count`].
+    Saturate,
+}
+
+/// Configuration of a [`LatencyHistogram`].
+#[derive(Clone, Copy, Debug)]
+pub struct HistogramConfig {
+    /// Number of significant decimal digits, from 0 to 5.
+    pub sigfig: u8,
+    /// The highest trackable latency, or the initial one when the histogram resizes.
+    pub max: Duration,
+    /// What to do with latencies above [`HistogramConfig::max`].
+    pub overflow: OverflowPolicy,
+    /// Record zero latencies as they are instead of clamping them to 1ns.
+    ///
+    /// Zero latencies are counted either way, see [`LatencyHistogram::zero_count`].
+    pub keep_zeros: bool,
+    /// The interval at which iterations are expected to start, to correct for coordinated omission.
+    ///
+    /// A closed-loop benchmark does not start iterations while the target stalls, so the latencies of the
+    /// iterations it would have started are never recorded. When set, a latency longer than the interval is
+    /// recorded along with the latencies of the iterations missed meanwhile, via
+    /// [`Histogram::record_correct`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rlt::{HistogramConfig, LatencyHistogram};
+    /// # use std::time::Duration;
+    /// let corrected = HistogramConfig { expected_interval: Some(Duration::from_millis(1)), ..Default::default() };
+    /// let (mut raw, mut corrected) = (LatencyHistogram::new(), LatencyHistogram::with_config(corrected));
+    /// for hist in [&mut raw, &mut corrected] {
+    ///     for _ in 0..1000 {
+    ///         hist.record(Duration::from_micros(500));
+    ///     }
+    ///     // the target stalls for a second
+    ///     hist.record(Duration::from_secs(1));
+    /// }
+    /// assert!(raw.percentile(99.0) < Duration::from_millis(1));
+    /// assert!(corrected.percentile(99.0) > Duration::from_millis(900));
+    /// ```
+    pub expected_interval: Option<Duration>,
+}
+
+impl Default for HistogramConfig {
+    fn default() -> Self {
+        Self {
+            sigfig: 3,
+            max: Duration::from_secs(60),
+            overflow: OverflowPolicy::Resize,
+            keep_zeros: false,
+            expected_interval: None,
+        }
+    }
+}
 
 /// A simple wrapper around [`hdrhistogram::Histogram`] for latency measurements.
+///
+/// The statistics ([`min`](Self::min), [`max`](Self::max), [`mean`](Self::mean), [`stdev`](Self::stdev) and
+/// the quantiles) are only meaningful when the histogram is not empty, otherwise they are all zero. Check
+/// [`is_empty`](Self::is_empty) or [`count`](Self::count) first.
+#[derive(Clone)]
 pub struct LatencyHistogram {
     hist: Histogram<u64>,
+    saturated: u64,
+    zeros: u64,
+    keep_zeros: bool,
+    /// The expected interval in nanoseconds, or zero if latencies are not corrected.
+    expected_interval: u64,
 }
 
 impl LatencyHistogram {
     /// Creates a new latency histogram.
     pub fn new() -> LatencyHistogram {
-        Self { hist: Histogram::<u64>::new(3).expect("create histogram") }
+        Self::with_config(HistogramConfig::default())
+    }
+
+    /// Creates a new latency histogram with the given configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sigfig` is above 5 or `max` is shorter than 2ns.
+    pub fn with_config(config: HistogramConfig) -> LatencyHistogram {
+        let max = u64::try_from(config.max.as_nanos()).unwrap_or(u64::MAX);
+        let mut hist = Histogram::<u64>::new_with_bounds(1, max, config.sigfig).expect("create histogram");
+        hist.auto(config.overflow == OverflowPolicy::Resize);
+        let expected_interval = config
+            .expected_interval
+            .map_or(0, |d| d.as_nanos().try_into().unwrap_or(u64::MAX));
+        Self {
+            hist,
+            saturated: 0,
+            zeros: 0,
+            keep_zeros: config.keep_zeros,
+            expected_interval,
+        }
+    }
+
+    /// Creates a new latency histogram with the given number of significant digits.
+    ///
+    /// Fewer digits trade precision for a smaller memory footprint.
+    pub(crate) fn with_sigfig(sigfig: u8) -> LatencyHistogram {
+        Self::with_config(HistogramConfig { sigfig, ..Default::default() })
+    }
+
+    /// Adds all the latencies recorded in another histogram.
+    pub(crate) fn add(&mut self, other: &LatencyHistogram) -> Result<(), AdditionError> {
+        self.hist.add(&other.hist)?;
+        self.saturated += other.saturated;
+        self.zeros += other.zeros;
+        Ok(())
+    }
+
+    /// Removes the latencies recorded in another histogram, which must all have been recorded in this one.
+    pub(crate) fn subtract(&mut self, other: &LatencyHistogram) -> anyhow::Result<()> {
+        self.hist.subtract(&other.hist)?;
+        self.saturated -= other.saturated;
+        self.zeros = self.zeros.saturating_sub(other.zeros);
+        Ok(())
+    }
+
+    /// Merges all the latencies recorded in another histogram into this one.
+    ///
+    /// Unlike [`LatencyHistogram::add`], the range of this histogram grows as needed, so histograms loaded
+    /// with [`LatencyHistogram::from_base64`] can be merged as well.
+    pub fn merge(&mut self, other: &LatencyHistogram) -> anyhow::Result<()> {
+        self.hist.auto(true);
+        Ok(self.add(other)?)
+    }
+
+    /// Writes the histogram as a single interval of an HdrHistogram interval log (`.hgrm`).
+    ///
+    /// `elapsed` is the duration the latencies were recorded over. Values are in nanoseconds.
+    pub fn serialize(&self, w: &mut impl Write, elapsed: Duration) -> anyhow::Result<()> {
+        let mut serializer = V2DeflateSerializer::new();
+        let mut log = IntervalLogWriterBuilder::new()
+            .add_comment("Latencies recorded by rlt, in nanoseconds")
+            .with_start_time(SystemTime::now() - elapsed)
+            .with_max_value_divisor(1e9)
+            .begin_log_with(w, &mut serializer)?;
+        log.write_histogram(&self.hist, Duration::ZERO, elapsed, None)?;
+        Ok(())
+    }
+
+    /// Writes the header of an HdrHistogram interval log whose intervals are relative to the given time, see
+    /// [`LatencyHistogram::write_interval`]. Values are in nanoseconds.
+    pub(crate) fn begin_interval_log(w: &mut impl Write, start: SystemTime) -> anyhow::Result<()> {
+        IntervalLogWriterBuilder::new()
+            .add_comment("Latencies recorded by rlt, in nanoseconds")
+            .with_start_time(start)
+            .with_base_time(start)
+            .begin_log_with(w, &mut V2DeflateSerializer::new())?;
+        Ok(())
+    }
+
+    /// Appends the histogram as an interval to a log begun with [`LatencyHistogram::begin_interval_log`].
+    ///
+    /// `start` is the start of the interval relative to the base time of the log.
+    pub(crate) fn write_interval(&self, w: &mut impl Write, start: Duration, duration: Duration) -> anyhow::Result<()> {
+        let mut serializer = V2DeflateSerializer::new();
+        // without any header, the builder writes nothing before the interval
+        let mut log = IntervalLogWriterBuilder::new().begin_log_with(w, &mut serializer)?;
+        log.write_histogram(&self.hist, start, duration, None)?;
+        Ok(())
+    }
+
+    /// Loads a histogram from an interval log written by [`LatencyHistogram::serialize`].
+    ///
+    /// All the intervals in the log are merged into one histogram.
+    pub fn from_serialized(log: &[u8]) -> anyhow::Result<Self> {
+        let mut hist = Self::new();
+        for entry in IntervalLogIterator::new(log) {
+            match entry.map_err(|e| anyhow!("invalid interval log: {e:?}"))? {
+                LogEntry::Interval(interval) => hist.add(&Self::from_base64(interval.encoded_histogram())?)?,
+                LogEntry::StartTime(_) | LogEntry::BaseTime(_) => (),
+            }
+        }
+        Ok(hist)
+    }
+
+    /// Encodes the histogram in the compressed V2 format, as base64.
+    pub fn to_base64(&self) -> anyhow::Result<String> {
+        let mut buf = Vec::new();
+        V2DeflateSerializer::new().serialize(&self.hist, &mut buf)?;
+        Ok(STANDARD.encode(buf))
+    }
+
+    /// Decodes a histogram encoded by [`LatencyHistogram::to_base64`].
+    pub fn from_base64(s: &str) -> anyhow::Result<Self> {
+        let buf = STANDARD.decode(s)?;
+        let hist = Deserializer::new().deserialize(&mut buf.as_slice())?;
+        Ok(Self {
+            hist,
+            saturated: 0,
+            zeros: 0,
+            keep_zeros: false,
+            expected_interval: 0,
+        })
     }
 
     /// Records a latency value.
-    pub fn record(&mut self, d: Duration) -> Result<(), RecordError> {
-        self.hist.record(d.as_nanos() as u64)
+    ///
+    /// Latencies beyond the trackable range are clipped at its maximum and counted, see
+    /// [`LatencyHistogram::saturated_count`]. Zero latencies are clamped to 1ns unless
+    /// [`HistogramConfig::keep_zeros`] is set, so they do not distort the minimum, and counted, see
+    /// [`LatencyHistogram::zero_count`]. The latencies of missed iterations are recorded as well if
+    /// [`HistogramConfig::expected_interval`] is set.
+    pub fn record(&mut self, d: Duration) {
+        let mut v = u64::try_from(d.as_nanos()).unwrap_or(u64::MAX);
+        if v == 0 {
+            self.zeros += 1;
+            if !self.keep_zeros {
+                v = 1;
+            }
+        }
+        if self.expected_interval > 0 && v > self.expected_interval {
+            if self.hist.record_correct(v, self.expected_interval).is_ok() {
+                return;
+            }
+            // record the latencies of the missed iterations which are still trackable
+            let mut missed = v - self.expected_interval;
+            while missed >= self.expected_interval {
+                self.hist.saturating_record(missed);
+                missed -= self.expected_interval;
+            }
+        }
+        if self.hist.record(v).is_err() {
+            self.hist.saturating_record(v);
+            self.saturated += 1;
+        }
+    }
+
+    /// Returns the number of latencies which were clipped at the maximum trackable latency.
+    ///
+    /// When it is not zero, the percentiles near the tail are lower than the real ones.
+    pub fn saturated_count(&self) -> u64 {
+        self.saturated
+    }
+
+    /// Returns the number of zero latencies which were recorded.
+    pub fn zero_count(&self) -> u64 {
+        self.zeros
     }
 
     /// Returns true if this histogram has no recorded values.
@@ -26,6 +276,11 @@ impl LatencyHistogram {
         self.hist.is_empty()
     }
 
+    /// Returns the number of recorded latencies.
+    pub fn count(&self) -> u64 {
+        self.hist.len()
+    }
+
     /// Get the highest recorded latency in the histogram.
     pub fn max(&self) -> Duration {
         Duration::from_nanos(self.hist.max())
@@ -51,12 +306,23 @@ impl LatencyHistogram {
         self.value_at_quantile(0.5)
     }
 
-    /// Get the latency at a given quantile.
+    /// Get the latency at a given quantile, from 0 to 1.
     pub fn value_at_quantile(&self, q: f64) -> Duration {
         Duration::from_nanos(self.hist.value_at_quantile(q))
     }
 
-    /// Iterate through histogram values by quantile levels.
+    /// Get the latency at a given percentile, from 0 to 100.
+    pub fn percentile(&self, p: f64) -> Duration {
+        self.value_at_quantile(p / 100.0)
+    }
+
+    /// Get the number of latencies recorded in the given range, inclusive.
+    pub fn count_between(&self, low: Duration, high: Duration) -> u64 {
+        self.hist.count_between(low.as_nanos() as u64, high.as_nanos() as u64)
+    }
+
+    /// Iterate through histogram values by quantile levels, yielding each latency with the number of
+    /// latencies recorded since the previous one. Yields nothing when the histogram is empty.
     ///
     /// See [`hdrhistogram::Histogram::iter_quantiles`] for more details.
     pub fn quantiles(&self) -> impl Iterator<Item = (Duration, u64)> + '_ {
@@ -71,9 +337,38 @@ impl LatencyHistogram {
             .filter(|(_, n)| *n > 0)
     }
 
-    /// Compute each latency value at the given percentages.
+    /// Compute each latency value at the given percentages, e.g. [`PERCENTAGES`].
     pub fn percentiles<'a>(&'a self, percentages: &'a [f64]) -> impl Iterator<Item = (f64, Duration)> + 'a {
-        percentages.iter().map(|&p| (p, self.value_at_quantile(p / 100.0)))
+        percentages.iter().map(|&p| (p, self.percentile(p)))
+    }
+
+    /// The highest quantile, from 0 to 1, backed by enough samples to be reported.
+    ///
+    /// A quantile `q` needs at least `10 / (1 - q)` samples, so that 10 of them lie beyond it: 100 samples for
+    /// p90, 1000 for p99 and so on. Higher quantiles are mostly decided by a handful of the slowest samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rlt::LatencyHistogram;
+    /// # use std::time::Duration;
+    /// let mut hist = LatencyHistogram::new();
+    /// for _ in 0..1000 {
+    ///     hist.record(Duration::from_millis(1));
+    /// }
+    /// assert!(hist.is_reliable_percentile(99.0));
+    /// assert!(!hist.is_reliable_percentile(99.9));
+    /// ```
+    pub fn reliable_quantile_limit(&self) -> f64 {
+        (1.0 - TAIL_SAMPLES / self.count() as f64).max(0.0)
+    }
+
+    /// Whether the latency at the given percentile, from 0 to 100, is backed by enough samples to be reported.
+    ///
+    /// See [`LatencyHistogram::reliable_quantile_limit`].
+    pub fn is_reliable_percentile(&self, p: f64) -> bool {
+        // tolerate the rounding of the limit, e.g. p99 of exactly 1000 samples
+        !self.is_empty() && p / 100.0 <= self.reliable_quantile_limit() + 1e-9
     }
 }
 
@@ -82,3 +377,86 @@ impl Default for LatencyHistogram {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+    const NS: Duration = Duration::from_nanos(1);
+
+    /// Half zero and half 1ms latencies.
+    fn half_zeros(config: HistogramConfig) -> LatencyHistogram {
+        let mut hist = LatencyHistogram::with_config(config);
+        for _ in 0..50 {
+            hist.record(Duration::ZERO);
+            hist.record(MS);
+        }
+        hist
+    }
+
+    /// Whether the latency is the given one within the precision of the default histogram.
+    fn near(actual: Duration, expected: Duration) -> bool {
+        actual.abs_diff(expected) <= expected / 1000
+    }
+
+    #[test]
+    fn zeros_are_clamped_to_1ns_and_counted() {
+        let hist = half_zeros(HistogramConfig::default());
+        assert_eq!(hist.count(), 100);
+        assert_eq!(hist.zero_count(), 50);
+        assert_eq!(hist.min(), NS);
+        assert!(near(hist.max(), MS));
+        assert!(near(hist.mean(), MS / 2));
+        assert_eq!(hist.median(), NS);
+        assert!(near(hist.percentile(99.0), MS));
+    }
+
+    #[test]
+    fn zeros_are_kept_if_configured() {
+        let hist = half_zeros(HistogramConfig { keep_zeros: true, ..Default::default() });
+        assert_eq!(hist.count(), 100);
+        assert_eq!(hist.zero_count(), 50);
+        assert_eq!(hist.min(), Duration::ZERO);
+        assert!(near(hist.mean(), MS / 2));
+        assert_eq!(hist.median(), Duration::ZERO);
+        assert!(near(hist.percentile(99.0), MS));
+    }
+
+    #[test]
+    fn only_zeros() {
+        let mut clamped = LatencyHistogram::new();
+        let mut kept = LatencyHistogram::with_config(HistogramConfig { keep_zeros: true, ..Default::default() });
+        for hist in [&mut clamped, &mut kept] {
+            for _ in 0..10 {
+                hist.record(Duration::ZERO);
+            }
+            assert_eq!((hist.count(), hist.zero_count()), (10, 10));
+        }
+        assert_eq!((clamped.min(), clamped.mean(), clamped.max()), (NS, NS, NS));
+        assert_eq!(
+            (kept.min(), kept.mean(), kept.max()),
+            (Duration::ZERO, Duration::ZERO, Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn non_zero_latencies_are_not_counted_as_zeros() {
+        let mut hist = LatencyHistogram::new();
+        hist.record(NS);
+        hist.record(MS);
+        assert_eq!(hist.zero_count(), 0);
+        assert_eq!(hist.min(), NS);
+    }
+
+    #[test]
+    fn zero_count_follows_add_and_subtract() {
+        let mut total = half_zeros(HistogramConfig::default());
+        let part = half_zeros(HistogramConfig::default());
+        total.add(&part).unwrap();
+        assert_eq!((total.count(), total.zero_count()), (200, 100));
+
+        total.subtract(&part).unwrap();
+        assert_eq!((total.count(), total.zero_count()), (100, 50));
+    }
+}
@@ -0,0 +1,218 @@
+//! A ready-made bench suite for HTTP targets.
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{HeaderMap, HeaderName, HeaderValue, Method, Request, Uri};
+use http_body_util::{BodyExt, Full};
+use hyper_tls::HttpsConnector;
+use hyper_util::{
+    client::legacy::{connect::HttpConnector, Client},
+    rt::TokioExecutor,
+};
+use tokio::time::Instant;
+
+use crate::{
+    report::{BenchResult, IterReport},
+    runner::{splitmix64, BenchSuite, ErrorClassifier, IterInfo},
+};
+
+/// A request sent by an [`HttpBenchSuite`].
+#[derive(Clone, Debug)]
+pub struct HttpRequest {
+    /// The method of the request.
+    pub method: Method,
+    /// The target of the request, `http` or `https`.
+    pub uri: Uri,
+    /// The share of the iterations which send the request, relative to the weights of the other requests.
+    pub weight: u32,
+    /// The headers of the request.
+    pub headers: HeaderMap,
+    /// The body of the request.
+    pub body: Bytes,
+}
+
+impl HttpRequest {
+    /// Create a request with the given method and target, a weight of 1, no headers and an empty body.
+    pub fn new(method: Method, uri: Uri) -> Self {
+        Self {
+            method,
+            uri,
+            weight: 1,
+            headers: HeaderMap::new(),
+            body: Bytes::new(),
+        }
+    }
+
+    /// Create a `GET` request to the given target.
+    pub fn get(uri: Uri) -> Self {
+        Self::new(Method::GET, uri)
+    }
+
+    /// Set the weight of the request.
+    pub fn weight(mut self, weight: u32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Add a header to the request, keeping the ones of the same name.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Set the body of the request.
+    pub fn body(mut self, body: impl Into<Bytes>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    fn to_request(&self) -> Request<Full<Bytes>> {
+        let mut req = Request::new(Full::new(self.body.clone()));
+        *req.method_mut() = self.method.clone();
+        *req.uri_mut() = self.uri.clone();
+        *req.headers_mut() = self.headers.clone();
+        req
+    }
+}
+
+/// The connection pool of a worker of an [`HttpBenchSuite`].
+pub struct HttpWorker {
+    client: Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
+}
+
+/// A bench suite which sends HTTP requests, each iteration one of the given requests picked by their weights.
+///
+/// The pick depends only on the worker and the sequence number of the iteration, so every run sends the same
+/// requests in the same order. Each worker keeps its own pool of connections, which it reuses across the
+/// iterations.
+///
+/// Every iteration reports the status of the response, the body of the request as the bytes out and the one
+/// of the response as the bytes in, the time to the first byte and to the end of the body as the `ttfb` and
+/// `body` phases, and the method and target as the context of the slowest iterations. The
+/// [preflight check](BenchSuite::preflight) sends every request once and fails on a client or server error.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use rlt::{cli::BenchCli, HttpBenchSuite, HttpRequest};
+/// # use clap::Parser;
+/// # async fn run() -> anyhow::Result<()> {
+/// let suite = HttpBenchSuite::new([
+///     HttpRequest::get("http://localhost:8080/items".parse()?).weight(9),
+///     HttpRequest::new(http::Method::POST, "http://localhost:8080/items".parse()?)
+///         .header(http::header::CONTENT_TYPE, "application/json".parse()?)
+///         .body(r#"{"name":"rlt"}"#),
+/// ])?;
+/// rlt::cli::run(BenchCli::parse(), suite).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct HttpBenchSuite {
+    requests: Arc<[HttpRequest]>,
+    /// The running totals of the weights of the requests.
+    weights: Arc<[u64]>,
+}
+
+impl HttpBenchSuite {
+    /// Create a suite which sends the given requests.
+    ///
+    /// Fails if there is no request with a weight above zero.
+    pub fn new(requests: impl IntoIterator<Item = HttpRequest>) -> Result<Self> {
+        let requests: Arc<[HttpRequest]> = requests.into_iter().collect();
+        let weights: Arc<[u64]> = requests
+            .iter()
+            .scan(0, |total, r| {
+                *total += r.weight as u64;
+                Some(*total)
+            })
+            .collect();
+        anyhow::ensure!(
+            weights.last().is_some_and(|&total| total > 0),
+            "no request to send, every weight is zero"
+        );
+        Ok(Self { requests, weights })
+    }
+
+    /// The requests of the suite.
+    pub fn requests(&self) -> &[HttpRequest] {
+        &self.requests
+    }
+
+    /// Pick the request of an iteration.
+    fn pick(&self, info: &IterInfo) -> &HttpRequest {
+        let total = self.weights[self.weights.len() - 1];
+        let r = splitmix64((info.worker_id as u64) << 40 ^ info.worker_seq) % total;
+        &self.requests[self.weights.partition_point(|&w| w <= r)]
+    }
+}
+
+#[async_trait]
+impl BenchSuite for HttpBenchSuite {
+    type WorkerState = HttpWorker;
+
+    async fn preflight(&self) -> Result<()> {
+        let worker = self.state(0).await?;
+        for req in self.requests.iter().filter(|r| r.weight > 0) {
+            let status = worker.client.request(req.to_request()).await?.status();
+            anyhow::ensure!(
+                !status.is_client_error() && !status.is_server_error(),
+                "{} {} returned {status}",
+                req.method,
+                req.uri
+            );
+        }
+        Ok(())
+    }
+
+    async fn state(&self, _: u32) -> Result<Self::WorkerState> {
+        let client = Client::builder(TokioExecutor::new()).build(HttpsConnector::new());
+        Ok(HttpWorker { client })
+    }
+
+    async fn bench(&mut self, worker: &mut Self::WorkerState, info: &IterInfo) -> BenchResult<IterReport> {
+        let req = self.pick(info);
+        let t = Instant::now();
+        let mut resp = worker.client.request(req.to_request()).await?;
+        let ttfb = t.elapsed();
+        let status = resp.status().into();
+        let mut bytes_in = 0;
+        while let Some(frame) = resp.frame().await {
+            bytes_in += frame?.data_ref().map_or(0, Bytes::len) as u64;
+        }
+        let duration = t.elapsed();
+        Ok(IterReport {
+            duration,
+            status,
+            bytes_in,
+            bytes_out: req.body.len() as u64,
+            phases: vec![("ttfb".into(), ttfb), ("body".into(), duration - ttfb)],
+            context: Some(format!("{} {}", req.method, req.uri)),
+            ..Default::default()
+        })
+    }
+
+    fn error_classifier(&self) -> Option<ErrorClassifier> {
+        Some(classify)
+    }
+}
+
+/// Group the errors of the hyper client by their cause.
+fn classify(e: &anyhow::Error) -> Option<String> {
+    if let Some(e) = e.downcast_ref::<hyper_util::client::legacy::Error>() {
+        if e.is_connect() {
+            return Some("hyper: connect error".to_string());
+        }
+    }
+    let e = e.chain().find_map(|e| e.downcast_ref::<hyper::Error>())?;
+    let class = match () {
+        _ if e.is_timeout() => "timeout",
+        _ if e.is_closed() => "connection closed",
+        _ if e.is_incomplete_message() => "incomplete message",
+        _ if e.is_parse() => "parse error",
+        _ => return None,
+    };
+    Some(format!("hyper: {class}"))
+}
@@ -20,7 +20,7 @@
 //! use anyhow::Result;
 //! use async_trait::async_trait;
 //! use clap::Parser;
-//! use rlt::{cli::BenchCli, IterInfo, IterReport, StatelessBenchSuite, Status};
+//! use rlt::{cli::BenchCli, BenchResult, IterInfo, IterReport, StatelessBenchSuite, Status};
 //! use tokio::time::Instant;
 //!
 //! #[derive(Clone)]
@@ -28,7 +28,7 @@
 //!
 //! #[async_trait]
 //! impl StatelessBenchSuite for SimpleBench {
-//!     async fn bench(&mut self, _: &IterInfo) -> Result<IterReport> {
+//!     async fn bench(&mut self, _: &IterInfo) -> BenchResult<IterReport> {
 //!         let t = Instant::now();
 //!         // do the work here
 //!         let duration = t.elapsed();
@@ -38,6 +38,7 @@
 //!             status: Status::success(0),
 //!             bytes: 42, // bytes processed in current iteration
 //!             items: 5,  // items processed in current iteration
+//!             ..Default::default() // e.g. the received and sent bytes, or named sub-durations
 //!         };
 //!         Ok(report)
 //!     }
@@ -45,20 +46,36 @@
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<()> {
-//!     rlt::cli::run(BenchCli::parse(), SimpleBench).await
+//!     Ok(rlt::cli::run(BenchCli::parse(), SimpleBench).await?)
 //! }
 //! ```
 //!
 //! Stateful bench is also supported, see the [examples/http_reqwest](https://github.com/wfxr/rlt/blob/main/examples/http_reqwest.rs).
 #![deny(missing_docs)]
 
+mod assertion;
+mod builder;
 mod clock;
+#[cfg(feature = "control")]
+mod control;
 mod duration;
+mod error;
 mod histogram;
+#[cfg(feature = "http-suite")]
+mod http_suite;
+mod partial_report;
+mod pause;
+#[cfg(feature = "prometheus")]
+mod prometheus;
 mod report;
+#[cfg(feature = "sysinfo")]
+mod resource;
 mod runner;
+#[cfg(any(feature = "prometheus", feature = "control"))]
+mod server;
 mod stats;
 mod status;
+mod status_file;
 mod util;
 
 pub mod cli;
@@ -66,12 +83,34 @@ pub mod collector;
 pub mod reporter;
 
 pub use crate::{
+    assertion::{Assertion, AssertionResult},
+    builder::BenchBuilder,
+    clock::Clock,
+    error::Error,
+    histogram::{HistogramConfig, LatencyHistogram, OverflowPolicy, PERCENTAGES},
+    pause::PauseControl,
     report::BenchReport,
-    report::IterReport,
+    report::{
+        BenchResult, Discarded, IterError, IterReport, IterResult, RunMetadata, Saturation, SecondSample, SetupStats,
+        SlowIteration, SuspectDurations,
+    },
     runner::IterInfo,
-    runner::{BenchSuite, StatelessBenchSuite},
+    runner::{BenchOpts, BenchPhase, Bound, CancelReason, InFlight, Runner},
+    runner::{
+        BenchSuite, Blocking, BlockingBenchSuite, ErrorClassifier, ErrorOpts, Shared, SharedBenchSuite, StableOpts,
+        StatelessBenchSuite,
+    },
     status::{Status, StatusKind},
+    util::ByteUnit,
 };
 
+#[cfg(feature = "sysinfo")]
+pub use crate::resource::{ResourceSample, ResourceUsage};
+
+#[cfg(feature = "http-suite")]
+pub use crate::http_suite::{HttpBenchSuite, HttpRequest, HttpWorker};
+
 #[cfg(feature = "tracing")]
-pub use tui_logger::tracing_subscriber_layer as tui_tracing_subscriber_layer;
+pub use crate::collector::tui::{
+    init_tracing, tracing_subscriber_layer as tui_tracing_subscriber_layer, TuiTracingLayer, DEFAULT_LOG_BUFFER,
+};
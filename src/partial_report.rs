@@ -0,0 +1,122 @@
+//! A report file rewritten with the report of the run so far while the benchmark is running.
+use std::{
+    path::PathBuf,
+    sync::{Arc, OnceLock},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    clock::Clock,
+    collector::{ReportAggregator, ReportSink},
+    pause::PauseControl,
+    report::{IterResult, RunMetadata},
+    reporter::BenchReporter,
+    runner::BenchOpts,
+    util::write_atomic,
+};
+
+/// A report sink which feeds the aggregator of the partial reports.
+///
+/// The aggregator is separate from the one of the collector, so the statistics are not reset with the TUI.
+pub(crate) struct PartialReportSink {
+    clock: Clock,
+    agg: Arc<Mutex<ReportAggregator>>,
+}
+
+impl ReportSink for PartialReportSink {
+    fn on_report(&mut self, res: &IterResult) -> Result<()> {
+        self.agg.lock().ingest_ref(res, self.clock.elapsed())
+    }
+}
+
+struct Writer {
+    path: PathBuf,
+    reporter: Arc<dyn BenchReporter + Send + Sync>,
+    clock: Clock,
+    concurrency: u32,
+    pause: Arc<PauseControl>,
+    metadata: RunMetadata,
+    started_at: Arc<OnceLock<SystemTime>>,
+    agg: Arc<Mutex<ReportAggregator>>,
+}
+
+impl Writer {
+    /// Rewrite the file with the report of the run so far.
+    fn write(&self) -> Result<()> {
+        let agg = self.agg.lock().clone();
+        let mut report = agg.into_report(self.clock.elapsed(), self.concurrency);
+        report.paused_duration = self.pause.paused_duration();
+        report.pause_count = self.pause.pause_count();
+        report.metadata = RunMetadata {
+            started_at: self.started_at.get().copied(),
+            pauses: self.pause.pauses(),
+            ..self.metadata.clone()
+        };
+        write_atomic(&self.path, |w| self.reporter.print_partial(w, &report))
+    }
+}
+
+/// A running writer of the partial reports.
+pub(crate) struct PartialReport {
+    cancel: CancellationToken,
+    handle: JoinHandle<Result<()>>,
+}
+
+impl PartialReport {
+    /// Start rewriting the report file at the given path every interval, with the given reporter.
+    ///
+    /// The reports are stamped with `started_at` once the benchmark is running.
+    ///
+    /// Returns the writer and a sink which feeds the report from the iteration results. The final report is
+    /// written by the caller once the writer is shut down.
+    pub(crate) fn start(
+        path: impl Into<PathBuf>,
+        interval: Duration,
+        reporter: Arc<dyn BenchReporter + Send + Sync>,
+        opts: &BenchOpts,
+        pause: Arc<PauseControl>,
+        metadata: RunMetadata,
+        started_at: Arc<OnceLock<SystemTime>>,
+    ) -> (Self, PartialReportSink) {
+        let agg = Arc::new(Mutex::new(ReportAggregator::new(opts)));
+        let sink = PartialReportSink { clock: opts.clock.clone(), agg: agg.clone() };
+        let writer = Writer {
+            path: path.into(),
+            reporter,
+            clock: opts.clock.clone(),
+            concurrency: opts.concurrency,
+            pause,
+            metadata,
+            started_at,
+            agg,
+        };
+
+        let cancel = CancellationToken::new();
+        let handle = tokio::spawn({
+            let cancel = cancel.clone();
+            async move {
+                let mut ticker = tokio::time::interval(interval);
+                ticker.tick().await;
+                loop {
+                    tokio::select! {
+                        _ = cancel.cancelled() => return Ok(()),
+                        _ = ticker.tick() => writer.write()?,
+                    }
+                }
+            }
+        });
+
+        (Self { cancel, handle }, sink)
+    }
+
+    /// Stop the periodic updates.
+    pub(crate) async fn shutdown(self) -> Result<()> {
+        self.cancel.cancel();
+        self.handle.await?
+    }
+}
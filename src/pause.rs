@@ -0,0 +1,108 @@
+//! This module provides the pause control of the benchmark.
+use std::time::SystemTime;
+
+use parking_lot::Mutex;
+use tokio::{
+    sync::watch,
+    time::{Duration, Instant},
+};
+
+use crate::clock::Clock;
+
+/// Pauses and resumes the benchmark runner together with its logical clock.
+#[derive(Debug)]
+pub struct PauseControl {
+    clock: Clock,
+    tx: watch::Sender<bool>,
+    history: Mutex<PauseHistory>,
+}
+
+#[derive(Debug, Default)]
+struct PauseHistory {
+    count: u64,
+    /// Total duration of the finished pauses.
+    duration: Duration,
+    /// Start of the ongoing pause, if any.
+    since: Option<(Instant, SystemTime)>,
+    /// Wall-clock start and end of the finished pauses.
+    intervals: Vec<(SystemTime, SystemTime)>,
+}
+
+impl PauseControl {
+    /// Create a new pause control for the given clock, initially not paused.
+    pub fn new(clock: Clock) -> Self {
+        Self {
+            clock,
+            tx: watch::channel(false).0,
+            history: Mutex::default(),
+        }
+    }
+
+    /// Number of times the benchmark has been paused.
+    pub fn pause_count(&self) -> u64 {
+        self.history.lock().count
+    }
+
+    /// Total wall-clock time spent paused, including the ongoing pause.
+    pub fn paused_duration(&self) -> Duration {
+        let history = self.history.lock();
+        history.duration + history.since.map(|(t, _)| t.elapsed()).unwrap_or_default()
+    }
+
+    /// Wall-clock start and end of every pause, with the ongoing pause ending now.
+    pub fn pauses(&self) -> Vec<(SystemTime, SystemTime)> {
+        let history = self.history.lock();
+        let ongoing = history.since.map(|(_, start)| (start, SystemTime::now()));
+        history.intervals.iter().copied().chain(ongoing).collect()
+    }
+
+    /// Returns true if the benchmark is paused.
+    pub fn is_paused(&self) -> bool {
+        *self.tx.borrow()
+    }
+
+    /// Pause the benchmark. Does nothing if it is already paused.
+    pub fn pause(&self) {
+        self.set_paused(true);
+    }
+
+    /// Resume the benchmark. Does nothing if it is not paused.
+    pub fn resume(&self) {
+        self.set_paused(false);
+    }
+
+    /// Toggle between paused and running.
+    pub fn toggle(&self) {
+        self.set_paused(!self.is_paused());
+    }
+
+    /// Subscribe to the changes of the paused state.
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.tx.subscribe()
+    }
+
+    fn set_paused(&self, paused: bool) {
+        self.tx.send_if_modified(|state| {
+            if *state == paused {
+                return false;
+            }
+            let mut history = self.history.lock();
+            match paused {
+                true => {
+                    self.clock.pause();
+                    history.count += 1;
+                    history.since = Some((Instant::now(), SystemTime::now()));
+                }
+                false => {
+                    self.clock.resume();
+                    if let Some((since, start)) = history.since.take() {
+                        history.duration += since.elapsed();
+                        history.intervals.push((start, SystemTime::now()));
+                    }
+                }
+            }
+            *state = paused;
+            true
+        });
+    }
+}
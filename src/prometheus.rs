@@ -0,0 +1,268 @@
+//! Expose live benchmark metrics in the Prometheus exposition format.
+use std::{
+    fmt::Write,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use anyhow::Result;
+use hyper::{Method, StatusCode};
+use tokio::{net::TcpListener, sync::watch, task::JoinHandle};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    collector::ReportSink,
+    report::{BenchResult, IterReport, IterResult},
+    runner::InFlight,
+    server,
+};
+
+/// Upper bounds of the latency histogram buckets, in seconds.
+const LATENCY_BUCKETS: &[f64] = &[
+    0.0001, 0.00025, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// A registry of live metrics, updated from the iteration report stream.
+pub(crate) struct Metrics {
+    iters: AtomicU64,
+    items: AtomicU64,
+    bytes: AtomicU64,
+    errors: AtomicU64,
+    latency_buckets: Vec<AtomicU64>,
+    latency_sum_nanos: AtomicU64,
+    in_flight: InFlight,
+    running: AtomicBool,
+    pause: watch::Receiver<bool>,
+}
+
+impl Metrics {
+    fn new(in_flight: InFlight, pause: watch::Receiver<bool>) -> Self {
+        Self {
+            iters: AtomicU64::default(),
+            items: AtomicU64::default(),
+            bytes: AtomicU64::default(),
+            errors: AtomicU64::default(),
+            latency_buckets: LATENCY_BUCKETS.iter().map(|_| AtomicU64::default()).collect(),
+            latency_sum_nanos: AtomicU64::default(),
+            in_flight,
+            running: AtomicBool::new(true),
+            pause,
+        }
+    }
+
+    fn observe(&self, res: &BenchResult<IterReport>) {
+        let report = match res {
+            Ok(report) => report,
+            Err(_) => {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+        };
+
+        self.iters.fetch_add(1, Ordering::Relaxed);
+        self.items.fetch_add(report.items, Ordering::Relaxed);
+        self.bytes.fetch_add(report.total_bytes(), Ordering::Relaxed);
+        self.latency_sum_nanos
+            .fetch_add(report.duration.as_nanos() as u64, Ordering::Relaxed);
+
+        let secs = report.duration.as_secs_f64();
+        if let Some(i) = LATENCY_BUCKETS.iter().position(|&le| secs <= le) {
+            self.latency_buckets[i].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn finish(&self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+
+    /// Render all metrics in the Prometheus text exposition format.
+    fn render(&self) -> String {
+        let mut out = String::new();
+        let mut counter = |name: &str, help: &str, v: u64| {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} counter");
+            let _ = writeln!(out, "{name} {v}");
+        };
+        counter(
+            "rlt_iterations_total",
+            "Total number of iterations.",
+            self.iters.load(Ordering::Relaxed),
+        );
+        counter(
+            "rlt_items_total",
+            "Total number of processed items.",
+            self.items.load(Ordering::Relaxed),
+        );
+        counter(
+            "rlt_bytes_total",
+            "Total number of processed bytes.",
+            self.bytes.load(Ordering::Relaxed),
+        );
+        counter(
+            "rlt_errors_total",
+            "Total number of failed iterations.",
+            self.errors.load(Ordering::Relaxed),
+        );
+
+        let _ = writeln!(out, "# HELP rlt_latency_seconds Iteration latency in seconds.");
+        let _ = writeln!(out, "# TYPE rlt_latency_seconds histogram");
+        let mut cumulative = 0;
+        for (le, n) in LATENCY_BUCKETS.iter().zip(&self.latency_buckets) {
+            cumulative += n.load(Ordering::Relaxed);
+            let _ = writeln!(out, "rlt_latency_seconds_bucket{{le=\"{le}\"}} {cumulative}");
+        }
+        let count = self.iters.load(Ordering::Relaxed);
+        let sum = self.latency_sum_nanos.load(Ordering::Relaxed) as f64 / 1e9;
+        let _ = writeln!(out, "rlt_latency_seconds_bucket{{le=\"+Inf\"}} {count}");
+        let _ = writeln!(out, "rlt_latency_seconds_sum {sum}");
+        let _ = writeln!(out, "rlt_latency_seconds_count {count}");
+
+        let mut gauge = |name: &str, help: &str, v: u32| {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} gauge");
+            let _ = writeln!(out, "{name} {v}");
+        };
+        let running = self.running.load(Ordering::Relaxed);
+        let paused = running && *self.pause.borrow();
+        gauge(
+            "rlt_active_workers",
+            "Number of workers inside an iteration.",
+            if running { self.in_flight.get() } else { 0 },
+        );
+        gauge(
+            "rlt_paused",
+            "Whether the benchmark is paused (1) or not (0).",
+            paused as u32,
+        );
+
+        out
+    }
+}
+
+/// A report sink which accounts iteration reports into the shared metrics.
+pub(crate) struct MetricsSink(Arc<Metrics>);
+
+impl ReportSink for MetricsSink {
+    fn on_report(&mut self, res: &IterResult) -> Result<()> {
+        self.0.observe(&res.result);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.0.finish();
+        Ok(())
+    }
+}
+
+/// A running Prometheus metrics endpoint.
+pub(crate) struct PrometheusServer {
+    cancel: CancellationToken,
+    handle: JoinHandle<()>,
+}
+
+impl PrometheusServer {
+    /// Bind the metrics endpoint on the given address.
+    ///
+    /// Returns the server and a sink which feeds the metrics from the iteration reports. The active workers are
+    /// the iterations in flight of the runner.
+    pub(crate) async fn start(
+        addr: SocketAddr,
+        in_flight: InFlight,
+        pause: watch::Receiver<bool>,
+    ) -> Result<(Self, MetricsSink)> {
+        let listener = TcpListener::bind(addr).await?;
+        let metrics = Arc::new(Metrics::new(in_flight, pause));
+        let cancel = CancellationToken::new();
+        let sink = MetricsSink(metrics.clone());
+
+        let handle = tokio::spawn(server::serve(listener, cancel.clone(), move |req| {
+            match (req.method(), req.uri().path()) {
+                (&Method::GET, "/metrics") => server::response(
+                    StatusCode::OK,
+                    "text/plain; version=0.0.4; charset=utf-8",
+                    metrics.render(),
+                ),
+                _ => server::not_found(),
+            }
+        }));
+
+        Ok((Self { cancel, handle }, sink))
+    }
+
+    /// Stop serving and wait for the in-flight connections to finish.
+    pub(crate) async fn shutdown(self) -> Result<()> {
+        self.cancel.cancel();
+        Ok(self.handle.await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::{
+        clock::Clock,
+        pause::PauseControl,
+        runner::{BenchOpts, IterInfo, Runner, StatelessBenchSuite},
+        status::Status,
+    };
+
+    /// A suite whose iterations take a second on the clock.
+    #[derive(Clone)]
+    struct SleepBench(Clock);
+
+    #[async_trait]
+    impl StatelessBenchSuite for SleepBench {
+        async fn bench(&mut self, _: &IterInfo) -> BenchResult<IterReport> {
+            self.0.sleep(Duration::from_secs(1)).await;
+            Ok(IterReport::new(Duration::from_secs(1), Status::success(200)))
+        }
+    }
+
+    fn sample(metrics: &Metrics, name: &str) -> String {
+        let render = metrics.render();
+        let line = render.lines().find(|l| l.starts_with(&format!("{name} "))).unwrap();
+        line[name.len() + 1..].to_string()
+    }
+
+    #[tokio::test]
+    async fn active_workers_are_the_iterations_in_flight() {
+        let clock = Clock::manual();
+        let opts = BenchOpts {
+            concurrency: 3,
+            iterations: Some(3),
+            clock: clock.clone(),
+            ..Default::default()
+        };
+        let pause = Arc::new(PauseControl::new(clock.clone()));
+        let (res_tx, mut res_rx) = mpsc::unbounded_channel();
+        let runner = Runner::new(
+            SleepBench(clock.clone()),
+            opts,
+            res_tx,
+            pause.clone(),
+            CancellationToken::new(),
+        );
+        let metrics = Metrics::new(runner.in_flight(), pause.subscribe());
+        assert_eq!(sample(&metrics, "rlt_active_workers"), "0");
+
+        let run = tokio::spawn(runner.run());
+        while sample(&metrics, "rlt_active_workers") != "3" {
+            tokio::task::yield_now().await;
+        }
+        clock.advance(Duration::from_secs(1));
+        for _ in 0..3 {
+            metrics.observe(&res_rx.recv().await.unwrap().result);
+        }
+        run.await.unwrap().unwrap();
+        assert_eq!(sample(&metrics, "rlt_active_workers"), "0");
+        assert_eq!(sample(&metrics, "rlt_iterations_total"), "3");
+    }
+}
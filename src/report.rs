@@ -1,12 +1,19 @@
 //! The benchmark report module.
-use std::collections::HashMap;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    fmt,
+    time::SystemTime,
+};
 
 use tokio::time::Duration;
 
 use crate::{
+    assertion::AssertionResult,
     histogram::LatencyHistogram,
-    stats::IterStats,
-    status::{Status, StatusKind},
+    runner::{Bound, CancelReason, IterInfo},
+    stats::{IterStats, RateStats},
+    status::Status,
 };
 
 /// The iteration report.
@@ -16,40 +23,608 @@ pub struct IterReport {
     pub duration: Duration,
     /// The reported status of the iteration.
     pub status: Status,
-    /// The reported processed bytes of the iteration.
+    /// The reported processed bytes of the iteration, in no particular direction.
+    ///
+    /// Suites which distinguish the direction report [`bytes_in`](Self::bytes_in) and
+    /// [`bytes_out`](Self::bytes_out) instead. All of them are counted into the total bytes.
     pub bytes: u64,
+    /// The reported bytes received in the iteration, e.g. the size of a response.
+    pub bytes_in: u64,
+    /// The reported bytes sent in the iteration, e.g. the size of a request.
+    pub bytes_out: u64,
     /// The reported processed items of the iteration. Useful when testing services with batch support.
     pub items: u64,
+    /// Durations of the named phases of the iteration, e.g. the time to first byte of a request.
+    ///
+    /// Each phase is tracked in a latency histogram of its own, next to the one of the whole iteration.
+    pub phases: Vec<(Cow<'static, str>, Duration)>,
+    /// Optional description of the iteration, e.g. the request it sent, shown if it is among the slowest ones.
+    ///
+    /// See [`BenchOpts::slowest`](crate::runner::BenchOpts::slowest).
+    pub context: Option<String>,
+}
+
+impl IterReport {
+    /// Create a report of an iteration which took the given duration and processed one item, with no bytes and
+    /// no phases.
+    ///
+    /// Set the other fields on the returned report, or build it with struct update syntax from
+    /// [`IterReport::default`].
+    pub fn new(duration: Duration, status: Status) -> Self {
+        Self {
+            duration,
+            status,
+            bytes: 0,
+            bytes_in: 0,
+            bytes_out: 0,
+            items: 1,
+            phases: Vec::new(),
+            context: None,
+        }
+    }
+
+    /// The total bytes of the iteration, the undirected ones and those received and sent.
+    pub(crate) fn total_bytes(&self) -> u64 {
+        self.bytes + self.bytes_in + self.bytes_out
+    }
+}
+
+/// A successful report of an empty iteration which processed one item, see [`IterReport::new`].
+impl Default for IterReport {
+    fn default() -> Self {
+        Self::new(Duration::ZERO, Status::success(0))
+    }
+}
+
+/// The result of a bench iteration, see [`IterError`].
+pub type BenchResult<T> = std::result::Result<T, IterError>;
+
+/// The error of a failed iteration.
+///
+/// Any error which converts into [`anyhow::Error`] converts into an `IterError`, so `?` works as usual in bench
+/// functions. An error may carry the status and duration of the iteration, e.g. of a request which ran into a
+/// timeout. They are recorded in the status distribution and latency histogram, next to the error itself.
+#[derive(Debug)]
+pub struct IterError {
+    /// The status of the failed iteration, if known.
+    pub status: Option<Status>,
+    /// The duration of the failed iteration, if known.
+    pub duration: Option<Duration>,
+    /// The cause of the failure.
+    pub source: anyhow::Error,
+}
+
+impl IterError {
+    /// Create an error without a status or duration.
+    pub fn new(source: impl Into<anyhow::Error>) -> Self {
+        Self { status: None, duration: None, source: source.into() }
+    }
+
+    /// Attach the status of the failed iteration.
+    pub fn with_status(mut self, status: Status) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Attach the duration of the failed iteration.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+}
+
+impl<E: Into<anyhow::Error>> From<E> for IterError {
+    fn from(e: E) -> Self {
+        Self::new(e)
+    }
+}
+
+impl fmt::Display for IterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.source, f)
+    }
+}
+
+/// The result of a single iteration, sent from the runner to the report collector.
+#[derive(Debug)]
+pub struct IterResult {
+    /// Information about the iteration which produced the result.
+    pub info: IterInfo,
+    /// Elapsed time of the benchmark clock when the iteration started.
+    ///
+    /// The time-bucketed statistics count the iteration in the bucket it started in rather than the one it
+    /// finished in.
+    pub start: Duration,
+    /// The report of the iteration, or the error it failed with.
+    pub result: BenchResult<IterReport>,
 }
 
 /// The final benchmark report.
+#[derive(Clone)]
 pub struct BenchReport {
     /// Number of workers to run concurrently
     pub concurrency: u32,
     /// Iteration latency histogram.
     pub hist: LatencyHistogram,
+    /// Latency histograms of the iteration phases, in the order the phases were first reported.
+    pub phase_hists: Vec<(Cow<'static, str>, LatencyHistogram)>,
+    /// Histogram of the iteration latencies divided by their items, if tracked with
+    /// [`BenchOpts::per_item_latency`]. Iterations without items are not recorded.
+    ///
+    /// [`BenchOpts::per_item_latency`]: crate::runner::BenchOpts::per_item_latency
+    pub item_hist: Option<LatencyHistogram>,
+    /// Latency histograms of the workers, indexed by the worker id, if tracked with
+    /// [`BenchOpts::per_worker_latency`]. They have fewer significant digits than [`hist`](Self::hist).
+    ///
+    /// [`BenchOpts::per_worker_latency`]: crate::runner::BenchOpts::per_worker_latency
+    pub worker_hists: Option<Vec<LatencyHistogram>>,
     /// Iteration statistics.
     pub stats: IterStats,
     /// Status distribution.
     pub status_dist: HashMap<Status, u64>,
+    /// Number of iterations whose status was counted under [`Status::other`] because the status distribution
+    /// reached [`BenchOpts::status_max_kinds`].
+    ///
+    /// [`BenchOpts::status_max_kinds`]: crate::runner::BenchOpts::status_max_kinds
+    pub folded_statuses: u64,
     /// Error distribution.
     pub error_dist: HashMap<String, u64>,
+    /// Verbatim sample messages of each entry in the error distribution.
+    pub error_samples: HashMap<String, Vec<String>>,
+    /// Statistics of the per-second iteration rate.
+    pub rate_stats: RateStats,
+    /// Samples of every completed second of the run, if recorded with [`BenchOpts::record_timeseries`].
+    ///
+    /// [`BenchOpts::record_timeseries`]: crate::runner::BenchOpts::record_timeseries
+    pub timeseries: Vec<SecondSample>,
+    /// The slowest successful iterations, the slowest first, if tracked with [`BenchOpts::slowest`].
+    ///
+    /// [`BenchOpts::slowest`]: crate::runner::BenchOpts::slowest
+    pub slowest: Vec<SlowIteration>,
     /// The total elapsed time of the benchmark.
+    ///
+    /// If the statistics were reset during the run, only the time since the reset is counted.
+    pub elapsed: Duration,
+    /// The elapsed time of the benchmark at which the statistics were last reset, if any.
+    pub reset_at: Option<Duration>,
+    /// Wall-clock time the benchmark spent paused, which is not counted in `elapsed`.
+    pub paused_duration: Duration,
+    /// Number of times the benchmark was paused.
+    pub pause_count: u64,
+    /// Information about the run, attached by the CLI after the benchmark finished.
+    pub metadata: RunMetadata,
+    /// Count redirects as successful iterations in [`success_ratio`](Self::success_ratio) and
+    /// [`error_ratio`](Self::error_ratio), see [`BenchOpts::redirect_is_success`].
+    ///
+    /// [`BenchOpts::redirect_is_success`]: crate::runner::BenchOpts::redirect_is_success
+    pub redirect_is_success: bool,
+    /// Outcomes of the assertions evaluated against the report, see [`BenchBuilder::assertions`].
+    ///
+    /// [`BenchBuilder::assertions`]: crate::BenchBuilder::assertions
+    pub assertions: Vec<AssertionResult>,
+    /// Whether the benchmark stopped because it reached a steady state, see [`BenchOpts::until_stable`].
+    ///
+    /// [`BenchOpts::until_stable`]: crate::runner::BenchOpts::until_stable
+    pub converged: bool,
+    /// What was left out of the report from the start of the run, see [`BenchOpts::discard_first`].
+    ///
+    /// [`BenchOpts::discard_first`]: crate::runner::BenchOpts::discard_first
+    pub discarded: Option<Discarded>,
+    /// Signs that the load generator itself limited the benchmark, if it showed any.
+    pub saturation: Option<Saturation>,
+    /// The iterations whose latency was implausible, if there were any.
+    pub suspect: Option<SuspectDurations>,
+    /// How long the workers took to set up, attached by [`BenchBuilder::run`] once any of them finished.
+    ///
+    /// [`BenchBuilder::run`]: crate::BenchBuilder::run
+    pub setup: Option<SetupStats>,
+    /// Resource usage of the benchmark process during the run, if it could be sampled.
+    #[cfg(feature = "sysinfo")]
+    pub resource_usage: Option<crate::ResourceUsage>,
+}
+
+/// Aggregated statistics of a single second of a benchmark run.
+#[derive(Clone, Copy, Debug)]
+pub struct SecondSample {
+    /// Start of the second, relative to the start of the statistics.
     pub elapsed: Duration,
+    /// Number of successful iterations.
+    pub iters: u64,
+    /// Number of failed iterations.
+    pub errors: u64,
+    /// Number of processed bytes.
+    pub bytes: u64,
+    /// Median latency.
+    pub p50: Duration,
+    /// 99th percentile latency.
+    pub p99: Duration,
+}
+
+/// A successful iteration which was among the slowest ones of a benchmark run.
+#[derive(Clone, Debug)]
+pub struct SlowIteration {
+    /// The reported duration of the iteration.
+    pub duration: Duration,
+    /// The id of the worker which ran the iteration.
+    pub worker_id: u32,
+    /// The iteration sequence number of the worker.
+    pub worker_seq: u64,
+    /// The reported status of the iteration.
+    pub status: Status,
+    /// The reported context of the iteration, see [`IterReport::context`].
+    pub context: Option<String>,
+}
+
+/// The part of a run which was left out of its report.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Discarded {
+    /// The elapsed time which was left out.
+    pub duration: Duration,
+    /// Number of the iterations left out which returned a report, whatever their status.
+    pub iters: u64,
+    /// Number of the iterations left out which returned an error.
+    pub errors: u64,
+}
+
+/// How long the workers of a run took to create their states and set up, see [`Runner::setup`].
+///
+/// [`Runner::setup`]: crate::Runner::setup
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SetupStats {
+    /// Number of the workers which finished their setup.
+    pub workers: u32,
+    /// The shortest setup of a worker.
+    pub min: Duration,
+    /// The mean setup of the workers.
+    pub mean: Duration,
+    /// The longest setup of a worker.
+    pub max: Duration,
+}
+
+impl SetupStats {
+    /// Count the setup of one more worker.
+    pub(crate) fn record(&mut self, setup: Duration) {
+        self.merge(&SetupStats { workers: 1, min: setup, mean: setup, max: setup });
+    }
+
+    fn merge(&mut self, other: &SetupStats) {
+        if other.workers == 0 {
+            return;
+        }
+        let workers = self.workers + other.workers;
+        self.min = match self.workers {
+            0 => other.min,
+            _ => self.min.min(other.min),
+        };
+        self.max = self.max.max(other.max);
+        self.mean = (self.mean * self.workers + other.mean * other.workers) / workers;
+        self.workers = workers;
+    }
+}
+
+/// The iterations of a run whose latency is implausible, e.g. because of a bug or a non-monotonic time source in
+/// the bench suite.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SuspectDurations {
+    /// Number of the zero latencies.
+    pub zero: u64,
+    /// Number of the latencies above the ceiling.
+    pub too_long: u64,
+    /// The latency above which latencies are suspect, see [`BenchOpts::suspect_ceiling`].
+    ///
+    /// [`BenchOpts::suspect_ceiling`]: crate::runner::BenchOpts::suspect_ceiling
+    pub ceiling: Duration,
+    /// Whether the suspect latencies were left out of the latency histograms, see
+    /// [`BenchOpts::exclude_suspect`].
+    ///
+    /// [`BenchOpts::exclude_suspect`]: crate::runner::BenchOpts::exclude_suspect
+    pub excluded: bool,
+}
+
+impl SuspectDurations {
+    /// Total number of the suspect latencies.
+    pub fn total(&self) -> u64 {
+        self.zero + self.too_long
+    }
+}
+
+/// A sign that the load generator, rather than the target, was the bottleneck of a benchmark.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Saturation {
+    /// The achieved rate stayed more than 10% below the rate limit.
+    RateLag {
+        /// The achieved rate, in iterations per second.
+        achieved: f64,
+        /// The rate limit, in iterations per second.
+        target: f64,
+    },
+    /// The median latency doubled while the iterations kept succeeding with all the workers busy.
+    LatencyGrowth {
+        /// The median latency at the start of the run.
+        baseline: Duration,
+        /// The median latency when the growth was detected.
+        latest: Duration,
+    },
+}
+
+impl fmt::Display for Saturation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rate = |ips: f64| match ips {
+            ips if ips >= 1000.0 => format!("{:.1}k", ips / 1000.0).replace(".0k", "k"),
+            ips => format!("{ips:.1}"),
+        };
+        write!(f, "generator may be the bottleneck: ")?;
+        match self {
+            Self::RateLag { achieved, target } => write!(f, "achieved {} of {} ips", rate(*achieved), rate(*target)),
+            Self::LatencyGrowth { baseline, latest } => write!(
+                f,
+                "median latency grew from {:.2?} to {:.2?} while iterations kept succeeding",
+                baseline, latest
+            ),
+        }
+    }
+}
+
+/// Information about a benchmark run which is not derived from the iterations.
+#[derive(Clone, Debug, Default)]
+pub struct RunMetadata {
+    /// Wall-clock time at which the benchmark started.
+    pub started_at: Option<SystemTime>,
+    /// Wall-clock time at which the benchmark finished.
+    pub finished_at: Option<SystemTime>,
+    /// Wall-clock start and end of every pause, to exclude the gaps when correlating with server metrics.
+    pub pauses: Vec<(SystemTime, SystemTime)>,
+    /// The command line the benchmark was started with.
+    pub command: Vec<String>,
+    /// The configured number of iterations, if any.
+    pub iterations: Option<u64>,
+    /// The configured duration, if any.
+    pub duration: Option<Duration>,
+    /// The configured rate limit in iterations per second, if any.
+    pub rate: Option<u32>,
+    /// The expected interval the latencies were corrected for coordinated omission with, if any.
+    pub expected_interval: Option<Duration>,
+    /// User labels of the run.
+    pub labels: BTreeMap<String, String>,
+    /// The bound which ended the run, or `None` if it was stopped before reaching one.
+    pub finished_by: Option<Bound>,
+    /// What cancelled the run before it reached its configured end, if anything did.
+    pub cancelled_by: Option<CancelReason>,
 }
 
 impl BenchReport {
-    /// Returns the success ratio of the benchmark.
+    /// Returns true if no iteration completed, successfully or not, e.g. when the run was stopped right away.
+    pub fn is_empty(&self) -> bool {
+        self.stats.total() == 0
+    }
+
+    /// Returns true if the run reached one of its bounds or converged without being cancelled.
+    ///
+    /// A run without any bound only stops once cancelled, so it never completes.
+    pub fn completed(&self) -> bool {
+        self.metadata.cancelled_by.is_none() && (self.metadata.finished_by.is_some() || self.converged)
+    }
+
+    /// Returns the success ratio of the benchmark, out of all iterations including the failed ones, so that it
+    /// adds up to 1 with the [`error_ratio`](Self::error_ratio).
+    ///
+    /// Informational statuses never count as successes, redirects only if
+    /// [`redirect_is_success`](Self::redirect_is_success) is set.
     pub fn success_ratio(&self) -> f64 {
-        if self.stats.counter.iters == 0 {
+        if self.stats.total() == 0 {
             return 0.0;
         }
         self.stats
             .details
             .iter()
-            .filter(|(k, _)| k.kind() == StatusKind::Success)
+            .filter(|(k, _)| k.kind().is_success(self.redirect_is_success))
             .map(|(_, v)| v.iters as f64)
             .sum::<f64>()
-            / self.stats.counter.iters as f64
+            / self.stats.total() as f64
+    }
+
+    /// Returns the ratio of the iterations which returned an error or a non-success status.
+    pub fn error_ratio(&self) -> f64 {
+        self.stats.error_ratio(self.redirect_is_success)
+    }
+
+    /// Merges another report into this one, e.g. the report of another shard of the same benchmark.
+    ///
+    /// Histograms, counters and distributions are summed up, as well as the workers, and the longest elapsed
+    /// time wins. The per-second rate statistics and time series of different runs cannot be combined and are
+    /// cleared, as are the per-item and per-worker latencies unless both reports track them. The workers of the
+    /// other report are numbered after the ones of this report.
+    ///
+    /// The error samples stay within the cap of [`ErrorOpts::samples`](crate::ErrorOpts::samples) per error.
+    /// The reports do not record the cap, so it is taken as the most samples of any error in either report,
+    /// which is the cap of their aggregators as soon as one error reached it.
+    pub fn merge(&mut self, other: &BenchReport) -> anyhow::Result<()> {
+        self.hist.merge(&other.hist)?;
+        for (name, hist) in &other.phase_hists {
+            match self.phase_hists.iter_mut().find(|(n, _)| n == name) {
+                Some((_, merged)) => merged.merge(hist)?,
+                None => self.phase_hists.push((name.clone(), hist.clone())),
+            }
+        }
+        self.item_hist = match (self.item_hist.take(), &other.item_hist) {
+            (Some(mut ours), Some(theirs)) => {
+                ours.merge(theirs)?;
+                Some(ours)
+            }
+            _ => None,
+        };
+        self.worker_hists = match (self.worker_hists.take(), &other.worker_hists) {
+            (Some(mut ours), Some(theirs)) => {
+                ours.resize_with(self.concurrency as usize, LatencyHistogram::new);
+                ours.extend(theirs.iter().cloned());
+                Some(ours)
+            }
+            _ => None,
+        };
+        self.stats += &other.stats;
+        for (status, count) in &other.status_dist {
+            *self.status_dist.entry(*status).or_default() += count;
+        }
+        for (error, count) in &other.error_dist {
+            *self.error_dist.entry(error.clone()).or_default() += count;
+        }
+        let max_samples = self
+            .error_samples
+            .values()
+            .chain(other.error_samples.values())
+            .map(Vec::len)
+            .max()
+            .unwrap_or_default();
+        for (error, samples) in &other.error_samples {
+            let merged = self.error_samples.entry(error.clone()).or_default();
+            for sample in samples {
+                if merged.len() < max_samples && !merged.contains(sample) {
+                    merged.push(sample.clone());
+                }
+            }
+        }
+        let limit = self.slowest.len().max(other.slowest.len());
+        self.slowest.extend(other.slowest.iter().cloned());
+        self.slowest.sort_by_key(|s| std::cmp::Reverse(s.duration));
+        self.slowest.truncate(limit);
+        self.rate_stats = RateStats::default();
+        self.timeseries.clear();
+
+        self.concurrency += other.concurrency;
+        self.elapsed = self.elapsed.max(other.elapsed);
+        self.reset_at = None;
+        self.paused_duration = self.paused_duration.max(other.paused_duration);
+        self.pause_count += other.pause_count;
+        self.folded_statuses += other.folded_statuses;
+        // the outcomes do not hold for the merged report
+        self.assertions.clear();
+        self.converged &= other.converged;
+        self.saturation = self.saturation.or(other.saturation);
+        self.suspect = match (self.suspect, other.suspect) {
+            (Some(ours), Some(theirs)) => Some(SuspectDurations {
+                zero: ours.zero + theirs.zero,
+                too_long: ours.too_long + theirs.too_long,
+                ceiling: ours.ceiling,
+                excluded: ours.excluded && theirs.excluded,
+            }),
+            (ours, theirs) => ours.or(theirs),
+        };
+        self.discarded = match (self.discarded, other.discarded) {
+            (Some(ours), Some(theirs)) => Some(Discarded {
+                duration: ours.duration.max(theirs.duration),
+                iters: ours.iters + theirs.iters,
+                errors: ours.errors + theirs.errors,
+            }),
+            (ours, theirs) => ours.or(theirs),
+        };
+        self.setup = match (self.setup, other.setup) {
+            (Some(mut ours), Some(theirs)) => {
+                ours.merge(&theirs);
+                Some(ours)
+            }
+            (ours, theirs) => ours.or(theirs),
+        };
+        // the usage of different processes cannot be combined
+        #[cfg(feature = "sysinfo")]
+        {
+            self.resource_usage = None;
+        }
+
+        let (ours, theirs) = (&mut self.metadata, &other.metadata);
+        ours.started_at = ours.started_at.into_iter().chain(theirs.started_at).min();
+        ours.finished_at = ours.finished_at.into_iter().chain(theirs.finished_at).max();
+        ours.pauses.extend(theirs.pauses.iter().copied());
+        ours.pauses.sort();
+        // the merged run is cancelled if any of its parts was
+        ours.cancelled_by = ours.cancelled_by.or(theirs.cancelled_by);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{collector::ReportAggregator, runner::BenchOpts};
+
+    /// A report of the given results, one per worker sequence number of a single worker.
+    fn report_of(results: impl IntoIterator<Item = BenchResult<IterReport>>) -> BenchReport {
+        report_with(&BenchOpts::default(), results)
+    }
+
+    fn report_with(opts: &BenchOpts, results: impl IntoIterator<Item = BenchResult<IterReport>>) -> BenchReport {
+        let mut agg = ReportAggregator::new(opts);
+        for (i, result) in results.into_iter().enumerate() {
+            let mut info = IterInfo::new(0, 1);
+            info.worker_seq = i as u64;
+            let start = Duration::from_millis(i as u64);
+            agg.ingest(IterResult { info, start, result }, start).unwrap();
+        }
+        agg.into_report(Duration::from_secs(1), 1)
+    }
+
+    fn ok(status: Status) -> BenchResult<IterReport> {
+        Ok(IterReport::new(Duration::from_millis(1), status))
+    }
+
+    fn err() -> BenchResult<IterReport> {
+        Err(anyhow::anyhow!("connection reset").into())
+    }
+
+    #[test]
+    fn success_and_error_ratios_add_up() {
+        let results = (0..10).map(|i| match i {
+            0..=5 => ok(Status::success(200)),
+            6 => ok(Status::client_error(404)),
+            _ => err(),
+        });
+        let report = report_of(results);
+
+        assert_eq!(report.stats.total(), 10);
+        assert_eq!(report.success_ratio(), 0.6);
+        assert_eq!(report.error_ratio(), 0.4);
+    }
+
+    #[test]
+    fn mostly_failed_run() {
+        let report = report_of((0..10).map(|i| if i == 0 { ok(Status::success(200)) } else { err() }));
+        assert_eq!(report.success_ratio(), 0.1);
+        assert_eq!(report.error_ratio(), 0.9);
+
+        let report = report_of((0..4).map(|_| err()));
+        assert_eq!((report.success_ratio(), report.error_ratio()), (0.0, 1.0));
+    }
+
+    #[test]
+    fn merges_two_reports() {
+        let mut opts = BenchOpts::default();
+        opts.errors.normalize = true;
+        let timeout = |id: u32| Err(anyhow::anyhow!("request {id} timed out").into());
+        let mut report = report_with(
+            &opts,
+            (0..10).map(|i| if i < 8 { ok(Status::success(200)) } else { timeout(i) }),
+        );
+        let other = report_with(
+            &opts,
+            (0..20).map(|i| match i {
+                0..=9 => ok(Status::success(200)),
+                10..=14 => ok(Status::server_error(503)),
+                _ => timeout(i),
+            }),
+        );
+        report.merge(&other).unwrap();
+
+        assert_eq!(report.concurrency, 2);
+        assert_eq!(report.stats.total(), 30);
+        assert_eq!(report.hist.count(), 23);
+        assert_eq!(report.status_dist[&Status::success(200)], 18);
+        assert_eq!(report.status_dist[&Status::server_error(503)], 5);
+        assert_eq!(
+            report.error_dist,
+            HashMap::from([("request <n> timed out".to_string(), 7)])
+        );
+        assert_eq!(
+            report.error_samples["request <n> timed out"],
+            ["request 8 timed out", "request 9 timed out", "request 15 timed out"]
+        );
     }
 }
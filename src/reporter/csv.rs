@@ -0,0 +1,122 @@
+use std::{collections::BTreeMap, io::Write, time::SystemTime};
+
+use itertools::Itertools;
+
+use super::BenchReporter;
+use crate::report::BenchReport;
+
+/// A CSV reporter which writes a header and a single row per run.
+///
+/// The columns are, in order:
+///
+/// | Column          | Description                                     |
+/// |-----------------|-------------------------------------------------|
+/// | `timestamp`     | RFC 3339 time at which the report was written   |
+/// | `concurrency`   | Number of workers                               |
+/// | `elapsed`       | Duration of the benchmark, in seconds           |
+/// | `iters`         | Total number of iterations                      |
+/// | `iters_rate`    | Iterations per second                           |
+/// | `errors`        | Total number of iterations which failed         |
+/// | `errors_rate`   | Errors per second                               |
+/// | `items`         | Total number of items                           |
+/// | `items_rate`    | Items per second                                |
+/// | `bytes`         | Total number of bytes                           |
+/// | `bytes_rate`    | Bytes per second                                |
+/// | `success_ratio` | Ratio of successful iterations, from 0 to 1     |
+/// | `latency_min`   | Minimum latency, in seconds                     |
+/// | `latency_mean`  | Mean latency, in seconds                        |
+/// | `latency_p50`   | Median latency, in seconds                      |
+/// | `latency_p90`   | 90th percentile latency, in seconds             |
+/// | `latency_p99`   | 99th percentile latency, in seconds             |
+/// | `latency_max`   | Maximum latency, in seconds                     |
+///
+/// The latency columns are empty when no iteration succeeded. Each label is appended as an
+/// extra `label.<key>` column, in sorted key order.
+#[derive(Default)]
+pub struct CsvReporter {
+    /// Labels appended as extra columns.
+    pub labels: BTreeMap<String, String>,
+}
+
+impl CsvReporter {
+    /// Create a new CSV reporter with the given labels.
+    pub fn new(labels: BTreeMap<String, String>) -> Self {
+        Self { labels }
+    }
+}
+
+impl BenchReporter for CsvReporter {
+    fn print(&self, w: &mut dyn Write, report: &BenchReport) -> anyhow::Result<()> {
+        let elapsed = report.elapsed.as_secs_f64();
+        let counter = &report.stats.counter;
+        let hist = &report.hist;
+
+        let mut header = [
+            "timestamp",
+            "concurrency",
+            "elapsed",
+            "iters",
+            "iters_rate",
+            "errors",
+            "errors_rate",
+            "items",
+            "items_rate",
+            "bytes",
+            "bytes_rate",
+            "success_ratio",
+            "latency_min",
+            "latency_mean",
+            "latency_p50",
+            "latency_p90",
+            "latency_p99",
+            "latency_max",
+        ]
+        .map(String::from)
+        .to_vec();
+        header.extend(self.labels.keys().map(|k| format!("label.{k}")));
+
+        let mut row = vec![
+            humantime::format_rfc3339_seconds(SystemTime::now()).to_string(),
+            report.concurrency.to_string(),
+            elapsed.to_string(),
+            counter.iters.to_string(),
+            (counter.iters as f64 / elapsed).to_string(),
+            counter.errors.to_string(),
+            (counter.errors as f64 / elapsed).to_string(),
+            counter.items.to_string(),
+            (counter.items as f64 / elapsed).to_string(),
+            counter.bytes.to_string(),
+            (counter.bytes as f64 / elapsed).to_string(),
+            report.success_ratio().to_string(),
+        ];
+        let latencies = [
+            hist.min(),
+            hist.mean(),
+            hist.median(),
+            hist.value_at_quantile(0.9),
+            hist.value_at_quantile(0.99),
+            hist.max(),
+        ];
+        row.extend(latencies.map(|d| match hist.is_empty() {
+            true => String::new(),
+            false => d.as_secs_f64().to_string(),
+        }));
+        row.extend(self.labels.values().cloned());
+
+        writeln!(w, "{}", header.iter().map(|s| escape(s)).join(","))?;
+        writeln!(w, "{}", row.iter().map(|s| escape(s)).join(","))?;
+        Ok(())
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "csv"
+    }
+}
+
+/// Quote a field if it contains a separator, a quote or a line break.
+fn escape(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
@@ -0,0 +1,266 @@
+use std::{cmp::Reverse, fmt::Write as _, io::Write};
+
+use itertools::Itertools;
+
+use super::BenchReporter;
+use crate::{
+    duration::{DurationExt, FormattedDuration},
+    histogram::PERCENTAGES,
+    report::BenchReport,
+    status::StatusKind,
+    util::{ByteUnit, IntoAdjustedByte, TryIntoAdjustedByte},
+};
+
+const TEMPLATE: &str = include_str!("report.html");
+
+/// Width and height of the latency chart, in pixels.
+const CHART_WIDTH: usize = 900;
+const CHART_HEIGHT: usize = 240;
+
+/// An HTML reporter which writes a single self-contained page.
+///
+/// The page has no external resources, so it renders offline.
+#[derive(Default)]
+pub struct HtmlReporter {
+    /// The unit convention used to display byte sizes.
+    pub byte_unit: ByteUnit,
+}
+
+impl HtmlReporter {
+    /// Create a new HTML reporter with the given byte unit convention.
+    pub fn new(byte_unit: ByteUnit) -> Self {
+        Self { byte_unit }
+    }
+}
+
+impl BenchReporter for HtmlReporter {
+    fn print(&self, w: &mut dyn Write, report: &BenchReport) -> anyhow::Result<()> {
+        let html = TEMPLATE
+            .replace("{{notes}}", &notes(report))
+            .replace("{{cards}}", &cards(report, self.byte_unit)?)
+            .replace("{{latency_chart}}", &latency_chart(report))
+            .replace("{{latency_table}}", &latency_table(report))
+            .replace("{{status_table}}", &status_table(report))
+            .replace("{{errors}}", &error_table(report));
+        w.write_all(html.as_bytes())?;
+        Ok(())
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "html"
+    }
+}
+
+fn notes(report: &BenchReport) -> String {
+    let mut out = String::new();
+    if report.pause_count > 0 {
+        let _ = writeln!(
+            out,
+            "<p>Paused {:.2}s in total ({}×).</p>",
+            report.paused_duration.as_secs_f64(),
+            report.pause_count
+        );
+    }
+    if let Some(reset_at) = report.reset_at {
+        let _ = writeln!(
+            out,
+            "<p>Statistics were reset at {:.2}s of the run.</p>",
+            reset_at.as_secs_f64()
+        );
+    }
+    out
+}
+
+fn cards(report: &BenchReport, byte_unit: ByteUnit) -> anyhow::Result<String> {
+    let elapsed = report.elapsed.as_secs_f64();
+    let counter = &report.stats.counter;
+    let success = 100.0 * report.success_ratio();
+    let class = if success >= 100.0 {
+        "good"
+    } else if success >= 99.0 {
+        "warn"
+    } else {
+        "bad"
+    };
+
+    let cards = [
+        (
+            "Duration",
+            format!("{elapsed:.2}s"),
+            format!("concurrency {}", report.concurrency),
+            "",
+        ),
+        (
+            "Success",
+            format!("{success:.2}%"),
+            format!("{} iters", counter.iters),
+            class,
+        ),
+        (
+            "Iterations",
+            format!("{:.2}/s", counter.iters as f64 / elapsed),
+            format!("{} in total", counter.iters),
+            "",
+        ),
+        (
+            "Errors",
+            format!("{:.2}/s", counter.errors as f64 / elapsed),
+            format!("{} in total", counter.errors),
+            if counter.errors > 0 { "bad" } else { "" },
+        ),
+        (
+            "Items",
+            format!("{:.2}/s", counter.items as f64 / elapsed),
+            format!("{} in total", counter.items),
+            "",
+        ),
+        (
+            "Bytes",
+            format!("{:.2}/s", (counter.bytes as f64 / elapsed).adjusted(byte_unit)?),
+            format!("{:.2} in total", counter.bytes.adjusted(byte_unit)),
+            "",
+        ),
+    ];
+    Ok(cards
+        .iter()
+        .map(|(label, value, sub, class)| {
+            format!(
+                r#"<div class="card"><div class="label">{label}</div><div class="value {class}">{}</div><div class="sub">{}</div></div>"#,
+                escape(value),
+                escape(sub)
+            )
+        })
+        .join("\n"))
+}
+
+fn latency_chart(report: &BenchReport) -> String {
+    let hist = &report.hist;
+    let buckets = hist.quantiles().collect_vec();
+    if buckets.is_empty() {
+        return empty();
+    }
+
+    let u = hist.median().appropriate_unit();
+    let max = buckets.iter().map(|(_, n)| *n).max().unwrap_or(1) as f64;
+    let (label_height, count_height) = (16, 14);
+    let plot = CHART_HEIGHT - label_height - count_height;
+    let slot = CHART_WIDTH as f64 / buckets.len() as f64;
+
+    let mut svg = format!(
+        r#"<svg viewBox="0 0 {CHART_WIDTH} {CHART_HEIGHT}" width="100%" role="img" aria-label="Latency distribution">"#
+    );
+    for (i, (latency, n)) in buckets.iter().enumerate() {
+        let h = (*n as f64 / max * plot as f64).max(1.0);
+        let x = i as f64 * slot;
+        let y = (count_height + plot) as f64 - h;
+        let center = x + slot / 2.0;
+        let latency = format!("{:.2}", FormattedDuration::from(*latency, u));
+        let _ = write!(
+            svg,
+            r#"<rect class="bar" x="{:.1}" y="{y:.1}" width="{:.1}" height="{h:.1}"><title>{n} iters ≤ {latency}</title></rect>"#,
+            x + slot * 0.1,
+            slot * 0.8,
+        );
+        let _ = write!(
+            svg,
+            r#"<text x="{center:.1}" y="{:.1}" text-anchor="middle">{n}</text>"#,
+            y - 3.0
+        );
+        let _ = write!(
+            svg,
+            r#"<text x="{center:.1}" y="{}" text-anchor="middle">{latency}</text>"#,
+            CHART_HEIGHT - 3
+        );
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+fn latency_table(report: &BenchReport) -> String {
+    let hist = &report.hist;
+    if hist.is_empty() {
+        return empty();
+    }
+
+    let u = hist.median().appropriate_unit();
+    let fmt = |d| format!("{:.2}", FormattedDuration::from(d, u));
+    let stats = [
+        ("Avg", hist.mean()),
+        ("Min", hist.min()),
+        ("Med", hist.median()),
+        ("Max", hist.max()),
+        ("Stdev", hist.stdev()),
+    ];
+    let rows = stats
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), fmt(v)))
+        .chain(
+            hist.percentiles(PERCENTAGES)
+                .filter(|&(p, _)| hist.is_reliable_percentile(p))
+                .map(|(p, v)| (format!("{p:.2}%"), fmt(v))),
+        )
+        .map(|(k, v)| format!("<tr><th>{k}</th><td>{v}</td></tr>"))
+        .join("\n");
+    match super::omitted_percentiles(hist, PERCENTAGES) {
+        Some(note) => format!("<table>\n{rows}\n</table>\n<p class=\"empty\">{note}</p>"),
+        None => format!("<table>\n{rows}\n</table>"),
+    }
+}
+
+fn status_table(report: &BenchReport) -> String {
+    if report.status_dist.is_empty() {
+        return empty();
+    }
+
+    let rows = report
+        .status_dist
+        .iter()
+        .sorted_unstable_by_key(|(_, &cnt)| Reverse(cnt))
+        .map(|(status, cnt)| {
+            let class = match status.kind() {
+                StatusKind::Success => "good",
+                StatusKind::Informational | StatusKind::Redirect => "info",
+                StatusKind::ClientError => "warn",
+                StatusKind::ServerError | StatusKind::Error => "bad",
+            };
+            format!(
+                r#"<tr><td class="text {class}">{}</td><td>{cnt}</td></tr>"#,
+                escape(&status.to_string())
+            )
+        })
+        .join("\n");
+    format!("<table>\n<tr><th>Status</th><th>Iters</th></tr>\n{rows}\n</table>")
+}
+
+fn error_table(report: &BenchReport) -> String {
+    if report.error_dist.is_empty() {
+        return String::new();
+    }
+
+    let rows = report
+        .error_dist
+        .iter()
+        .sorted_unstable_by_key(|(_, &cnt)| Reverse(cnt))
+        .map(|(err, cnt)| format!(r#"<tr><td class="text bad">{}</td><td>{cnt}</td></tr>"#, escape(err)))
+        .join("\n");
+    format!("<h2>Error distribution</h2>\n<table>\n<tr><th>Error</th><th>Iters</th></tr>\n{rows}\n</table>")
+}
+
+fn empty() -> String {
+    r#"<p class="empty">No latency data.</p>"#.to_string()
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
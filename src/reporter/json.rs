@@ -1,85 +1,563 @@
-use crate::{histogram::PERCENTAGES, report::BenchReport};
+use crate::{
+    histogram::{LatencyHistogram, PERCENTAGES},
+    report::{BenchReport, Discarded, RunMetadata, SetupStats, SlowIteration, SuspectDurations},
+    runner::{Bound, CancelReason},
+    stats::{Counter, IterStats, RateStats},
+    status::Status,
+    util::ByteUnit,
+};
 
 use super::BenchReporter;
 
-use serde::Serialize;
-use std::{collections::BTreeMap, io::Write};
+use anyhow::Context;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+    io::Write,
+    time::{Duration, SystemTime},
+};
+
+/// Version of the JSON report layout, bumped on incompatible changes.
+const SCHEMA_VERSION: u32 = 1;
 
 /// A JSON reporter for benchmark results.
-pub struct JsonReporter;
+#[derive(Default)]
+pub struct JsonReporter {
+    /// The unit convention stated in the summary.
+    ///
+    /// Byte sizes are always reported as raw numbers of bytes.
+    pub byte_unit: ByteUnit,
+
+    /// Embed the latency histogram as base64 under `latency.hdr`.
+    ///
+    /// See [`LatencyHistogram::from_base64`](crate::LatencyHistogram::from_base64) for loading it.
+    pub embed_hdr: bool,
+
+    /// Print the report on a single line instead of pretty-printing it.
+    pub compact: bool,
+}
+
+impl JsonReporter {
+    /// Create a new JSON reporter with the given byte unit convention.
+    pub fn new(byte_unit: ByteUnit) -> Self {
+        Self { byte_unit, embed_hdr: false, compact: false }
+    }
+
+    /// Load a benchmark report from the output of a JSON reporter.
+    ///
+    /// The latency histogram must have been embedded with [`embed_hdr`](Self::embed_hdr). The report only
+    /// knows the number of iterations of each status, so their items and bytes are accounted to the totals.
+    /// The outcomes of assertions are not loaded, as they are evaluated again on the loaded report.
+    pub fn parse(json: &str) -> anyhow::Result<BenchReport> {
+        let report: ReportInput = serde_json::from_str(json)?;
+        let summary = report.summary;
+
+        let hist = match report.latency {
+            Some(latency) => {
+                let hdr = latency
+                    .hdr
+                    .context("the report has no embedded latency histogram, see --embed-hdr")?;
+                LatencyHistogram::from_base64(&hdr)?
+            }
+            None => LatencyHistogram::new(),
+        };
+        let item_hist = match report.item_latency {
+            Some(latency) => {
+                let hdr = latency
+                    .hdr
+                    .context("the report has no embedded per-item latency histogram, see --embed-hdr")?;
+                Some(LatencyHistogram::from_base64(&hdr)?)
+            }
+            None => None,
+        };
+
+        // the histograms of the workers are only loaded if all of them were embedded
+        let worker_hists = match report.workers.iter().all(|w| w.hdr.is_some()) {
+            true if !report.workers.is_empty() => {
+                let mut hists = Vec::new();
+                for w in &report.workers {
+                    anyhow::ensure!(
+                        w.worker_id < summary.concurrency,
+                        "invalid worker id {} of a report of {} workers",
+                        w.worker_id,
+                        summary.concurrency
+                    );
+                    let id = w.worker_id as usize;
+                    if hists.len() <= id {
+                        hists.resize_with(id + 1, LatencyHistogram::new);
+                    }
+                    hists[id] = LatencyHistogram::from_base64(w.hdr.as_deref().unwrap_or_default())?;
+                }
+                Some(hists)
+            }
+            _ => None,
+        };
+
+        let mut details = HashMap::new();
+        for (status, iters) in &report.status {
+            let status: Status = status.parse()?;
+            details.insert(status, Counter { iters: *iters, ..Default::default() });
+        }
+        let iters = summary.iters.total;
+        let stats = IterStats {
+            counter: Counter {
+                iters,
+                items: summary.items.total,
+                bytes: summary.bytes.total,
+                bytes_in: summary.bytes.bytes_in.map_or(0, |b| b.total),
+                bytes_out: summary.bytes.bytes_out.map_or(0, |b| b.total),
+                duration: secs(hist.mean().as_secs_f64() * iters as f64, "total latency")?,
+                // older reports only have the error distribution
+                errors: summary.errors.map_or_else(|| report.errors.values().sum(), |e| e.total),
+            },
+            details,
+        };
+
+        let timestamp = |t: Option<String>| t.map(|t| humantime::parse_rfc3339_weak(&t)).transpose();
+        let metadata = RunMetadata {
+            started_at: timestamp(report.metadata.started_at)?,
+            finished_at: timestamp(report.metadata.finished_at)?,
+            pauses: report
+                .metadata
+                .pauses
+                .into_iter()
+                .map(|p| {
+                    Ok((
+                        humantime::parse_rfc3339_weak(&p.start)?,
+                        humantime::parse_rfc3339_weak(&p.end)?,
+                    ))
+                })
+                .collect::<anyhow::Result<_>>()?,
+            command: report.metadata.command,
+            iterations: report.metadata.config.iterations,
+            duration: report
+                .metadata
+                .config
+                .duration
+                .map(|d| secs(d, "duration"))
+                .transpose()?,
+            rate: report.metadata.config.rate,
+            expected_interval: report
+                .metadata
+                .config
+                .expected_interval
+                .map(|i| secs(i, "expected interval"))
+                .transpose()?,
+            labels: report.metadata.labels,
+            finished_by: report.metadata.finished_by,
+            cancelled_by: report.metadata.cancelled_by,
+        };
+
+        Ok(BenchReport {
+            concurrency: summary.concurrency,
+            hist,
+            phase_hists: Vec::new(),
+            item_hist,
+            worker_hists,
+            status_dist: report
+                .status
+                .iter()
+                .map(|(k, &v)| Ok((k.parse()?, v)))
+                .collect::<anyhow::Result<_>>()?,
+            stats,
+            error_dist: report.errors.into_iter().collect(),
+            error_samples: report.error_samples.into_iter().collect(),
+            rate_stats: RateStats::default(),
+            timeseries: Vec::new(),
+            slowest: report
+                .slowest
+                .into_iter()
+                .map(|s| {
+                    Ok(SlowIteration {
+                        duration: secs(s.latency, "latency")?,
+                        worker_id: s.worker_id,
+                        worker_seq: s.worker_seq,
+                        status: s.status.parse()?,
+                        context: s.context,
+                    })
+                })
+                .collect::<anyhow::Result<_>>()?,
+            elapsed: secs(summary.total_time, "total time")?,
+            reset_at: summary.reset_at.map(|t| secs(t, "reset time")).transpose()?,
+            paused_duration: secs(summary.paused_duration, "paused duration")?,
+            pause_count: summary.pause_count,
+            folded_statuses: summary.folded_statuses,
+            metadata,
+            redirect_is_success: false,
+            assertions: Vec::new(),
+            converged: summary.stopped.as_deref() == Some("converged"),
+            discarded: summary
+                .discarded
+                .map(|d| -> anyhow::Result<_> {
+                    Ok(Discarded {
+                        duration: secs(d.duration, "discarded duration")?,
+                        iters: d.iters,
+                        errors: d.errors,
+                    })
+                })
+                .transpose()?,
+            saturation: None,
+            suspect: summary
+                .suspect_durations
+                .map(|s| -> anyhow::Result<_> {
+                    Ok(SuspectDurations {
+                        zero: s.zero,
+                        too_long: s.too_long,
+                        ceiling: secs(s.ceiling, "suspect ceiling")?,
+                        excluded: s.excluded,
+                    })
+                })
+                .transpose()?,
+            setup: summary
+                .setup
+                .map(|s| -> anyhow::Result<_> {
+                    Ok(SetupStats {
+                        workers: s.workers,
+                        min: secs(s.min, "minimum setup time")?,
+                        mean: secs(s.mean, "mean setup time")?,
+                        max: secs(s.max, "maximum setup time")?,
+                    })
+                })
+                .transpose()?,
+            #[cfg(feature = "sysinfo")]
+            resource_usage: None,
+        })
+    }
+}
+
+/// Convert seconds read from a report into a duration, failing on a negative or too large value.
+fn secs(secs: f64, what: &str) -> anyhow::Result<Duration> {
+    Duration::try_from_secs_f64(secs).with_context(|| format!("invalid {what} of {secs} seconds"))
+}
 
 impl BenchReporter for JsonReporter {
     fn print(&self, w: &mut dyn Write, report: &BenchReport) -> anyhow::Result<()> {
+        self.write(w, &self.report(report)?)
+    }
+
+    fn print_partial(&self, w: &mut dyn Write, report: &BenchReport) -> anyhow::Result<()> {
+        let report = Report { partial: true, ..self.report(report)? };
+        self.write(w, &report)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "json"
+    }
+}
+
+impl JsonReporter {
+    /// Print the reports of the steps of a sweep as a JSON array, in the order of the steps.
+    pub fn print_sweep(&self, w: &mut dyn Write, reports: &[BenchReport]) -> anyhow::Result<()> {
+        let reports = reports
+            .iter()
+            .map(|r| self.report(r))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        self.write(w, &reports)
+    }
+
+    fn write(&self, w: &mut dyn Write, value: &impl Serialize) -> anyhow::Result<()> {
+        match self.compact {
+            true => serde_json::to_writer(&mut *w, value)?,
+            false => serde_json::to_writer_pretty(&mut *w, value)?,
+        }
+        writeln!(w)?;
+        Ok(())
+    }
+
+    fn report(&self, report: &BenchReport) -> anyhow::Result<Report> {
         let elapsed = report.elapsed.as_secs_f64();
         let counter = &report.stats.counter;
+        // a run stopped right away has nothing to divide
+        let rate = |n: u64| if elapsed > 0.0 { n as f64 / elapsed } else { 0.0 };
         let summary = Summary {
             success_ratio: report.success_ratio(),
+            error_ratio: report.error_ratio(),
             total_time: elapsed,
             concurrency: report.concurrency,
+            reset_at: report.reset_at.map(|t| t.as_secs_f64()),
+            stopped: report.converged.then_some("converged"),
+            discarded: report.discarded.map(|d| DiscardedSummary {
+                duration: d.duration.as_secs_f64(),
+                iters: d.iters,
+                errors: d.errors,
+            }),
+            suspect_durations: report.suspect.map(|s| SuspectSummary {
+                zero: s.zero,
+                too_long: s.too_long,
+                ceiling: s.ceiling.as_secs_f64(),
+                excluded: s.excluded,
+            }),
+            setup: report.setup.map(|s| SetupSummary {
+                workers: s.workers,
+                min: s.min.as_secs_f64(),
+                mean: s.mean.as_secs_f64(),
+                max: s.max.as_secs_f64(),
+            }),
+            saturation: report.saturation.map(|s| s.to_string()),
+            warning: report.is_empty().then_some("no iterations completed"),
+            paused_duration: report.paused_duration.as_secs_f64(),
+            pause_count: report.pause_count,
+            folded_statuses: report.folded_statuses,
+            byte_unit: self.byte_unit,
 
             iters: ItersSummary {
                 total: counter.iters,
-                rate: counter.iters as f64 / elapsed,
+                rate: rate(counter.iters),
+                rate_stdev: report.rate_stats.stdev(),
+                rate_cv: report.rate_stats.cv(),
                 bytes_per_iter: counter.bytes.checked_div(counter.iters),
             },
 
+            errors: ErrorsSummary { total: counter.errors, rate: rate(counter.errors) },
+
             items: ItemsSummary {
                 total: counter.items,
-                rate: counter.items as f64 / elapsed,
+                rate: rate(counter.items),
                 items_per_iter: counter.items as f64 / counter.iters as f64,
                 bytes_per_item: counter.bytes.checked_div(counter.items),
             },
 
-            bytes: BytesSummary { total: counter.bytes, rate: counter.bytes as f64 / elapsed },
+            bytes: BytesSummary {
+                total: counter.bytes,
+                rate: rate(counter.bytes),
+                directions: counter.has_directions().then(|| BytesDirections {
+                    bytes_in: BytesTotal { total: counter.bytes_in, rate: rate(counter.bytes_in) },
+                    bytes_out: BytesTotal { total: counter.bytes_out, rate: rate(counter.bytes_out) },
+                }),
+            },
         };
 
         let latency = if report.hist.is_empty() {
             None
         } else {
             Latency {
-                stats: LatencyStats {
-                    min: report.hist.min().as_secs_f64(),
-                    max: report.hist.max().as_secs_f64(),
-                    mean: report.hist.mean().as_secs_f64(),
-                    median: report.hist.median().as_secs_f64(),
-                    stdev: report.hist.stdev().as_secs_f64(),
-                },
-                percentiles: report
-                    .hist
-                    .percentiles(PERCENTAGES)
-                    .map(|(p, v)| (format!("p{p}"), v.as_secs_f64()))
-                    .collect(),
+                stats: LatencyStats::new(&report.hist),
+                percentiles: percentiles(&report.hist),
                 histogram: report
                     .hist
                     .quantiles()
                     .map(|(k, v)| (k.as_secs_f64().to_string(), v))
                     .collect(),
+                reliable_quantile: report.hist.reliable_quantile_limit(),
+                saturated: report.hist.saturated_count(),
+                zero_count: report.hist.zero_count(),
+                hdr: match self.embed_hdr {
+                    true => Some(report.hist.to_base64()?),
+                    false => None,
+                },
             }
             .into()
         };
 
-        serde_json::to_writer_pretty(
-            &mut *w,
-            &Report {
-                summary,
-                latency,
-                status: report.status_dist.iter().map(|(k, &v)| (k.to_string(), v)).collect(),
-                errors: report.error_dist.iter().map(|(k, &v)| (k.clone(), v)).collect(),
+        let report = Report {
+            partial: false,
+            metadata: Metadata::new(report),
+            summary,
+            latency,
+            status: report.status_dist.iter().map(|(k, &v)| (k.to_string(), v)).collect(),
+            phases: report
+                .phase_hists
+                .iter()
+                .map(|(name, hist)| PhaseLatency {
+                    name: name.to_string(),
+                    stats: LatencyStats::new(hist),
+                    percentiles: percentiles(hist),
+                })
+                .collect(),
+            item_latency: match &report.item_hist {
+                Some(hist) => Some(ItemLatency {
+                    stats: LatencyStats::new(hist),
+                    percentiles: percentiles(hist),
+                    hdr: match self.embed_hdr {
+                        true => Some(hist.to_base64()?),
+                        false => None,
+                    },
+                }),
+                None => None,
             },
-        )?;
+            workers: report
+                .worker_hists
+                .iter()
+                .flatten()
+                .enumerate()
+                .filter(|(_, hist)| !hist.is_empty())
+                .sorted_by_key(|(_, hist)| Reverse(hist.percentile(99.0)))
+                .map(|(id, hist)| {
+                    Ok(WorkerLatency {
+                        worker_id: id as u32,
+                        iters: hist.count(),
+                        mean: hist.mean().as_secs_f64(),
+                        p99: hist.percentile(99.0).as_secs_f64(),
+                        hdr: match self.embed_hdr {
+                            true => Some(hist.to_base64()?),
+                            false => None,
+                        },
+                    })
+                })
+                .collect::<anyhow::Result<_>>()?,
+            errors: report.error_dist.iter().map(|(k, &v)| (k.clone(), v)).collect(),
+            timeseries: report
+                .timeseries
+                .iter()
+                .map(|s| Sample {
+                    elapsed: s.elapsed.as_secs_f64(),
+                    iters: s.iters,
+                    errors: s.errors,
+                    bytes: s.bytes,
+                    p50: s.p50.as_secs_f64(),
+                    p99: s.p99.as_secs_f64(),
+                })
+                .collect(),
+            slowest: report
+                .slowest
+                .iter()
+                .map(|s| Slow {
+                    latency: s.duration.as_secs_f64(),
+                    worker_id: s.worker_id,
+                    worker_seq: s.worker_seq,
+                    status: s.status.to_string(),
+                    context: s.context.clone(),
+                })
+                .collect(),
+            error_samples: report
+                .error_samples
+                .iter()
+                .filter(|(k, v)| v.iter().any(|s| s != *k))
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            assertions: report
+                .assertions
+                .iter()
+                .map(|a| AssertionOutcome {
+                    assertion: a.assertion.to_string(),
+                    actual: a.actual,
+                    passed: a.passed,
+                })
+                .collect(),
+            #[cfg(feature = "sysinfo")]
+            resources: report.resource_usage.map(|u| Resources {
+                cores: u.cores,
+                cpu_mean: u.cpu_mean,
+                cpu_max: u.cpu_max,
+                rss_mean: u.rss_mean,
+                rss_max: u.rss_max,
+                saturated: u.saturated(),
+            }),
+        };
+        Ok(report)
+    }
+}
 
-        writeln!(w)?;
-        Ok(())
+#[derive(Serialize)]
+struct Metadata {
+    schema_version: u32,
+    rlt_version: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    started_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    finished_at: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pauses: Vec<Pause>,
+    command: Vec<String>,
+    config: Config,
+    labels: BTreeMap<String, String>,
+    /// The bound which ended the run, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    finished_by: Option<Bound>,
+    /// Whether the run reached one of its bounds or converged.
+    completed: bool,
+    /// What cancelled the run before its configured end, if anything did.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cancelled_by: Option<CancelReason>,
+}
+
+impl Metadata {
+    fn new(report: &BenchReport) -> Self {
+        let metadata = &report.metadata;
+        let timestamp = |t: Option<SystemTime>| t.map(|t| humantime::format_rfc3339_millis(t).to_string());
+        Self {
+            schema_version: SCHEMA_VERSION,
+            rlt_version: env!("CARGO_PKG_VERSION"),
+            started_at: timestamp(metadata.started_at),
+            finished_at: timestamp(metadata.finished_at),
+            pauses: metadata
+                .pauses
+                .iter()
+                .map(|&(start, end)| Pause {
+                    start: humantime::format_rfc3339_millis(start).to_string(),
+                    end: humantime::format_rfc3339_millis(end).to_string(),
+                })
+                .collect(),
+            command: metadata.command.clone(),
+            config: Config {
+                concurrency: report.concurrency,
+                iterations: metadata.iterations,
+                duration: metadata.duration.map(|d| d.as_secs_f64()),
+                rate: metadata.rate,
+                expected_interval: metadata.expected_interval.map(|d| d.as_secs_f64()),
+            },
+            labels: metadata.labels.clone(),
+            finished_by: metadata.finished_by,
+            completed: report.completed(),
+            cancelled_by: metadata.cancelled_by,
+        }
     }
 }
 
+/// The wall-clock interval of a pause.
+#[derive(Serialize, Deserialize)]
+struct Pause {
+    start: String,
+    end: String,
+}
+
+#[derive(Serialize)]
+struct Config {
+    concurrency: u32,
+    iterations: Option<u64>,
+    duration: Option<f64>,
+    rate: Option<u32>,
+    /// The expected interval of the coordinated omission correction, in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected_interval: Option<f64>,
+}
+
 #[derive(Serialize)]
 struct Summary {
     success_ratio: f64,
+    error_ratio: f64,
     total_time: f64,
     concurrency: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reset_at: Option<f64>,
+    /// Why the benchmark stopped before its configured end, if it did.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stopped: Option<&'static str>,
+    /// What was left out from the start of the run, if anything.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    discarded: Option<DiscardedSummary>,
+    /// The iterations with an implausible latency, if there were any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suspect_durations: Option<SuspectSummary>,
+    /// How long the workers took to set up, if any of them finished.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    setup: Option<SetupSummary>,
+    /// Why the load generator may have been the bottleneck, if it showed any signs of it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    saturation: Option<String>,
+    /// Why the report holds no statistics, if it does not.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    warning: Option<&'static str>,
+    paused_duration: f64,
+    pause_count: u64,
+    #[serde(skip_serializing_if = "is_zero")]
+    folded_statuses: u64,
+    byte_unit: ByteUnit,
 
     iters: ItersSummary,
+    errors: ErrorsSummary,
     items: ItemsSummary,
     bytes: BytesSummary,
 }
@@ -89,9 +567,20 @@ struct ItersSummary {
     total: u64,
     rate: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
+    rate_stdev: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate_cv: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     bytes_per_iter: Option<u64>,
 }
 
+/// The iterations which returned an error, which are not part of the total iterations.
+#[derive(Serialize)]
+struct ErrorsSummary {
+    total: u64,
+    rate: f64,
+}
+
 #[derive(Serialize)]
 struct ItemsSummary {
     total: u64,
@@ -106,6 +595,23 @@ struct ItemsSummary {
 struct BytesSummary {
     total: u64,
     rate: f64,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    directions: Option<BytesDirections>,
+}
+
+/// The received and sent bytes, reported when the suite distinguishes them.
+#[derive(Serialize)]
+struct BytesDirections {
+    #[serde(rename = "in")]
+    bytes_in: BytesTotal,
+    #[serde(rename = "out")]
+    bytes_out: BytesTotal,
+}
+
+#[derive(Serialize)]
+struct BytesTotal {
+    total: u64,
+    rate: f64,
 }
 
 #[derive(Serialize)]
@@ -117,22 +623,375 @@ pub struct LatencyStats {
     stdev: f64,
 }
 
+impl LatencyStats {
+    fn new(hist: &LatencyHistogram) -> Self {
+        Self {
+            min: hist.min().as_secs_f64(),
+            max: hist.max().as_secs_f64(),
+            mean: hist.mean().as_secs_f64(),
+            median: hist.median().as_secs_f64(),
+            stdev: hist.stdev().as_secs_f64(),
+        }
+    }
+}
+
+fn percentiles(hist: &LatencyHistogram) -> BTreeMap<String, f64> {
+    hist.percentiles(PERCENTAGES)
+        .map(|(p, v)| (format!("p{p}"), v.as_secs_f64()))
+        .collect()
+}
+
+/// The latency per item, with the histogram embedded like the one of the iterations.
+#[derive(Serialize)]
+struct ItemLatency {
+    stats: LatencyStats,
+    percentiles: BTreeMap<String, f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hdr: Option<String>,
+}
+
+/// The latency of a worker, in seconds, with its histogram embedded like the one of the iterations.
+#[derive(Serialize)]
+struct WorkerLatency {
+    worker_id: u32,
+    iters: u64,
+    mean: f64,
+    p99: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hdr: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct WorkerLatencyInput {
+    worker_id: u32,
+    hdr: Option<String>,
+}
+
+#[derive(Serialize)]
+struct PhaseLatency {
+    name: String,
+    stats: LatencyStats,
+    percentiles: BTreeMap<String, f64>,
+}
+
 #[derive(Serialize)]
 pub struct Latency {
     stats: LatencyStats,
     percentiles: BTreeMap<String, f64>,
     histogram: BTreeMap<String, u64>,
+    reliable_quantile: f64,
+    saturated: u64,
+    zero_count: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hdr: Option<String>,
 }
 
 #[derive(Serialize)]
 struct Report {
+    /// Whether the benchmark was still running, see [`BenchReporter::print_partial`].
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    partial: bool,
+    metadata: Metadata,
     summary: Summary,
     #[serde(skip_serializing_if = "Option::is_none")]
     latency: Option<Latency>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    phases: Vec<PhaseLatency>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    item_latency: Option<ItemLatency>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    workers: Vec<WorkerLatency>,
     status: BTreeMap<String, u64>,
     errors: BTreeMap<String, u64>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    error_samples: BTreeMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    timeseries: Vec<Sample>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    slowest: Vec<Slow>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    assertions: Vec<AssertionOutcome>,
+    #[cfg(feature = "sysinfo")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resources: Option<Resources>,
+}
+
+/// The resource usage of the benchmark process, with the CPU usage in percent of a single core and the sizes in
+/// bytes.
+#[cfg(feature = "sysinfo")]
+#[derive(Serialize)]
+struct Resources {
+    cores: usize,
+    cpu_mean: f64,
+    cpu_max: f64,
+    rss_mean: u64,
+    rss_max: u64,
+    saturated: bool,
+}
+
+/// The outcome of an assertion, with the actual latencies in seconds and ratios as fractions.
+#[derive(Serialize)]
+struct AssertionOutcome {
+    assertion: String,
+    actual: f64,
+    passed: bool,
+}
+
+/// A per-second sample, with the times in seconds.
+#[derive(Serialize)]
+struct Sample {
+    elapsed: f64,
+    iters: u64,
+    errors: u64,
+    bytes: u64,
+    p50: f64,
+    p99: f64,
+}
+
+/// One of the slowest iterations, with the latency in seconds.
+#[derive(Serialize, Deserialize)]
+struct Slow {
+    latency: f64,
+    worker_id: u32,
+    worker_seq: u64,
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<String>,
 }
 
 fn not_normal_f64(v: &f64) -> bool {
     !v.is_normal()
 }
+
+fn is_zero(v: &u64) -> bool {
+    *v == 0
+}
+
+/// The parts of a JSON report which [`JsonReporter::parse`] loads a report from.
+#[derive(Deserialize)]
+struct ReportInput {
+    metadata: MetadataInput,
+    summary: SummaryInput,
+    latency: Option<LatencyInput>,
+    #[serde(default)]
+    item_latency: Option<LatencyInput>,
+    #[serde(default)]
+    workers: Vec<WorkerLatencyInput>,
+    status: BTreeMap<String, u64>,
+    errors: BTreeMap<String, u64>,
+    #[serde(default)]
+    error_samples: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    slowest: Vec<Slow>,
+}
+
+#[derive(Deserialize)]
+struct MetadataInput {
+    started_at: Option<String>,
+    finished_at: Option<String>,
+    #[serde(default)]
+    pauses: Vec<Pause>,
+    command: Vec<String>,
+    config: ConfigInput,
+    labels: BTreeMap<String, String>,
+    #[serde(default)]
+    finished_by: Option<Bound>,
+    #[serde(default)]
+    cancelled_by: Option<CancelReason>,
+}
+
+#[derive(Deserialize)]
+struct ConfigInput {
+    iterations: Option<u64>,
+    duration: Option<f64>,
+    rate: Option<u32>,
+    #[serde(default)]
+    expected_interval: Option<f64>,
+}
+
+/// The iterations with an implausible latency, with the ceiling in seconds.
+#[derive(Serialize, Deserialize)]
+struct SuspectSummary {
+    zero: u64,
+    too_long: u64,
+    ceiling: f64,
+    excluded: bool,
+}
+
+/// How long the workers took to set up, in seconds.
+#[derive(Serialize, Deserialize)]
+struct SetupSummary {
+    workers: u32,
+    min: f64,
+    mean: f64,
+    max: f64,
+}
+
+/// The part of the run which was left out of the report, with the duration in seconds.
+#[derive(Serialize, Deserialize)]
+struct DiscardedSummary {
+    duration: f64,
+    iters: u64,
+    errors: u64,
+}
+
+#[derive(Deserialize)]
+struct SummaryInput {
+    total_time: f64,
+    concurrency: u32,
+    reset_at: Option<f64>,
+    #[serde(default)]
+    stopped: Option<String>,
+    #[serde(default)]
+    discarded: Option<DiscardedSummary>,
+    #[serde(default)]
+    suspect_durations: Option<SuspectSummary>,
+    #[serde(default)]
+    setup: Option<SetupSummary>,
+    paused_duration: f64,
+    pause_count: u64,
+    #[serde(default)]
+    folded_statuses: u64,
+    iters: TotalInput,
+    #[serde(default)]
+    errors: Option<TotalInput>,
+    items: TotalInput,
+    bytes: BytesInput,
+}
+
+#[derive(Deserialize)]
+struct BytesInput {
+    total: u64,
+    #[serde(rename = "in")]
+    bytes_in: Option<TotalInput>,
+    #[serde(rename = "out")]
+    bytes_out: Option<TotalInput>,
+}
+
+#[derive(Deserialize)]
+struct TotalInput {
+    total: u64,
+}
+
+#[derive(Deserialize)]
+struct LatencyInput {
+    hdr: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use super::*;
+    use crate::{
+        collector::ReportAggregator,
+        report::{IterReport, IterResult},
+        runner::{BenchOpts, IterInfo},
+    };
+
+    /// A report of 200 iterations of two workers, with a mix of statuses, errors and bytes.
+    fn sample_report() -> BenchReport {
+        let opts = BenchOpts {
+            concurrency: 2,
+            per_worker_latency: true,
+            slowest: 3,
+            ..Default::default()
+        };
+        let mut agg = ReportAggregator::new(&opts);
+        for i in 0..200u64 {
+            let mut info = IterInfo::new(i as u32 % 2, 2);
+            info.worker_seq = i / 2;
+            let start = Duration::from_millis(i * 50);
+            let duration = Duration::from_micros(500 + i * 37);
+            let result = match i % 20 {
+                0 => Err(anyhow::anyhow!("connection reset").into()),
+                1 | 2 => Ok(IterReport::new(duration, Status::server_error(503))),
+                _ => Ok(IterReport {
+                    bytes_in: 1000 + i,
+                    bytes_out: 100,
+                    items: 2,
+                    context: Some(format!("GET /items/{i}")),
+                    ..IterReport::new(duration, Status::success(200))
+                }),
+            };
+            agg.ingest(IterResult { info, start, result }, start + duration)
+                .unwrap();
+        }
+        let mut report = agg.into_report(Duration::from_secs(10), 2);
+        report.metadata = RunMetadata {
+            started_at: Some(UNIX_EPOCH + Duration::from_secs(1_717_243_203)),
+            finished_at: Some(UNIX_EPOCH + Duration::from_secs(1_717_243_213)),
+            command: vec!["bench".into(), "-c".into(), "2".into()],
+            duration: Some(Duration::from_secs(10)),
+            labels: [("env".to_string(), "test".to_string())].into(),
+            finished_by: Some(Bound::Duration),
+            ..Default::default()
+        };
+        report
+    }
+
+    fn print(reporter: &JsonReporter, report: &BenchReport) -> String {
+        let mut out = Vec::new();
+        reporter.print(&mut out, report).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let reporter = JsonReporter { embed_hdr: true, ..Default::default() };
+        let json = print(&reporter, &sample_report());
+        let loaded = JsonReporter::parse(&json).unwrap();
+        let reloaded = print(&reporter, &loaded);
+
+        let mut json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let reloaded: serde_json::Value = serde_json::from_str(&reloaded).unwrap();
+        // The per-second rate statistics are not part of what `parse` loads back.
+        let iters = json["summary"]["iters"].as_object_mut().unwrap();
+        assert!(iters.remove("rate_stdev").is_some() && iters.remove("rate_cv").is_some());
+        assert_eq!(json, reloaded);
+    }
+
+    /// The error of parsing the sample report after changing it with `edit`.
+    fn parse_err(edit: impl FnOnce(&mut serde_json::Value)) -> String {
+        let reporter = JsonReporter { embed_hdr: true, ..Default::default() };
+        let mut json: serde_json::Value = serde_json::from_str(&print(&reporter, &sample_report())).unwrap();
+        edit(&mut json);
+        match JsonReporter::parse(&json.to_string()) {
+            Ok(_) => panic!("the edited report parsed"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        for secs in [-1.0, 1e20] {
+            let err = parse_err(|json| json["summary"]["total_time"] = secs.into());
+            assert_eq!(err, format!("invalid total time of {secs} seconds"));
+            let err = parse_err(|json| json["slowest"][0]["latency"] = secs.into());
+            assert_eq!(err, format!("invalid latency of {secs} seconds"));
+            let err = parse_err(|json| json["metadata"]["config"]["duration"] = secs.into());
+            assert_eq!(err, format!("invalid duration of {secs} seconds"));
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_workers() {
+        let err = parse_err(|json| json["workers"][1]["worker_id"] = u32::MAX.into());
+        assert_eq!(err, format!("invalid worker id {} of a report of 2 workers", u32::MAX));
+        let err = parse_err(|json| json["workers"][1]["worker_id"] = 2.into());
+        assert_eq!(err, "invalid worker id 2 of a report of 2 workers");
+    }
+
+    #[test]
+    fn compact_is_one_line() {
+        let report = sample_report();
+        let compact = print(&JsonReporter { compact: true, ..Default::default() }, &report);
+        let pretty = print(&JsonReporter::default(), &report);
+
+        assert_eq!(compact.matches('\n').count(), 1);
+        assert!(compact.ends_with('\n'));
+        let compact: serde_json::Value = serde_json::from_str(&compact).unwrap();
+        let pretty: serde_json::Value = serde_json::from_str(&pretty).unwrap();
+        assert_eq!(compact, pretty);
+    }
+}
@@ -0,0 +1,273 @@
+use std::{cmp::Reverse, io::Write};
+
+use itertools::Itertools;
+
+use super::BenchReporter;
+use crate::{
+    duration::{DurationExt, FormattedDuration},
+    histogram::PERCENTAGES,
+    report::BenchReport,
+    util::{ByteUnit, IntoAdjustedByte, TryIntoAdjustedByte},
+};
+
+/// A Markdown reporter for benchmark results, suitable for pasting into GitHub comments.
+///
+/// The report is made of GitHub-flavored tables without any ANSI styling.
+#[derive(Default)]
+pub struct MarkdownReporter {
+    /// The unit convention used to display byte sizes.
+    pub byte_unit: ByteUnit,
+}
+
+impl MarkdownReporter {
+    /// Create a new Markdown reporter with the given byte unit convention.
+    pub fn new(byte_unit: ByteUnit) -> Self {
+        Self { byte_unit }
+    }
+}
+
+impl BenchReporter for MarkdownReporter {
+    fn print(&self, w: &mut dyn Write, report: &BenchReport) -> anyhow::Result<()> {
+        print_summary(w, report, self.byte_unit)?;
+
+        if report.stats.counter.iters > 0 {
+            writeln!(w)?;
+            print_latency(w, report)?;
+
+            writeln!(w)?;
+            print_status(w, report)?;
+        }
+
+        if !report.error_dist.is_empty() {
+            writeln!(w)?;
+            print_error(w, report)?;
+        }
+
+        Ok(())
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "md"
+    }
+}
+
+fn print_summary(w: &mut dyn Write, report: &BenchReport, byte_unit: ByteUnit) -> anyhow::Result<()> {
+    let elapsed = report.elapsed.as_secs_f64();
+    let counter = &report.stats.counter;
+
+    writeln!(w, "### Summary")?;
+    writeln!(w)?;
+    writeln!(
+        w,
+        "Benchmark took **{:.2}s** with concurrency **{}** (**{:.2}%** success).",
+        elapsed,
+        report.concurrency,
+        100.0 * report.success_ratio()
+    )?;
+    if report.pause_count > 0 {
+        writeln!(
+            w,
+            "Paused **{:.2}s** in total ({}×).",
+            report.paused_duration.as_secs_f64(),
+            report.pause_count
+        )?;
+    }
+    if let Some(reset_at) = report.reset_at {
+        writeln!(
+            w,
+            "Statistics were reset at **{:.2}s** of the run.",
+            reset_at.as_secs_f64()
+        )?;
+    }
+    writeln!(w)?;
+
+    print_table(
+        w,
+        &["", "Total", "Rate"],
+        &[Align::Left, Align::Right, Align::Right],
+        [
+            vec![
+                "Iters".to_string(),
+                counter.iters.to_string(),
+                format!("{:.2}/s", counter.iters as f64 / elapsed),
+            ],
+            vec![
+                "Errors".to_string(),
+                counter.errors.to_string(),
+                format!("{:.2}/s", counter.errors as f64 / elapsed),
+            ],
+            vec![
+                "Items".to_string(),
+                counter.items.to_string(),
+                format!("{:.2}/s", counter.items as f64 / elapsed),
+            ],
+            vec![
+                "Bytes".to_string(),
+                format!("{:.2}", counter.bytes.adjusted(byte_unit)),
+                format!("{:.2}/s", (counter.bytes as f64 / elapsed).adjusted(byte_unit)?),
+            ],
+        ],
+    )
+}
+
+fn print_latency(w: &mut dyn Write, report: &BenchReport) -> anyhow::Result<()> {
+    let hist = &report.hist;
+    writeln!(w, "### Latencies")?;
+    if hist.is_empty() {
+        return Ok(());
+    }
+    writeln!(w)?;
+
+    let u = hist.median().appropriate_unit();
+    let fmt = |d| format!("{:.2}", FormattedDuration::from(d, u));
+    print_table(
+        w,
+        &["Avg", "Min", "Med", "Max", "Stdev"],
+        &[Align::Right; 5],
+        [vec![
+            fmt(hist.mean()),
+            fmt(hist.min()),
+            fmt(hist.median()),
+            fmt(hist.max()),
+            fmt(hist.stdev()),
+        ]],
+    )?;
+    writeln!(w)?;
+
+    print_table(
+        w,
+        &["Percentile", "Latency"],
+        &[Align::Right; 2],
+        hist.percentiles(PERCENTAGES)
+            .filter(|&(p, _)| hist.is_reliable_percentile(p))
+            .map(|(p, v)| vec![format!("{:.2}%", p), fmt(v)]),
+    )?;
+    if let Some(note) = super::omitted_percentiles(hist, PERCENTAGES) {
+        writeln!(w)?;
+        writeln!(w, "_{note}_")?;
+    }
+    Ok(())
+}
+
+fn print_status(w: &mut dyn Write, report: &BenchReport) -> anyhow::Result<()> {
+    writeln!(w, "### Status distribution")?;
+    writeln!(w)?;
+    print_table(
+        w,
+        &["Status", "Iters"],
+        &[Align::Left, Align::Right],
+        report
+            .status_dist
+            .iter()
+            .sorted_unstable_by_key(|(_, &cnt)| Reverse(cnt))
+            .map(|(status, cnt)| vec![status.to_string(), cnt.to_string()]),
+    )
+}
+
+fn print_error(w: &mut dyn Write, report: &BenchReport) -> anyhow::Result<()> {
+    writeln!(w, "### Error distribution")?;
+    writeln!(w)?;
+    print_table(
+        w,
+        &["Error", "Iters"],
+        &[Align::Left, Align::Right],
+        report
+            .error_dist
+            .iter()
+            .sorted_unstable_by_key(|(_, &cnt)| Reverse(cnt))
+            .map(|(err, cnt)| vec![err.clone(), cnt.to_string()]),
+    )
+}
+
+#[derive(Clone, Copy)]
+enum Align {
+    Left,
+    Right,
+}
+
+fn print_table(
+    w: &mut dyn Write,
+    header: &[&str],
+    align: &[Align],
+    rows: impl IntoIterator<Item = Vec<String>>,
+) -> anyhow::Result<()> {
+    writeln!(w, "| {} |", header.iter().map(|s| escape(s)).join(" | "))?;
+    let sep = align.iter().map(|a| match a {
+        Align::Left => ":---",
+        Align::Right => "---:",
+    });
+    writeln!(w, "| {} |", sep.format(" | "))?;
+    for row in rows {
+        writeln!(w, "| {} |", row.iter().map(|s| escape(s)).join(" | "))?;
+    }
+    Ok(())
+}
+
+/// Escape a cell so that arbitrary text, e.g. multi-line errors, does not break the table.
+fn escape(cell: &str) -> String {
+    let mut out = String::with_capacity(cell.len());
+    for c in cell.trim().chars() {
+        match c {
+            '|' => out.push_str("\\|"),
+            '\\' => out.push_str("\\\\"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '\n' => out.push_str("<br>"),
+            '\r' => (),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        collector::ReportAggregator,
+        report::{IterReport, IterResult},
+        runner::{BenchOpts, IterInfo},
+        status::Status,
+    };
+
+    #[test]
+    fn escapes_table_cells() {
+        let mut agg = ReportAggregator::new(&BenchOpts::default());
+        for i in 0..10u64 {
+            let mut info = IterInfo::new(0, 1);
+            info.worker_seq = i;
+            let start = Duration::from_millis(i * 10);
+            let result = match i {
+                0..=2 => Err(anyhow::anyhow!("expected a | b\r\ngot <c>\\d").into()),
+                _ => Ok(IterReport::new(
+                    Duration::from_millis(i),
+                    Status::success(200).with_label("a|b\nc"),
+                )),
+            };
+            agg.ingest(IterResult { info, start, result }, start).unwrap();
+        }
+        let mut out = Vec::new();
+        MarkdownReporter::default()
+            .print(&mut out, &agg.into_report(Duration::from_secs(1), 1))
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(
+            out.contains("\n| expected a \\| b<br>got &lt;c&gt;\\\\d | 3 |\n"),
+            "{out}"
+        );
+        assert!(out.contains("\n| Success(200 a\\|b<br>c) | 7 |\n"), "{out}");
+
+        // Every table keeps the number of columns of its header.
+        for table in out.split("\n\n").filter(|block| block.starts_with('|')) {
+            let columns = |row: &str| row.replace("\\|", "").matches('|').count();
+            let header = columns(table.lines().next().unwrap());
+            for row in table.lines() {
+                assert!(row.starts_with("| ") && row.ends_with(" |"), "{row:?}");
+                assert_eq!(columns(row), header, "{row:?}");
+            }
+        }
+    }
+}
@@ -1,14 +1,43 @@
 //! This module defines a trait for printing benchmark reports.
+mod csv;
+#[cfg(feature = "html-report")]
+mod html;
 mod json;
+mod markdown;
+mod prom;
 mod text;
 
+pub use csv::CsvReporter;
+#[cfg(feature = "html-report")]
+pub use html::HtmlReporter;
 pub use json::JsonReporter;
+pub use markdown::MarkdownReporter;
+pub use prom::PromReporter;
 pub use text::TextReporter;
 
-use crate::report::BenchReport;
+use crate::{histogram::LatencyHistogram, report::BenchReport};
 
 /// A trait for reporting benchmark results.
 pub trait BenchReporter {
     /// Print the report to the given writer.
     fn print(&self, w: &mut dyn std::io::Write, report: &BenchReport) -> anyhow::Result<()>;
+
+    /// Print the report of a benchmark which is still running, e.g. to a report file rewritten periodically.
+    ///
+    /// Prints the report as usual by default. The JSON reporter marks the report with `"partial": true`.
+    fn print_partial(&self, w: &mut dyn std::io::Write, report: &BenchReport) -> anyhow::Result<()> {
+        self.print(w, report)
+    }
+
+    /// The file extension to use when the report is written to a file.
+    fn file_extension(&self) -> &'static str {
+        "txt"
+    }
+}
+
+/// A note on the percentiles omitted from a report for lack of samples, e.g.
+/// `p99.9+ omitted: insufficient samples (n=412)`.
+fn omitted_percentiles(hist: &LatencyHistogram, percentages: &[f64]) -> Option<String> {
+    let p = percentages.iter().find(|&&p| !hist.is_reliable_percentile(p))?;
+    Some(format!("p{p}+ omitted: insufficient samples (n={})", hist.count()))
 }
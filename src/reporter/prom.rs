@@ -0,0 +1,217 @@
+use std::{collections::BTreeMap, fmt::Display, io::Write};
+
+use itertools::Itertools;
+
+use super::BenchReporter;
+use crate::report::BenchReport;
+
+/// Quantiles of the latency summary.
+const QUANTILES: &[f64] = &[0.5, 0.9, 0.95, 0.99, 0.999];
+
+/// A reporter which writes the final results in the Prometheus text exposition format.
+///
+/// The output is suitable for the textfile collector of node-exporter. All metric names are prefixed with
+/// [`prefix`](Self::prefix) and every sample carries the [`labels`](Self::labels):
+///
+/// | Metric                           | Type    | Description                                 |
+/// |----------------------------------|---------|---------------------------------------------|
+/// | `<prefix>_concurrency`           | gauge   | Number of workers                           |
+/// | `<prefix>_elapsed_seconds`       | gauge   | Duration of the benchmark                   |
+/// | `<prefix>_iterations_total`      | counter | Total number of iterations                  |
+/// | `<prefix>_items_total`           | counter | Total number of items                       |
+/// | `<prefix>_bytes_total`           | counter | Total number of bytes                       |
+/// | `<prefix>_errors_total`          | counter | Total number of failed iterations           |
+/// | `<prefix>_success_ratio`         | gauge   | Ratio of successful iterations, from 0 to 1 |
+/// | `<prefix>_latency_seconds`       | summary | Iteration latency, by `quantile`            |
+///
+/// The latency quantiles are omitted when no iteration succeeded.
+pub struct PromReporter {
+    /// Prefix of the metric names.
+    pub prefix: String,
+    /// Labels attached to every sample.
+    pub labels: BTreeMap<String, String>,
+}
+
+impl Default for PromReporter {
+    fn default() -> Self {
+        Self { prefix: "rlt".to_string(), labels: BTreeMap::new() }
+    }
+}
+
+impl PromReporter {
+    /// Create a new Prometheus reporter with the given metric prefix and labels.
+    pub fn new(prefix: impl Into<String>, labels: BTreeMap<String, String>) -> Self {
+        Self { prefix: prefix.into(), labels }
+    }
+
+    fn label_pairs(&self) -> Vec<(String, String)> {
+        self.labels.iter().map(|(k, v)| (sanitize(k), v.clone())).collect()
+    }
+}
+
+impl BenchReporter for PromReporter {
+    fn print(&self, w: &mut dyn Write, report: &BenchReport) -> anyhow::Result<()> {
+        let counter = &report.stats.counter;
+        let mut out = Exposition {
+            w,
+            prefix: sanitize(&self.prefix),
+            labels: self.label_pairs(),
+            name: String::new(),
+        };
+
+        out.metric("concurrency", "gauge", "Number of concurrent workers.")?;
+        out.sample("", &[], report.concurrency)?;
+        out.metric("elapsed_seconds", "gauge", "Duration of the benchmark in seconds.")?;
+        out.sample("", &[], report.elapsed.as_secs_f64())?;
+        out.metric("iterations_total", "counter", "Total number of iterations.")?;
+        out.sample("", &[], counter.iters)?;
+        out.metric("items_total", "counter", "Total number of processed items.")?;
+        out.sample("", &[], counter.items)?;
+        out.metric("bytes_total", "counter", "Total number of processed bytes.")?;
+        out.sample("", &[], counter.bytes)?;
+        out.metric("errors_total", "counter", "Total number of failed iterations.")?;
+        out.sample("", &[], report.stats.counter.errors)?;
+        out.metric("success_ratio", "gauge", "Ratio of successful iterations.")?;
+        out.sample("", &[], report.success_ratio())?;
+
+        out.metric("latency_seconds", "summary", "Iteration latency in seconds.")?;
+        if !report.hist.is_empty() {
+            for &q in QUANTILES {
+                let v = report.hist.value_at_quantile(q).as_secs_f64();
+                out.sample("", &[("quantile", q.to_string())], v)?;
+            }
+        }
+        out.sample("_sum", &[], counter.duration.as_secs_f64())?;
+        out.sample("_count", &[], counter.iters)?;
+        Ok(())
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "prom"
+    }
+}
+
+/// Writer of metric families, which tracks the name of the current one.
+struct Exposition<'a> {
+    w: &'a mut dyn Write,
+    prefix: String,
+    labels: Vec<(String, String)>,
+    name: String,
+}
+
+impl Exposition<'_> {
+    /// Start a new metric family.
+    fn metric(&mut self, name: &str, kind: &str, help: &str) -> anyhow::Result<()> {
+        self.name = format!("{}_{name}", self.prefix);
+        writeln!(self.w, "# HELP {} {help}", self.name)?;
+        writeln!(self.w, "# TYPE {} {kind}", self.name)?;
+        Ok(())
+    }
+
+    /// Write a sample of the current metric family.
+    fn sample(&mut self, suffix: &str, extra: &[(&str, String)], v: impl Display) -> anyhow::Result<()> {
+        let labels = self
+            .labels
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .chain(extra.iter().map(|(k, v)| (*k, v.as_str())))
+            .map(|(k, v)| format!("{k}=\"{}\"", escape(v)))
+            .join(",");
+        match labels.is_empty() {
+            true => writeln!(self.w, "{}{suffix} {v}", self.name)?,
+            false => writeln!(self.w, "{}{suffix}{{{labels}}} {v}", self.name)?,
+        }
+        Ok(())
+    }
+}
+
+/// Escape a label value as required by the exposition format.
+fn escape(value: &str) -> String {
+    value.replace('\\', r"\\").replace('"', r#"\""#).replace('\n', r"\n")
+}
+
+/// Replace the characters which are not allowed in metric and label names with underscores.
+fn sanitize(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    match name.starts_with(|c: char| c.is_ascii_digit()) {
+        true => format!("_{name}"),
+        false => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use regex::Regex;
+
+    use super::*;
+    use crate::{
+        collector::ReportAggregator,
+        report::{IterReport, IterResult},
+        runner::{BenchOpts, IterInfo},
+        status::Status,
+    };
+
+    fn sample_report() -> BenchReport {
+        let mut agg = ReportAggregator::new(&BenchOpts::default());
+        for i in 0..10u64 {
+            let mut info = IterInfo::new(0, 1);
+            info.worker_seq = i;
+            let start = Duration::from_millis(i * 10);
+            let result = match i {
+                0 => Err(anyhow::anyhow!("bad \"quote\" \\ and\nnewline").into()),
+                _ => Ok(IterReport::new(Duration::from_millis(i), Status::success(200))),
+            };
+            agg.ingest(IterResult { info, start, result }, start).unwrap();
+        }
+        agg.into_report(Duration::from_secs(1), 1)
+    }
+
+    #[test]
+    fn escapes_label_values() {
+        let labels = BTreeMap::from([
+            ("env".to_string(), "test".to_string()),
+            ("run id".to_string(), "a \"quoted\" C:\\path\nsecond line".to_string()),
+        ]);
+        let mut out = Vec::new();
+        PromReporter::new("rlt", labels)
+            .print(&mut out, &sample_report())
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let value = r#""(?:[^"\\\n]|\\["\\n])*""#;
+        let label = format!("[a-zA-Z_][a-zA-Z0-9_]*={value}");
+        let sample = Regex::new(&format!(r"^([a-zA-Z_:][a-zA-Z0-9_:]*)(\{{{label}(,{label})*\}})? \S+$")).unwrap();
+        let comment = Regex::new(r"^# (HELP|TYPE) ([a-zA-Z_:][a-zA-Z0-9_:]*) \S.*$").unwrap();
+
+        let mut family = None;
+        for line in out.lines() {
+            if let Some(caps) = comment.captures(line) {
+                match &caps[1] {
+                    "HELP" => family = Some(caps[2].to_string()),
+                    _ => assert_eq!(family.as_deref(), Some(&caps[2]), "TYPE without HELP: {line}"),
+                }
+                continue;
+            }
+            let caps = sample
+                .captures(line)
+                .unwrap_or_else(|| panic!("invalid sample: {line:?}"));
+            let family = family.as_deref().expect("sample before any HELP");
+            assert!(caps[1].starts_with(family), "sample {line} outside of family {family}");
+            assert!(
+                line.contains(r#"run_id="a \"quoted\" C:\\path\nsecond line""#),
+                "{line}"
+            );
+        }
+
+        assert!(out.contains("# HELP rlt_errors_total Total number of failed iterations.\n"));
+        assert!(out.contains("# TYPE rlt_errors_total counter\n"));
+        assert!(out.contains("# TYPE rlt_latency_seconds summary\n"));
+        assert!(out.contains("rlt_errors_total{env=\"test\",run_id="));
+        assert!(!out.contains("newline"), "error messages are not exported");
+    }
+}
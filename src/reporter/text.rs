@@ -1,6 +1,12 @@
 use crossterm::style::{StyledContent, Stylize};
 use itertools::Itertools;
-use std::{cmp::Reverse, collections::HashMap, io::Write};
+use std::{
+    borrow::Cow,
+    cmp::Reverse,
+    collections::HashMap,
+    io::Write,
+    time::{Duration, SystemTime},
+};
 use tabled::settings::object::{Cell, Columns, FirstColumn, FirstRow, LastColumn, Object, Rows};
 use tabled::settings::Padding;
 use tabled::{
@@ -10,26 +16,87 @@ use tabled::{
 
 use crate::duration::TimeUnit;
 use crate::{
+    assertion::AssertionResult,
     duration::{DurationExt, FormattedDuration},
     histogram::{LatencyHistogram, PERCENTAGES},
-    report::BenchReport,
+    report::{BenchReport, SlowIteration},
     status::{Status, StatusKind},
-    util::{IntoAdjustedByte, TryIntoAdjustedByte},
+    util::{strip_ansi, ByteUnit, IntoAdjustedByte, TryIntoAdjustedByte},
 };
 
 /// A text reporter for benchmark results.
-pub struct TextReporter;
+#[derive(Default)]
+pub struct TextReporter {
+    /// The unit convention used to display byte sizes.
+    pub byte_unit: ByteUnit,
+
+    /// Draw the histogram with ASCII characters only.
+    pub ascii: bool,
+
+    /// Style the report with ANSI escape codes, e.g. when writing to a terminal.
+    pub color: bool,
+}
+
+impl TextReporter {
+    /// Create a new text reporter with the given byte unit convention.
+    pub fn new(byte_unit: ByteUnit) -> Self {
+        Self { byte_unit, ascii: false, color: true }
+    }
+}
 
 impl super::BenchReporter for TextReporter {
     fn print(&self, w: &mut dyn Write, report: &BenchReport) -> anyhow::Result<()> {
-        print_summary(w, report)?;
+        if !self.color {
+            let mut buf = Vec::new();
+            self.print_styled(&mut buf, report)?;
+            w.write_all(&strip_ansi(&buf))?;
+            return Ok(());
+        }
+        self.print_styled(w, report)
+    }
+}
+
+impl TextReporter {
+    /// Print a table of the reports of the steps of a concurrency sweep, in the order of the steps.
+    pub fn print_sweep(&self, w: &mut dyn Write, reports: &[BenchReport]) -> anyhow::Result<()> {
+        if !self.color {
+            let mut buf = Vec::new();
+            print_sweep(&mut buf, reports)?;
+            w.write_all(&strip_ansi(&buf))?;
+            return Ok(());
+        }
+        print_sweep(w, reports)
+    }
+
+    fn print_styled(&self, w: &mut dyn Write, report: &BenchReport) -> anyhow::Result<()> {
+        print_summary(w, report, self.byte_unit)?;
 
         if report.stats.counter.iters > 0 {
             writeln!(w)?;
-            print_latency(w, &report.hist)?;
+            print_latency(w, &report.hist, report.metadata.expected_interval, self.ascii)?;
+
+            if !report.phase_hists.is_empty() {
+                writeln!(w)?;
+                print_phases(w, &report.phase_hists)?;
+            }
+
+            if let Some(hist) = report.item_hist.as_ref().filter(|h| !h.is_empty()) {
+                writeln!(w)?;
+                print_item_latency(w, hist)?;
+            }
+
+            if let Some(hists) = report.worker_hists.as_ref().filter(|h| h.iter().any(|h| !h.is_empty())) {
+                writeln!(w)?;
+                print_worker_latency(w, hists)?;
+            }
 
             writeln!(w)?;
-            print_status(w, &report.status_dist)?;
+            print_status(w, &report.status_dist, report.folded_statuses)?;
+
+            if !report.slowest.is_empty() {
+                writeln!(w)?;
+                print_slowest(w, &report.slowest)?;
+            }
         }
 
         if !report.error_dist.is_empty() {
@@ -37,6 +104,17 @@ impl super::BenchReporter for TextReporter {
             print_error(w, report)?;
         }
 
+        #[cfg(feature = "sysinfo")]
+        if let Some(usage) = &report.resource_usage {
+            writeln!(w)?;
+            print_resource_usage(w, usage, self.byte_unit)?;
+        }
+
+        if !report.assertions.is_empty() {
+            writeln!(w)?;
+            print_assertions(w, &report.assertions)?;
+        }
+
         Ok(())
     }
 }
@@ -57,6 +135,7 @@ fn print_latency_histogram(
     hist: &LatencyHistogram,
     u: TimeUnit,
     indent: usize,
+    ascii: bool,
 ) -> anyhow::Result<()> {
     let quantiles = hist
         .quantiles()
@@ -67,9 +146,15 @@ fn print_latency_histogram(
     }
 
     let &max_count = quantiles.iter().map(|(_, count)| count).max().unwrap();
-    let quantiles = quantiles
-        .into_iter()
-        .map(|(latency, count)| vec![count.to_string(), latency, "🭵".into(), render_bar(count, max_count)]);
+    let sep = if ascii { "|" } else { "🭵" };
+    let quantiles = quantiles.into_iter().map(|(latency, count)| {
+        vec![
+            count.to_string(),
+            latency,
+            sep.into(),
+            render_bar(count, max_count, ascii),
+        ]
+    });
     let mut quantiles = Builder::from_iter(quantiles).build();
     quantiles
         .with(Style::empty())
@@ -95,35 +180,107 @@ fn print_latency_histogram(
     Ok(())
 }
 
-fn render_bar(count: u64, max_count: u64) -> String {
+fn render_bar(count: u64, max_count: u64, ascii: bool) -> String {
+    let (full, half) = if ascii { ("#", '-') } else { ("■", '◧') };
     let ratio = count as f64 / max_count as f64;
     let len = 32.0 * ratio;
-    let mut bar = "■".repeat(len as usize);
+    let mut bar = full.repeat(len as usize);
     if len.fract() >= 0.5 {
-        bar.push('◧');
+        bar.push(half);
     }
     bar
 }
 
 #[rustfmt::skip]
-fn print_summary(w: &mut dyn Write, report: &BenchReport) -> anyhow::Result<()> {
+fn print_summary(w: &mut dyn Write, report: &BenchReport, byte_unit: ByteUnit) -> anyhow::Result<()> {
     let elapsed = report.elapsed.as_secs_f64();
     let counter = &report.stats.counter;
 
     writeln!(w, "{}", "Summary".h1())?;
-    writeln!(w, "  Benchmark took {} with concurrency {} ({} success)",
+    write!(w, "  Benchmark took {} with concurrency {}",
                         format!("{:.2}s", elapsed).yellow().bold(),
-                        format!("{}", report.concurrency).cyan().bold(),
-                        render_success_ratio(100.0 * report.success_ratio()))?;
+                        format!("{}", report.concurrency).cyan().bold())?;
+    match report.is_empty() {
+        true => writeln!(w)?,
+        false => writeln!(w, " ({} success)", render_success_ratio(100.0 * report.success_ratio()))?,
+    }
+    if let (Some(started_at), Some(finished_at)) = (report.metadata.started_at, report.metadata.finished_at) {
+        let (start, end) = (utc(started_at), utc(finished_at));
+        let end = match end.split_once(' ') {
+            Some((date, time)) if start.starts_with(date) => time,
+            _ => &end,
+        };
+        writeln!(w, "  Ran {} → {} UTC", start, end)?;
+    }
+    if let Some(setup) = report.setup {
+        writeln!(w, "  Setup of {} worker{} took {} to {} ({} on average)",
+                        setup.workers,
+                        if setup.workers == 1 { "" } else { "s" },
+                        format!("{:.2}s", setup.min.as_secs_f64()).yellow().bold(),
+                        format!("{:.2}s", setup.max.as_secs_f64()).yellow().bold(),
+                        format!("{:.2}s", setup.mean.as_secs_f64()).yellow().bold())?;
+    }
+    if report.pause_count > 0 {
+        writeln!(w, "  Paused {} in total ({}×)",
+                        format!("{:.2}s", report.paused_duration.as_secs_f64()).yellow().bold(),
+                        report.pause_count)?;
+    }
+    if report.converged {
+        writeln!(w, "  Stopped: {}", "converged".green().bold())?;
+    }
+    if let Some(reason) = report.metadata.cancelled_by {
+        writeln!(w, "  Cancelled by: {}", reason.to_string().yellow().bold())?;
+    }
+    // only worth noting when either bound could have ended the run
+    if let Some(bound) = report.metadata.finished_by.filter(|_| {
+        report.metadata.duration.is_some() && report.metadata.iterations.is_some()
+    }) {
+        writeln!(w, "  Finished due to: {}", bound.to_string().cyan().bold())?;
+    }
+    if let Some(saturation) = report.saturation {
+        writeln!(w, "  {}", format!("Warning: {saturation}").yellow().bold())?;
+    }
+    if let Some(discarded) = report.discarded {
+        writeln!(w, "  Discarded the first {} of the run ({} iterations, {} errors)",
+                        format!("{:.2}s", discarded.duration.as_secs_f64()).yellow().bold(),
+                        discarded.iters,
+                        discarded.errors)?;
+    }
+    if let Some(suspect) = report.suspect {
+        writeln!(w, "  {} had suspect durations ({} zero, {} above {}){}",
+                        format!("{} iterations", suspect.total()).yellow().bold(),
+                        suspect.zero,
+                        suspect.too_long,
+                        humantime::format_duration(suspect.ceiling),
+                        if suspect.excluded { ", left out of the latency histogram" } else { "" })?;
+    }
+    if let Some(reset_at) = report.reset_at {
+        writeln!(w, "  Statistics were reset at {} of the run",
+                        format!("{:.2}s", reset_at.as_secs_f64()).yellow().bold())?;
+    }
+    if report.is_empty() {
+        writeln!(w, "  {}", "No iterations completed".yellow().bold())?;
+        return Ok(());
+    }
+    if let (Some(stdev), Some(cv)) = (report.rate_stats.stdev(), report.rate_stats.cv()) {
+        writeln!(w, "  Per-second iteration rate deviated by {} (CV {})",
+                        format!("{:.2}/s", stdev).yellow().bold(),
+                        format!("{:.2}%", 100.0 * cv).yellow().bold())?;
+    }
     writeln!(w)?;
 
-    let stats = vec![
+    let mut stats = vec![
         vec!["".into(), "Total".into(), "Rate".into()],
         vec![
             "Iters".into(),
             format!("{}", counter.iters),
             format!("{:.2}/s", counter.iters as f64 / elapsed),
         ],
+        vec![
+            "Errors".into(),
+            format!("{}", counter.errors),
+            format!("{:.2}/s", counter.errors as f64 / elapsed),
+        ],
         vec![
             "Items".into(),
             format!("{}", counter.items),
@@ -131,10 +288,19 @@ fn print_summary(w: &mut dyn Write, report: &BenchReport) -> anyhow::Result<()>
         ],
         vec![
             "Bytes".into(),
-            format!("{:.2}", counter.bytes.adjusted()),
-            format!("{:.2}/s", (counter.bytes as f64 / elapsed).adjusted()?),
+            format!("{:.2}", counter.bytes.adjusted(byte_unit)),
+            format!("{:.2}/s", (counter.bytes as f64 / elapsed).adjusted(byte_unit)?),
         ],
     ];
+    if counter.has_directions() {
+        for (name, bytes) in [("Bytes in", counter.bytes_in), ("Bytes out", counter.bytes_out)] {
+            stats.push(vec![
+                name.into(),
+                format!("{:.2}", bytes.adjusted(byte_unit)),
+                format!("{:.2}/s", (bytes as f64 / elapsed).adjusted(byte_unit)?),
+            ]);
+        }
+    }
     let mut stats = Builder::from(stats).build();
     stats
         .with(Style::empty())
@@ -142,7 +308,7 @@ fn print_summary(w: &mut dyn Write, report: &BenchReport) -> anyhow::Result<()>
         .with(Padding::new(2, 2, 0, 0))
         .with(Colorization::exact([Color::BOLD], Cell::new(0, 1)))
         .with(Colorization::exact([Color::BOLD], Cell::new(0, 2)))
-        .with(Colorization::exact([Color::FG_GREEN], Rows::new(1..=4).not(Columns::new(0..=0))))
+        .with(Colorization::exact([Color::FG_GREEN], Rows::new(1..).not(Columns::new(0..=0))))
         .modify(FirstRow, Alignment::center())
     ;
 
@@ -150,7 +316,21 @@ fn print_summary(w: &mut dyn Write, report: &BenchReport) -> anyhow::Result<()>
     Ok(())
 }
 
-fn print_latency(w: &mut dyn Write, hist: &LatencyHistogram) -> anyhow::Result<()> {
+/// Format a wall-clock time as `YYYY-MM-DD hh:mm:ss` in UTC.
+fn utc(t: SystemTime) -> String {
+    humantime::format_rfc3339_seconds(t)
+        .to_string()
+        .replace('T', " ")
+        .trim_end_matches('Z')
+        .to_string()
+}
+
+fn print_latency(
+    w: &mut dyn Write,
+    hist: &LatencyHistogram,
+    expected_interval: Option<Duration>,
+    ascii: bool,
+) -> anyhow::Result<()> {
     writeln!(w, "{}", "Latencies".h1())?;
     if hist.is_empty() {
         return Ok(());
@@ -161,6 +341,25 @@ fn print_latency(w: &mut dyn Write, hist: &LatencyHistogram) -> anyhow::Result<(
 
     writeln!(w, "{}", "  Stats".h2())?;
     print_latency_stats(w, hist, u)?;
+    if hist.saturated_count() > 0 {
+        let clipped = format!("{} latencies", hist.saturated_count()).yellow().bold();
+        writeln!(
+            w,
+            "    {clipped} exceeded the trackable range and were clipped at the max"
+        )?;
+    }
+    if hist.zero_count() > 0 {
+        let zeros = format!("{} latencies", hist.zero_count()).yellow().bold();
+        writeln!(w, "    {zeros} were zero")?;
+    }
+    if let Some(interval) = expected_interval {
+        let u = interval.appropriate_unit();
+        let interval = format!("{:.2}", FormattedDuration::from(interval, u)).yellow().bold();
+        writeln!(
+            w,
+            "    Corrected for coordinated omission with an expected interval of {interval}"
+        )?;
+    }
     writeln!(w)?;
 
     writeln!(w, "{}", "  Percentiles".h2())?;
@@ -168,7 +367,7 @@ fn print_latency(w: &mut dyn Write, hist: &LatencyHistogram) -> anyhow::Result<(
     writeln!(w)?;
 
     writeln!(w, "{}", "  Histogram".h2())?;
-    print_latency_histogram(w, hist, u, 2)?;
+    print_latency_histogram(w, hist, u, 2, ascii)?;
 
     Ok(())
 }
@@ -200,14 +399,178 @@ fn print_latency_stats(w: &mut dyn Write, hist: &LatencyHistogram, u: TimeUnit)
     Ok(())
 }
 
-fn print_latency_percentiles(w: &mut dyn Write, hist: &LatencyHistogram, u: TimeUnit) -> anyhow::Result<()> {
-    let percentiles = hist.percentiles(PERCENTAGES).map(|(p, v)| {
+/// The average, minimum, median, p90, p99 and maximum of the histogram, in the unit of its median.
+fn latency_cells(hist: &LatencyHistogram) -> Vec<String> {
+    let u = hist.median().appropriate_unit();
+    let fmt = |d| format!("{:.2}", FormattedDuration::from(d, u));
+    vec![
+        fmt(hist.mean()),
+        fmt(hist.min()),
+        fmt(hist.median()),
+        fmt(hist.value_at_quantile(0.9)),
+        fmt(hist.value_at_quantile(0.99)),
+        fmt(hist.max()),
+    ]
+}
+
+fn print_item_latency(w: &mut dyn Write, hist: &LatencyHistogram) -> anyhow::Result<()> {
+    writeln!(w, "{}", "Per-item latency".h1())?;
+    let header = ["Avg", "Min", "Med", "P90", "P99", "Max"].map(String::from).to_vec();
+    let mut table = Builder::from_iter([header, latency_cells(hist)]).build();
+    table
+        .with(Style::empty())
+        .with(Margin::new(2, 0, 0, 0))
+        .with(Padding::new(2, 2, 0, 0))
+        .with(Alignment::right())
+        .with(Colorization::exact([Color::BOLD], FirstRow))
+        .with(Colorization::exact([Color::FG_GREEN], Rows::new(1..)));
+    writeln!(w, "{}", table)?;
+    Ok(())
+}
+
+/// Number of the slowest workers shown in the per-worker latency table.
+const WORKER_ROWS: usize = 10;
+
+fn print_worker_latency(w: &mut dyn Write, hists: &[LatencyHistogram]) -> anyhow::Result<()> {
+    writeln!(w, "{}", "Per-worker latency".h1())?;
+    let workers = hists
+        .iter()
+        .enumerate()
+        .filter(|(_, h)| !h.is_empty())
+        .sorted_by_key(|(_, h)| Reverse(h.percentile(99.0)))
+        .collect_vec();
+    let u = workers[0].1.percentile(99.0).appropriate_unit();
+    let fmt = |d| format!("{:.2}", FormattedDuration::from(d, u));
+    let header = ["Worker", "Iters", "Avg", "P99"].map(String::from).to_vec();
+    let rows = workers.iter().take(WORKER_ROWS).map(|(id, hist)| {
+        vec![
+            id.to_string(),
+            hist.count().to_string(),
+            fmt(hist.mean()),
+            fmt(hist.percentile(99.0)),
+        ]
+    });
+    let mut table = Builder::from_iter(std::iter::once(header).chain(rows)).build();
+    table
+        .with(Style::empty())
+        .with(Margin::new(2, 0, 0, 0))
+        .with(Padding::new(2, 2, 0, 0))
+        .with(Alignment::right())
+        .with(Colorization::exact([Color::BOLD], FirstRow))
+        .with(Colorization::exact(
+            [Color::FG_GREEN],
+            Rows::new(1..).not(Columns::new(0..=1)),
+        ));
+    writeln!(w, "{}", table)?;
+    if let Some((_, fastest)) = workers.get(WORKER_ROWS..).and_then(|rest| rest.last()) {
+        let more = workers.len() - WORKER_ROWS;
+        let p99 = fmt(fastest.percentile(99.0)).green();
+        writeln!(w, "    and {more} more workers, down to a P99 of {p99}")?;
+    }
+    Ok(())
+}
+
+fn print_phases(w: &mut dyn Write, phases: &[(Cow<'static, str>, LatencyHistogram)]) -> anyhow::Result<()> {
+    writeln!(w, "{}", "Phase latencies".h1())?;
+    let header = ["Phase", "Avg", "Min", "Med", "P90", "P99", "Max"]
+        .map(String::from)
+        .to_vec();
+    let rows = phases.iter().map(|(name, hist)| {
+        let mut row = vec![name.to_string()];
+        row.extend(latency_cells(hist));
+        row
+    });
+    let mut phases = Builder::from_iter(std::iter::once(header).chain(rows)).build();
+    phases
+        .with(Style::empty())
+        .with(Margin::new(2, 0, 0, 0))
+        .with(Padding::new(2, 2, 0, 0))
+        .with(Alignment::right())
+        .with(Colorization::exact([Color::BOLD], FirstRow))
+        .with(Colorization::exact(
+            [Color::FG_GREEN],
+            Rows::new(1..).not(Columns::new(0..=0)),
+        ))
+        .modify(FirstColumn, Alignment::left());
+    writeln!(w, "{}", phases)?;
+    Ok(())
+}
+
+fn print_slowest(w: &mut dyn Write, slowest: &[SlowIteration]) -> anyhow::Result<()> {
+    writeln!(w, "{}", "Slowest iterations".h1())?;
+    let u = slowest[0].duration.appropriate_unit();
+    let header = ["Latency", "Worker", "Seq", "Status", "Context"]
+        .map(String::from)
+        .to_vec();
+    let rows = slowest.iter().map(|it| {
         vec![
-            format!("{:.2}%", p),
-            format!(" in "),
-            format!("{:.2}", FormattedDuration::from(v, u)),
+            format!("{:.2}", FormattedDuration::from(it.duration, u)),
+            it.worker_id.to_string(),
+            it.worker_seq.to_string(),
+            it.status.to_string(),
+            it.context.clone().unwrap_or_default(),
         ]
     });
+    let mut slowest = Builder::from_iter(std::iter::once(header).chain(rows)).build();
+    slowest
+        .with(Style::empty())
+        .with(Margin::new(2, 0, 0, 0))
+        .with(Padding::new(2, 2, 0, 0))
+        .with(Alignment::right())
+        .with(Colorization::exact([Color::BOLD], FirstRow))
+        .with(Colorization::exact(
+            [Color::FG_GREEN],
+            Rows::new(1..).intersect(Columns::new(0..=0)),
+        ))
+        .modify(LastColumn, Alignment::left());
+    writeln!(w, "{}", slowest)?;
+    Ok(())
+}
+
+fn print_sweep(w: &mut dyn Write, reports: &[BenchReport]) -> anyhow::Result<()> {
+    writeln!(w, "{}", "Concurrency sweep".h1())?;
+    let header = ["Concurrency", "Iters/s", "P50", "P99", "Errors"]
+        .map(String::from)
+        .to_vec();
+    let latency = |d: std::time::Duration| {
+        let u = d.appropriate_unit();
+        format!("{:.2}", FormattedDuration::from(d, u))
+    };
+    let rows = reports.iter().map(|r| {
+        vec![
+            r.concurrency.to_string(),
+            format!("{:.2}", r.stats.counter.iters as f64 / r.elapsed.as_secs_f64()),
+            latency(r.hist.median()),
+            latency(r.hist.percentile(99.0)),
+            format!("{:.2}%", 100.0 * r.error_ratio()),
+        ]
+    });
+    let mut sweep = Builder::from_iter(std::iter::once(header).chain(rows)).build();
+    sweep
+        .with(Style::empty())
+        .with(Margin::new(2, 0, 0, 0))
+        .with(Padding::new(2, 2, 0, 0))
+        .with(Alignment::right())
+        .with(Colorization::exact([Color::BOLD], FirstRow))
+        .with(Colorization::exact(
+            [Color::FG_GREEN],
+            Rows::new(1..).intersect(Columns::new(1..=1)),
+        ));
+    writeln!(w, "{}", sweep)?;
+    Ok(())
+}
+
+fn print_latency_percentiles(w: &mut dyn Write, hist: &LatencyHistogram, u: TimeUnit) -> anyhow::Result<()> {
+    let percentiles = hist
+        .percentiles(PERCENTAGES)
+        .filter(|&(p, _)| hist.is_reliable_percentile(p))
+        .map(|(p, v)| {
+            vec![
+                format!("{:.2}%", p),
+                " in ".to_string(),
+                format!("{:.2}", FormattedDuration::from(v, u)),
+            ]
+        });
     let mut percentiles = Builder::from_iter(percentiles).build();
     percentiles
         .with(Style::empty())
@@ -218,10 +581,13 @@ fn print_latency_percentiles(w: &mut dyn Write, hist: &LatencyHistogram, u: Time
         .with(Colorization::exact([Color::FG_GREEN], LastColumn))
         .modify(LastColumn, Alignment::right());
     writeln!(w, "{}", percentiles)?;
+    if let Some(note) = super::omitted_percentiles(hist, PERCENTAGES) {
+        writeln!(w, "    {}", note.yellow())?;
+    }
     Ok(())
 }
 
-fn print_status(w: &mut dyn Write, status: &HashMap<Status, u64>) -> anyhow::Result<()> {
+fn print_status(w: &mut dyn Write, status: &HashMap<Status, u64>, folded: u64) -> anyhow::Result<()> {
     let status_v = status
         .iter()
         .sorted_unstable_by_key(|(_, &cnt)| Reverse(cnt))
@@ -234,6 +600,8 @@ fn print_status(w: &mut dyn Write, status: &HashMap<Status, u64>) -> anyhow::Res
             let count = format!("{count:>count_width$}").green();
             let status = match status.kind() {
                 StatusKind::Success => status.to_string().green(),
+                StatusKind::Informational => status.to_string().blue(),
+                StatusKind::Redirect => status.to_string().cyan(),
                 StatusKind::ClientError => status.to_string().yellow(),
                 StatusKind::ServerError => status.to_string().red(),
                 StatusKind::Error => status.to_string().red(),
@@ -242,6 +610,61 @@ fn print_status(w: &mut dyn Write, status: &HashMap<Status, u64>) -> anyhow::Res
             writeln!(w, "  [{count}] {status}")?;
         }
     }
+    if folded > 0 {
+        let note = format!("Status cardinality capped, {folded} iterations were counted under (other)");
+        writeln!(w, "  {}", note.yellow())?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "sysinfo")]
+fn print_resource_usage(w: &mut dyn Write, usage: &crate::ResourceUsage, byte_unit: ByteUnit) -> anyhow::Result<()> {
+    writeln!(w, "{}", "Resource usage".h1())?;
+    writeln!(
+        w,
+        "  CPU: {} mean, {} max of {} cores",
+        format!("{:.1}%", usage.cpu_mean).green(),
+        format!("{:.1}%", usage.cpu_max).green(),
+        usage.cores
+    )?;
+    writeln!(
+        w,
+        "  RSS: {} mean, {} max",
+        format!("{:.2}", usage.rss_mean.adjusted(byte_unit)).green(),
+        format!("{:.2}", usage.rss_max.adjusted(byte_unit)).green(),
+    )?;
+    if usage.saturated() {
+        writeln!(
+            w,
+            "  {}",
+            "Warning: the benchmark process used over 90% of the available cores, so it may have been the \
+             bottleneck"
+                .yellow()
+                .bold()
+        )?;
+    }
+    Ok(())
+}
+
+fn print_assertions(w: &mut dyn Write, assertions: &[AssertionResult]) -> anyhow::Result<()> {
+    writeln!(w, "{}", "Assertions".h1())?;
+    let width = assertions
+        .iter()
+        .map(|a| a.assertion.to_string().len())
+        .max()
+        .unwrap_or(0);
+    for a in assertions {
+        let outcome = match a.passed {
+            true => "PASS".green().bold(),
+            false => "FAIL".red().bold(),
+        };
+        writeln!(
+            w,
+            "  {outcome}  {:<width$}  (actual {})",
+            a.assertion.to_string(),
+            a.actual()
+        )?;
+    }
     Ok(())
 }
 
@@ -255,7 +678,24 @@ fn print_error(w: &mut dyn Write, report: &BenchReport) -> anyhow::Result<()> {
     let iters_width = max.to_string().len();
     writeln!(w, "{}", "Error distribution".h1())?;
     for (error, count) in error_v {
-        writeln!(w, "{}", format!("  [{count:>iters_width$}] {error}").red())?;
+        let mut samples = report
+            .error_samples
+            .get(error)
+            .into_iter()
+            .flatten()
+            .filter(|&s| s != error);
+        match samples.next() {
+            Some(example) => writeln!(
+                w,
+                "{} {}",
+                format!("  [{count:>iters_width$}] {error}").red(),
+                format!("— example: {example}").dark_grey()
+            )?,
+            None => writeln!(w, "{}", format!("  [{count:>iters_width$}] {error}").red())?,
+        }
+        for sample in samples {
+            writeln!(w, "  {:iters_width$}    {}", "", sample.as_str().dark_grey())?;
+        }
     }
     Ok(())
 }
@@ -0,0 +1,170 @@
+//! This module samples the resource usage of the benchmark process itself, to tell whether the load generator
+//! was the bottleneck of the benchmark.
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use tokio::{
+    task::JoinHandle,
+    time::{Duration, Instant, MissedTickBehavior},
+};
+use tokio_util::sync::CancellationToken;
+
+/// Share of the available cores above which the process is considered saturated.
+const SATURATED_RATIO: f64 = 0.9;
+
+/// A sample of the resource usage of the benchmark process.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ResourceSample {
+    /// CPU usage since the previous sample, in percent of a single core.
+    pub cpu: f64,
+    /// Resident set size, in bytes.
+    pub rss: u64,
+}
+
+/// Resource usage of the benchmark process over the run, sampled once per second.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ResourceUsage {
+    /// Number of the samples taken.
+    pub samples: u64,
+    /// Mean CPU usage, in percent of a single core.
+    pub cpu_mean: f64,
+    /// Maximum CPU usage of a sample, in percent of a single core.
+    pub cpu_max: f64,
+    /// Mean resident set size, in bytes.
+    pub rss_mean: u64,
+    /// Maximum resident set size of a sample, in bytes.
+    pub rss_max: u64,
+    /// Number of cores available to the process.
+    pub cores: usize,
+}
+
+impl ResourceUsage {
+    /// Whether the CPU usage of the process reached 90% of the available cores at some point, in which case the
+    /// load generator may have limited the benchmark.
+    pub fn saturated(&self) -> bool {
+        self.cpu_max >= SATURATED_RATIO * 100.0 * self.cores as f64
+    }
+
+    fn record(&mut self, sample: ResourceSample) {
+        let n = self.samples as f64;
+        self.cpu_mean = (self.cpu_mean * n + sample.cpu) / (n + 1.0);
+        self.rss_mean = ((self.rss_mean as f64 * n + sample.rss as f64) / (n + 1.0)) as u64;
+        self.cpu_max = self.cpu_max.max(sample.cpu);
+        self.rss_max = self.rss_max.max(sample.rss);
+        self.samples += 1;
+    }
+}
+
+/// The latest sample of a [`ResourceSampler`], if any.
+pub(crate) type LatestSample = Arc<Mutex<Option<ResourceSample>>>;
+
+/// A background task which samples the resource usage of the process every second.
+///
+/// A sample which cannot be taken, e.g. on platforms without `getrusage`, is skipped.
+pub(crate) struct ResourceSampler {
+    cancel: CancellationToken,
+    handle: JoinHandle<ResourceUsage>,
+    latest: LatestSample,
+}
+
+impl ResourceSampler {
+    /// Start sampling the resource usage of the process.
+    pub(crate) fn start() -> Self {
+        let cancel = CancellationToken::new();
+        let latest = LatestSample::default();
+        let handle = tokio::spawn({
+            let (cancel, latest) = (cancel.clone(), latest.clone());
+            async move {
+                let mut usage = ResourceUsage {
+                    cores: std::thread::available_parallelism().map_or(1, |n| n.get()),
+                    ..Default::default()
+                };
+                let mut ticker = tokio::time::interval(Duration::from_secs(1));
+                ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                ticker.tick().await;
+                let mut prev = (Instant::now(), cpu_time());
+                loop {
+                    tokio::select! {
+                        _ = cancel.cancelled() => return usage,
+                        _ = ticker.tick() => (),
+                    }
+                    let now = (Instant::now(), cpu_time());
+                    if let (Some(cpu), Some(prev_cpu), Some(rss)) = (now.1, prev.1, rss()) {
+                        let wall = now.0 - prev.0;
+                        let sample = ResourceSample {
+                            cpu: 100.0 * cpu.saturating_sub(prev_cpu).as_secs_f64() / wall.as_secs_f64(),
+                            rss,
+                        };
+                        usage.record(sample);
+                        *latest.lock() = Some(sample);
+                    }
+                    prev = now;
+                }
+            }
+        });
+        Self { cancel, handle, latest }
+    }
+
+    /// The latest sample, updated every second.
+    pub(crate) fn latest(&self) -> LatestSample {
+        self.latest.clone()
+    }
+
+    /// Stop sampling and return the usage over the run, or `None` if no sample could be taken.
+    pub(crate) async fn shutdown(self) -> Option<ResourceUsage> {
+        self.cancel.cancel();
+        self.handle.await.ok().filter(|usage| usage.samples > 0)
+    }
+}
+
+/// Total CPU time of the process, in user and system mode.
+#[cfg(unix)]
+fn cpu_time() -> Option<Duration> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+    // SAFETY: `getrusage` fills the struct on success
+    let usage = unsafe {
+        if libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) != 0 {
+            return None;
+        }
+        usage.assume_init()
+    };
+    let time = |t: libc::timeval| Duration::new(t.tv_sec as u64, t.tv_usec as u32 * 1000);
+    Some(time(usage.ru_utime) + time(usage.ru_stime))
+}
+
+#[cfg(not(unix))]
+fn cpu_time() -> Option<Duration> {
+    None
+}
+
+/// Resident set size of the process, in bytes.
+#[cfg(target_os = "linux")]
+fn rss() -> Option<u64> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    // SAFETY: `sysconf` has no preconditions
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    Some(pages * u64::try_from(page_size).ok()?)
+}
+
+/// Resident set size of the process, in bytes. Falls back to the maximum one where the current one is not
+/// available.
+#[cfg(all(unix, not(target_os = "linux")))]
+fn rss() -> Option<u64> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+    // SAFETY: `getrusage` fills the struct on success
+    let usage = unsafe {
+        if libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) != 0 {
+            return None;
+        }
+        usage.assume_init()
+    };
+    // in bytes on macOS, in kilobytes elsewhere
+    let scale = if cfg!(target_os = "macos") { 1 } else { 1024 };
+    Some(u64::try_from(usage.ru_maxrss).ok()? * scale)
+}
+
+#[cfg(not(unix))]
+fn rss() -> Option<u64> {
+    None
+}
@@ -1,17 +1,19 @@
 //! This module defines traits for stateful and stateless benchmark suites.
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use std::{
     sync::{
-        atomic::{AtomicU64, Ordering},
-        Arc,
+        atomic::{AtomicU32, AtomicU64, Ordering},
+        Arc, OnceLock,
     },
-    time::Duration,
+    time::{Duration, SystemTime},
 };
 use tokio::{
     select,
     sync::{mpsc, watch},
     task::JoinSet,
+    time::Instant,
 };
 use tokio_util::sync::CancellationToken;
 
@@ -25,14 +27,70 @@ cfg_if::cfg_if! {
 
 use crate::{
     clock::Clock,
+    pause::PauseControl,
     // rate_limiter::{self, RateLimiter},
-    report::IterReport,
+    report::{BenchResult, IterReport, IterResult, SetupStats},
 };
 
+/// A configured bound of a benchmark, which ends it once reached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Bound {
+    /// The [`BenchOpts::duration`] of the benchmark.
+    Duration,
+    /// The number of [`BenchOpts::iterations`] of the benchmark.
+    Iterations,
+}
+
+impl std::fmt::Display for Bound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Bound::Duration => write!(f, "duration"),
+            Bound::Iterations => write!(f, "iterations"),
+        }
+    }
+}
+
+/// What cancelled a benchmark before it reached its configured end.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CancelReason {
+    /// The user quit the TUI.
+    User,
+    /// The process received a shutdown signal, e.g. Ctrl-C or SIGTERM.
+    Signal,
+    /// A `POST /stop` request to the control endpoint.
+    Control,
+}
+
+impl std::fmt::Display for CancelReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CancelReason::User => write!(f, "user"),
+            CancelReason::Signal => write!(f, "signal"),
+            CancelReason::Control => write!(f, "control"),
+        }
+    }
+}
+
+/// The phase of a benchmark run, see [`Runner::phase`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BenchPhase {
+    /// The workers are creating their states and running [`BenchSuite::setup`].
+    Setup,
+    /// Every worker finished its setup and runs the iterations.
+    Running,
+}
+
 /// Core options for the benchmark runner.
+///
+/// More fields may be added in the future, so start from [`BenchOpts::default`] and set the fields of interest.
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct BenchOpts {
-    /// Start time of the benchmark.
+    /// The logical clock of the benchmark, which the elapsed times are measured with.
+    ///
+    /// It must be the clock of the [`PauseControl`] passed to [`Runner::new`], so that pausing stops it.
     pub clock: Clock,
 
     /// Number of concurrent workers.
@@ -47,19 +105,232 @@ pub struct BenchOpts {
     #[cfg(feature = "rate_limit")]
     /// Rate limit for benchmarking, in iterations per second (ips).
     pub rate: Option<NonZeroU32>,
+
+    /// How errors are aggregated into the error distribution.
+    pub errors: ErrorOpts,
+
+    /// Maximum number of distinct statuses. Further statuses are counted under [`Status::other`] of their kind.
+    ///
+    /// [`Status::other`]: crate::Status::other
+    pub status_max_kinds: usize,
+
+    /// Keep per-second samples of the run in the report, see [`BenchReport::timeseries`].
+    ///
+    /// [`BenchReport::timeseries`]: crate::BenchReport::timeseries
+    pub record_timeseries: bool,
+
+    /// Number of the slowest iterations to keep in the report, see [`BenchReport::slowest`]. Zero disables it.
+    ///
+    /// [`BenchReport::slowest`]: crate::BenchReport::slowest
+    pub slowest: usize,
+
+    /// Count redirects as successful iterations in the success and error ratios.
+    pub redirect_is_success: bool,
+
+    /// Also track the latency per item of the iterations, see [`BenchReport::item_hist`].
+    ///
+    /// [`BenchReport::item_hist`]: crate::BenchReport::item_hist
+    pub per_item_latency: bool,
+
+    /// Also track a small latency histogram per worker, see [`BenchReport::worker_hists`]. Takes about 30 KiB
+    /// of memory per worker.
+    ///
+    /// [`BenchReport::worker_hists`]: crate::BenchReport::worker_hists
+    pub per_worker_latency: bool,
+
+    /// Correct the latency histogram for coordinated omission with this expected interval between iterations,
+    /// see [`HistogramConfig::expected_interval`].
+    ///
+    /// [`HistogramConfig::expected_interval`]: crate::HistogramConfig::expected_interval
+    pub expected_interval: Option<Duration>,
+
+    /// Leave out the iterations which finished within this time from the start of the run from the report, while
+    /// still showing them live, see [`BenchReport::discarded`].
+    ///
+    /// [`BenchReport::discarded`]: crate::BenchReport::discarded
+    pub discard_first: Option<Duration>,
+
+    /// Latencies above this one are implausible and counted as suspect, like zero latencies, see
+    /// [`BenchReport::suspect`].
+    ///
+    /// [`BenchReport::suspect`]: crate::BenchReport::suspect
+    pub suspect_ceiling: Duration,
+
+    /// Leave the suspect latencies out of the latency histograms and the slowest iterations.
+    pub exclude_suspect: bool,
+
+    /// Delay the first iteration of each worker, after its setup, by a random duration below this one, so the
+    /// workers do not all hit the target at once. The delays are drawn from [`BenchOpts::seed`].
+    pub start_jitter: Option<Duration>,
+
+    /// Seed of the random choices of the run, e.g. [`BenchOpts::start_jitter`]. Drawn from the system time
+    /// when not set.
+    pub seed: Option<u64>,
+
+    /// Stop the benchmark once it reaches a steady state, see [`StableOpts`].
+    pub until_stable: Option<StableOpts>,
+}
+
+/// Options for stopping a benchmark once it reaches a steady state.
+///
+/// The elapsed time is split into windows. A window is stable if its p99 latency and iteration rate are both
+/// within the tolerance of the previous window, and the benchmark stops after enough consecutive stable
+/// windows. [`BenchOpts::duration`] still caps the run if it never converges.
+#[derive(Clone, Copy, Debug)]
+pub struct StableOpts {
+    /// Maximum deviation from the previous window, as a fraction of its value.
+    pub tolerance: f64,
+
+    /// Number of consecutive stable windows to stop after.
+    pub windows: u32,
+
+    /// Length of a window.
+    pub window: Duration,
+}
+
+impl Default for StableOpts {
+    fn default() -> Self {
+        Self { tolerance: 0.02, windows: 3, window: Duration::from_secs(60) }
+    }
+}
+
+/// Classify an iteration error into a key of the error distribution, or `None` to fall back to the builtin
+/// classification.
+pub type ErrorClassifier = fn(&anyhow::Error) -> Option<String>;
+
+/// Options for aggregating iteration errors into the error distribution.
+///
+/// Errors are keyed by their class: the result of the [`classifier`](Self::classifier) if any, then the kind
+/// of an [`std::io::Error`] found in the error chain. Errors which cannot be classified are keyed by their
+/// message, which may embed request ids or addresses and would otherwise yield one entry per iteration, so
+/// messages are normalized into keys and the number of distinct keys is capped.
+#[derive(Clone, Copy, Debug)]
+pub struct ErrorOpts {
+    /// Maximum length of an error key, in characters. Longer messages are truncated.
+    pub max_len: usize,
+
+    /// Replace uuids, hex strings and numbers in error messages with placeholders.
+    pub normalize: bool,
+
+    /// Maximum number of distinct error keys. Further errors are counted under `"(other)"`.
+    pub max_kinds: usize,
+
+    /// Number of verbatim error messages kept as samples for each key.
+    pub samples: usize,
+
+    /// Custom classifier of errors, see [`BenchSuite::error_classifier`].
+    pub classifier: Option<ErrorClassifier>,
+}
+
+impl Default for ErrorOpts {
+    fn default() -> Self {
+        Self {
+            max_len: 200,
+            normalize: false,
+            max_kinds: 100,
+            samples: 3,
+            classifier: None,
+        }
+    }
+}
+
+impl Default for BenchOpts {
+    /// A single worker which runs until it is cancelled, with a clock started now.
+    fn default() -> Self {
+        Self {
+            clock: Clock::start_at(Instant::now()),
+            concurrency: 1,
+            iterations: None,
+            duration: None,
+            #[cfg(feature = "rate_limit")]
+            rate: None,
+            errors: ErrorOpts::default(),
+            status_max_kinds: 256,
+            record_timeseries: false,
+            slowest: 0,
+            redirect_is_success: false,
+            per_item_latency: false,
+            per_worker_latency: false,
+            expected_interval: None,
+            discard_first: None,
+            suspect_ceiling: Duration::from_secs(3600),
+            exclude_suspect: false,
+            start_jitter: None,
+            seed: None,
+            until_stable: None,
+        }
+    }
+}
+
+impl BenchOpts {
+    /// Progress of the benchmark in `[0, 1]`, or `None` if it runs infinitely.
+    ///
+    /// When both iterations and duration are set, the larger ratio wins.
+    pub(crate) fn progress(&self, iters: u64, elapsed: Duration) -> Option<f64> {
+        let iter_ratio = self.iterations.map(|n| iters as f64 / n as f64);
+        let time_ratio = self.duration.map(|d| elapsed.as_secs_f64() / d.as_secs_f64());
+        let ratio = match (iter_ratio, time_ratio) {
+            (None, None) => return None,
+            (Some(r), None) | (None, Some(r)) => r,
+            (Some(a), Some(b)) => a.max(b),
+        };
+        Some(ratio.clamp(0.0, 1.0))
+    }
+
+    /// Estimated time until the benchmark finishes, given the recent iteration rate.
+    ///
+    /// When both iterations and duration are set, the bound which is hit first wins, see
+    /// [`first_bound`](Self::first_bound). Returns `None` if the benchmark runs infinitely or the remaining
+    /// iterations cannot be estimated because the rate is zero.
+    pub(crate) fn eta(&self, iters: u64, elapsed: Duration, rate: f64) -> Option<Duration> {
+        let (time_eta, iter_eta) = self.etas(iters, elapsed, rate);
+        match self.first_bound(iters, elapsed, rate)? {
+            Bound::Duration => time_eta,
+            Bound::Iterations => iter_eta,
+        }
+    }
+
+    /// The bound which is predicted to end the benchmark first given the recent iteration rate, or `None` if it
+    /// runs infinitely.
+    ///
+    /// The duration wins while the remaining iterations cannot be estimated because the rate is zero.
+    pub(crate) fn first_bound(&self, iters: u64, elapsed: Duration, rate: f64) -> Option<Bound> {
+        match self.etas(iters, elapsed, rate) {
+            (Some(time_eta), Some(iter_eta)) if iter_eta < time_eta => Some(Bound::Iterations),
+            (Some(_), _) => Some(Bound::Duration),
+            (None, _) => self.iterations.map(|_| Bound::Iterations),
+        }
+    }
+
+    /// Estimated times until the duration and the iterations of the benchmark are reached, if set.
+    fn etas(&self, iters: u64, elapsed: Duration, rate: f64) -> (Option<Duration>, Option<Duration>) {
+        let time_eta = self.duration.map(|d| d.saturating_sub(elapsed));
+        let iter_eta = self
+            .iterations
+            .filter(|_| rate > 0.0)
+            .map(|n| Duration::from_secs_f64(n.saturating_sub(iters) as f64 / rate));
+        (time_eta, iter_eta)
+    }
 }
 
 /// A trait for benchmark suites.
 #[async_trait]
+#[allow(clippy::double_must_use)]
 pub trait BenchSuite: Clone {
     /// The state for each worker during the benchmark.
     type WorkerState: Send;
 
+    /// Check the target once before any worker state is created, e.g. with a single request, so that a
+    /// misconfigured target fails fast instead of with an error per iteration.
+    async fn preflight(&self) -> Result<()> {
+        Ok(())
+    }
+
     /// Initialize the state for a worker.
     async fn state(&self, worker_id: u32) -> Result<Self::WorkerState>;
 
     /// Run a single iteration of the benchmark.
-    async fn bench(&mut self, state: &mut Self::WorkerState, info: &IterInfo) -> Result<IterReport>;
+    async fn bench(&mut self, state: &mut Self::WorkerState, info: &IterInfo) -> BenchResult<IterReport>;
 
     /// Setup procedure before each worker starts.
     #[allow(unused_variables)]
@@ -72,13 +343,30 @@ pub trait BenchSuite: Clone {
     async fn teardown(self, state: Self::WorkerState, info: IterInfo) -> Result<()> {
         Ok(())
     }
+
+    /// Classifier of the errors returned by [`bench`](Self::bench), e.g. to group them by the error types of a
+    /// client library.
+    fn error_classifier(&self) -> Option<ErrorClassifier> {
+        None
+    }
 }
 
 /// A trait for stateless benchmark suites.
 #[async_trait]
+#[allow(clippy::double_must_use)]
 pub trait StatelessBenchSuite {
+    /// Check the target once before the benchmark starts, see [`BenchSuite::preflight`].
+    async fn preflight(&self) -> Result<()> {
+        Ok(())
+    }
+
     /// Run a single iteration of the benchmark.
-    async fn bench(&mut self, info: &IterInfo) -> Result<IterReport>;
+    async fn bench(&mut self, info: &IterInfo) -> BenchResult<IterReport>;
+
+    /// Classifier of the errors returned by [`bench`](Self::bench), see [`BenchSuite::error_classifier`].
+    fn error_classifier(&self) -> Option<ErrorClassifier> {
+        None
+    }
 }
 
 #[async_trait]
@@ -88,16 +376,243 @@ where
 {
     type WorkerState = ();
 
+    async fn preflight(&self) -> Result<()> {
+        StatelessBenchSuite::preflight(self).await
+    }
+
     async fn state(&self, _: u32) -> Result<()> {
         Ok(())
     }
 
-    async fn bench(&mut self, _: &mut Self::WorkerState, info: &IterInfo) -> Result<IterReport> {
+    async fn bench(&mut self, _: &mut Self::WorkerState, info: &IterInfo) -> BenchResult<IterReport> {
         StatelessBenchSuite::bench(self, info).await
     }
+
+    fn error_classifier(&self) -> Option<ErrorClassifier> {
+        StatelessBenchSuite::error_classifier(self)
+    }
+}
+
+/// A trait for benchmark suites with synchronous iterations, e.g. CPU-bound work or blocking clients.
+///
+/// Wrap the suite in [`Blocking`] to run it. Each worker runs its iterations one after another on the blocking
+/// thread pool of tokio, so up to `concurrency` threads are busy at a time while the async runtime stays free.
+pub trait BlockingBenchSuite: Clone + Send + Sync + 'static {
+    /// The state for each worker during the benchmark.
+    type WorkerState: Send + Sync + 'static;
+
+    /// Check the target once before the benchmark starts, see [`BenchSuite::preflight`].
+    fn preflight(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Initialize the state for a worker.
+    fn state(&self, worker_id: u32) -> Result<Self::WorkerState>;
+
+    /// Run a single iteration of the benchmark.
+    fn bench(&mut self, state: &mut Self::WorkerState, info: &IterInfo) -> BenchResult<IterReport>;
+
+    /// Teardown procedure after each worker finishes.
+    #[allow(unused_variables)]
+    fn teardown(self, state: Self::WorkerState, info: IterInfo) -> Result<()> {
+        Ok(())
+    }
+
+    /// Classifier of the errors returned by [`bench`](Self::bench), see [`BenchSuite::error_classifier`].
+    fn error_classifier(&self) -> Option<ErrorClassifier> {
+        None
+    }
+}
+
+/// Adapts a [`BlockingBenchSuite`] into a [`BenchSuite`] which runs every call on the blocking thread pool.
+#[derive(Clone)]
+pub struct Blocking<S>(pub S);
+
+#[async_trait]
+impl<S: BlockingBenchSuite> BenchSuite for Blocking<S> {
+    /// The suite of the worker travels with its state to the blocking thread and back. It is lost if an
+    /// iteration panics or is cancelled, which fails the later iterations of the worker.
+    type WorkerState = Option<(S, S::WorkerState)>;
+
+    async fn preflight(&self) -> Result<()> {
+        let suite = self.0.clone();
+        tokio::task::spawn_blocking(move || suite.preflight()).await?
+    }
+
+    async fn state(&self, worker_id: u32) -> Result<Self::WorkerState> {
+        let suite = self.0.clone();
+        tokio::task::spawn_blocking(move || {
+            let state = suite.state(worker_id)?;
+            Ok(Some((suite, state)))
+        })
+        .await?
+    }
+
+    async fn bench(&mut self, state: &mut Self::WorkerState, info: &IterInfo) -> BenchResult<IterReport> {
+        let (mut suite, mut inner) = state.take().ok_or_else(|| anyhow::anyhow!("worker state was lost"))?;
+        let info = info.clone();
+        let (suite, inner, result) = tokio::task::spawn_blocking(move || {
+            let result = suite.bench(&mut inner, &info);
+            (suite, inner, result)
+        })
+        .await?;
+        *state = Some((suite, inner));
+        result
+    }
+
+    async fn teardown(self, state: Self::WorkerState, info: IterInfo) -> Result<()> {
+        match state {
+            Some((suite, state)) => tokio::task::spawn_blocking(move || suite.teardown(state, info)).await?,
+            None => Ok(()),
+        }
+    }
+
+    fn error_classifier(&self) -> Option<ErrorClassifier> {
+        self.0.error_classifier()
+    }
+}
+
+/// A trait for benchmark suites which are shared by all the workers instead of cloned for each one.
+///
+/// Unlike [`BenchSuite::bench`], the iterations only borrow the suite immutably, so it needs neither `Clone` nor
+/// `&mut self`. Everything mutable lives in the state of the workers, while the suite can hold what the workers
+/// share, e.g. a single connection pool. Wrap the suite in [`Shared`] to run it.
+///
+/// To migrate a [`BenchSuite`], move the fields its iterations mutate into [`WorkerState`](Self::WorkerState),
+/// take `&self` in place of `&mut self` and `self` in the methods, and run `Shared::new(suite)` in place of the
+/// suite. Suites implementing [`BenchSuite`] keep working as they are.
+#[async_trait]
+#[allow(clippy::double_must_use)]
+pub trait SharedBenchSuite: Send + Sync + 'static {
+    /// The state for each worker during the benchmark.
+    type WorkerState: Send;
+
+    /// Check the target once before the benchmark starts, see [`BenchSuite::preflight`].
+    async fn preflight(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Initialize the state for a worker.
+    async fn state(&self, worker_id: u32) -> Result<Self::WorkerState>;
+
+    /// Run a single iteration of the benchmark.
+    async fn bench(&self, state: &mut Self::WorkerState, info: &IterInfo) -> BenchResult<IterReport>;
+
+    /// Setup procedure before each worker starts.
+    #[allow(unused_variables)]
+    async fn setup(&self, state: &mut Self::WorkerState, worker_id: u32) -> Result<()> {
+        Ok(())
+    }
+
+    /// Teardown procedure after each worker finishes.
+    #[allow(unused_variables)]
+    async fn teardown(&self, state: Self::WorkerState, info: IterInfo) -> Result<()> {
+        Ok(())
+    }
+
+    /// Classifier of the errors returned by [`bench`](Self::bench), see [`BenchSuite::error_classifier`].
+    fn error_classifier(&self) -> Option<ErrorClassifier> {
+        None
+    }
+}
+
+/// Adapts a [`SharedBenchSuite`] into a [`BenchSuite`] whose workers share the suite behind an [`Arc`].
+pub struct Shared<S>(pub Arc<S>);
+
+impl<S> Shared<S> {
+    /// Share the suite among the workers.
+    pub fn new(suite: S) -> Self {
+        Self(Arc::new(suite))
+    }
+}
+
+impl<S> Clone for Shared<S> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+#[async_trait]
+impl<S: SharedBenchSuite> BenchSuite for Shared<S> {
+    type WorkerState = S::WorkerState;
+
+    async fn preflight(&self) -> Result<()> {
+        self.0.preflight().await
+    }
+
+    async fn state(&self, worker_id: u32) -> Result<Self::WorkerState> {
+        self.0.state(worker_id).await
+    }
+
+    async fn bench(&mut self, state: &mut Self::WorkerState, info: &IterInfo) -> BenchResult<IterReport> {
+        self.0.bench(state, info).await
+    }
+
+    async fn setup(&mut self, state: &mut Self::WorkerState, worker_id: u32) -> Result<()> {
+        self.0.setup(state, worker_id).await
+    }
+
+    async fn teardown(self, state: Self::WorkerState, info: IterInfo) -> Result<()> {
+        self.0.teardown(state, info).await
+    }
+
+    fn error_classifier(&self) -> Option<ErrorClassifier> {
+        self.0.error_classifier()
+    }
 }
 
 /// A Benchmark runner with a given benchmark suite and control options.
+///
+/// This is the low-level API beneath [`BenchBuilder`](crate::BenchBuilder) and [`cli::run`](crate::cli::run),
+/// for embedding the benchmark into other tools. The runner sends the result of every iteration to a channel,
+/// and it is up to the caller to collect them, e.g. with a [`ReportCollector`](crate::collector::ReportCollector)
+/// of its own. [`BenchOpts`], [`Clock`], [`PauseControl`] and the signatures of the runner are kept stable
+/// across minor versions.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use async_trait::async_trait;
+/// use rlt::{BenchOpts, BenchResult, IterInfo, IterReport, PauseControl, Runner, StatelessBenchSuite, Status};
+/// use tokio::{sync::mpsc, time::Duration};
+/// use tokio_util::sync::CancellationToken;
+///
+/// #[derive(Clone)]
+/// struct NoopBench;
+///
+/// #[async_trait]
+/// impl StatelessBenchSuite for NoopBench {
+///     async fn bench(&mut self, _: &IterInfo) -> BenchResult<IterReport> {
+///         Ok(IterReport::new(Duration::from_millis(1), Status::success(0)))
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> anyhow::Result<()> {
+/// let mut opts = BenchOpts::default();
+/// opts.concurrency = 4;
+/// opts.iterations = Some(100);
+/// let pause = Arc::new(PauseControl::new(opts.clock.clone()));
+///
+/// let (res_tx, mut res_rx) = mpsc::unbounded_channel();
+/// let runner = Runner::new(NoopBench, opts, res_tx, pause, CancellationToken::new());
+///
+/// // a custom collector, which counts the items; the channel closes once the runner finishes
+/// let collector = tokio::spawn(async move {
+///     let mut items = 0;
+///     while let Some(res) = res_rx.recv().await {
+///         items += res.result.map(|r| r.items).unwrap_or_default();
+///     }
+///     items
+/// });
+///
+/// runner.run().await?;
+/// assert_eq!(collector.await?, 100);
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Clone)]
 pub struct Runner<BS>
 where
@@ -105,18 +620,57 @@ where
 {
     suite: BS,
     opts: BenchOpts,
-    res_tx: mpsc::UnboundedSender<Result<IterReport>>,
+    res_tx: mpsc::UnboundedSender<IterResult>,
     pause: watch::Receiver<bool>,
     cancel: CancellationToken,
     seq: Arc<AtomicU64>,
+    phase: Arc<watch::Sender<BenchPhase>>,
+    /// The wall-clock time at which the phase moved to [`BenchPhase::Running`].
+    started_at: Arc<OnceLock<SystemTime>>,
+    setup: Arc<watch::Sender<SetupStats>>,
+    in_flight: InFlight,
+}
+
+/// A gauge of the workers of a [`Runner`] which are inside [`BenchSuite::bench`].
+///
+/// The other workers are setting up, paused, waiting on the rate limiter, or done.
+#[derive(Clone, Debug, Default)]
+pub struct InFlight(Arc<AtomicU32>);
+
+impl InFlight {
+    /// The number of iterations in flight.
+    pub fn get(&self) -> u32 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Count an iteration in flight until the guard is dropped, also when the iteration is cancelled.
+    fn enter(&self) -> InFlightGuard<'_> {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        InFlightGuard(self)
+    }
+}
+
+struct InFlightGuard<'a>(&'a InFlight);
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.0 .0.fetch_sub(1, Ordering::Relaxed);
+    }
 }
 
 /// Information about the current iteration.
+///
+/// More fields may be added in the future, so construct it with [`IterInfo::new`].
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct IterInfo {
-    /// The id of the current worker.
+    /// The id of the current worker, from `0` to `total_workers - 1`.
     pub worker_id: u32,
 
+    /// The number of workers of the runner, i.e. [`BenchOpts::concurrency`]. Useful to partition a keyspace
+    /// across the workers.
+    pub total_workers: u32,
+
     /// The iteration sequence number of the current worker.
     pub worker_seq: u64,
 
@@ -125,44 +679,97 @@ pub struct IterInfo {
 }
 
 impl IterInfo {
-    /// Create a new iteration info for the given worker id.
-    pub fn new(worker_id: u32) -> Self {
-        Self { worker_id, worker_seq: 0, runner_seq: 0 }
+    /// Create a new iteration info for the given worker id out of the given number of workers.
+    pub fn new(worker_id: u32, total_workers: u32) -> Self {
+        Self { worker_id, total_workers, worker_seq: 0, runner_seq: 0 }
     }
 }
 
+/// A step of the SplitMix64 generator, which turns nearby inputs into unrelated outputs.
+pub(crate) fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
 impl<BS> Runner<BS>
 where
     BS: BenchSuite + Send + Sync + 'static,
     BS::WorkerState: Send + Sync + 'static,
 {
     /// Create a new benchmark runner with the given benchmark suite and options.
+    ///
+    /// The result of every iteration is sent to `res_tx`. The workers wait while `pause` is paused, and stop
+    /// when `cancel` is cancelled, which the runner also does once the duration of the benchmark is reached.
     pub fn new(
         suite: BS,
         opts: BenchOpts,
-        res_tx: mpsc::UnboundedSender<Result<IterReport>>,
-        pause: watch::Receiver<bool>,
+        res_tx: mpsc::UnboundedSender<IterResult>,
+        pause: Arc<PauseControl>,
         cancel: CancellationToken,
     ) -> Self {
-        Self { suite, opts, res_tx, pause, cancel, seq: Arc::default() }
+        Self {
+            suite,
+            opts,
+            res_tx,
+            pause: pause.subscribe(),
+            cancel,
+            seq: Arc::default(),
+            phase: Arc::new(watch::channel(BenchPhase::Setup).0),
+            started_at: Arc::default(),
+            setup: Arc::new(watch::channel(SetupStats::default()).0),
+            in_flight: InFlight::default(),
+        }
+    }
+
+    /// Watch the phase of the run, which moves to [`BenchPhase::Running`] once every worker finished its setup.
+    pub fn phase(&self) -> watch::Receiver<BenchPhase> {
+        self.phase.subscribe()
+    }
+
+    /// The wall-clock time at which the phase moves to [`BenchPhase::Running`], set once it does.
+    pub(crate) fn started_at(&self) -> Arc<OnceLock<SystemTime>> {
+        self.started_at.clone()
+    }
+
+    /// Watch how long the workers took to create their states and run [`BenchSuite::setup`], updated as each
+    /// of them finishes.
+    pub fn setup(&self) -> watch::Receiver<SetupStats> {
+        self.setup.subscribe()
+    }
+
+    /// The gauge of the iterations in flight, out of [`BenchOpts::concurrency`].
+    pub fn in_flight(&self) -> InFlight {
+        self.in_flight.clone()
     }
 
     async fn iteration(&mut self, state: &mut BS::WorkerState, info: &IterInfo) {
         self.wait_if_paused().await;
-        let res = self.suite.bench(state, info).await;
+        let start = self.opts.clock.elapsed();
+        let result = {
+            let _guard = self.in_flight.enter();
+            self.suite.bench(state, info).await
+        };
 
         #[cfg(feature = "tracing")]
-        if let Err(e) = &res {
-            log::error!("Error in iteration({info:?}): {:?}", e);
+        if let Err(e) = &result {
+            log::error!("Error in iteration({info:?}): {:?}", e.source);
         }
         // safe to ignore the error which means the receiver is dropped
-        let _ = self.res_tx.send(res);
+        let _ = self.res_tx.send(IterResult { info: info.clone(), start, result });
     }
 
     /// Run the benchmark.
     pub async fn run(self) -> Result<()> {
         let concurrency = self.opts.concurrency;
         let iterations = self.opts.iterations;
+        let seed = self.opts.seed.unwrap_or_else(|| {
+            let now = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default();
+            now.as_nanos() as u64
+        });
 
         #[cfg(feature = "rate_limit")]
         let buckets = self.opts.rate.map(|r| {
@@ -177,11 +784,36 @@ where
             let buckets = buckets.clone();
             let mut b = self.clone();
             set.spawn(async move {
-                let mut state = b.suite.state(worker).await?;
-                let mut info = IterInfo::new(worker);
+                let started = Instant::now();
+                let mut state = b
+                    .suite
+                    .state(worker)
+                    .await
+                    .with_context(|| format!("worker {worker} failed to create its state"))?;
+                let mut info = IterInfo::new(worker, concurrency);
                 let cancel = b.cancel.clone();
 
-                b.suite.setup(&mut state, worker).await?;
+                b.suite
+                    .setup(&mut state, worker)
+                    .await
+                    .with_context(|| format!("worker {worker} failed to set up"))?;
+                let setup = started.elapsed();
+                b.setup.send_modify(|s| {
+                    s.record(setup);
+                    if s.workers == concurrency {
+                        let _ = b.started_at.set(SystemTime::now());
+                        b.phase.send_replace(BenchPhase::Running);
+                    }
+                });
+                if let Some(jitter) = b.opts.start_jitter {
+                    // uniform in [0, 1) from the top 53 bits
+                    let r = (splitmix64(seed ^ worker as u64) >> 11) as f64 / (1u64 << 53) as f64;
+                    select! {
+                        biased;
+                        _ = cancel.cancelled() => (),
+                        _ = b.opts.clock.sleep(jitter.mul_f64(r)) => (),
+                    }
+                }
                 loop {
                     info.runner_seq = b.seq.fetch_add(1, Ordering::Relaxed);
                     if let Some(iterations) = iterations {
@@ -190,12 +822,16 @@ where
                         }
                     }
 
+                    // wait for the pause first, so a permit taken before the pause is not spent after it
                     #[cfg(feature = "rate_limit")]
                     if let Some(buckets) = &buckets {
                         select! {
                             biased;
                             _ = cancel.cancelled() => break,
-                            _ = buckets.until_ready() => (),
+                            _ = async {
+                                b.wait_if_paused().await;
+                                buckets.until_ready().await
+                            } => (),
                         }
                     }
 
@@ -217,7 +853,8 @@ where
                 biased;
                 _ = self.cancel.cancelled() => (),
                 _ = self.opts.clock.sleep(t) => self.cancel.cancel(),
-                _ = join_all(&mut set) => (),
+                // a failed worker fails the run, the others are aborted with the set
+                res = join_all(&mut set) => return res,
             }
         };
 
@@ -239,3 +876,158 @@ async fn join_all(set: &mut JoinSet<Result<()>>) -> Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::status::Status;
+
+    /// A suite whose iterations take the given time on the clock.
+    #[derive(Clone)]
+    struct SleepBench(Clock, Duration);
+
+    #[async_trait]
+    impl StatelessBenchSuite for SleepBench {
+        async fn bench(&mut self, _: &IterInfo) -> BenchResult<IterReport> {
+            self.0.sleep(self.1).await;
+            Ok(IterReport::new(self.1, Status::success(0)))
+        }
+    }
+
+    fn manual_opts() -> BenchOpts {
+        BenchOpts { clock: Clock::manual(), ..Default::default() }
+    }
+
+    /// Yield to the workers until the given number of iterations are in flight.
+    async fn wait_in_flight(in_flight: &InFlight, n: u32) {
+        while in_flight.get() != n {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn stops_once_the_duration_elapsed() {
+        let opts = BenchOpts { duration: Some(Duration::from_secs(1)), ..manual_opts() };
+        let clock = opts.clock.clone();
+        let pause = Arc::new(PauseControl::new(clock.clone()));
+        let (res_tx, mut res_rx) = mpsc::unbounded_channel();
+        let suite = SleepBench(clock.clone(), Duration::from_millis(100));
+        let runner = Runner::new(suite, opts, res_tx, pause, CancellationToken::new());
+        let in_flight = runner.in_flight();
+        let run = tokio::spawn(runner.run());
+
+        for _ in 0..9 {
+            wait_in_flight(&in_flight, 1).await;
+            clock.advance(Duration::from_millis(100));
+            assert!(res_rx.recv().await.unwrap().result.is_ok());
+        }
+        wait_in_flight(&in_flight, 1).await;
+        assert!(!run.is_finished());
+
+        clock.advance(Duration::from_millis(100));
+        run.await.unwrap().unwrap();
+        assert_eq!(clock.elapsed(), Duration::from_secs(1));
+    }
+
+    /// A suite whose workers finish their setup once notified.
+    #[derive(Clone)]
+    struct GatedSetup(Arc<tokio::sync::Notify>);
+
+    #[async_trait]
+    impl BenchSuite for GatedSetup {
+        type WorkerState = ();
+
+        async fn state(&self, _: u32) -> Result<()> {
+            Ok(())
+        }
+
+        async fn setup(&mut self, _: &mut (), _: u32) -> Result<()> {
+            self.0.notified().await;
+            Ok(())
+        }
+
+        async fn bench(&mut self, _: &mut (), _: &IterInfo) -> BenchResult<IterReport> {
+            Ok(IterReport::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn starts_once_the_setup_is_done() {
+        let opts = BenchOpts { iterations: Some(1), ..manual_opts() };
+        let gate = Arc::new(tokio::sync::Notify::new());
+        let pause = Arc::new(PauseControl::new(opts.clock.clone()));
+        let (res_tx, _res_rx) = mpsc::unbounded_channel();
+        let runner = Runner::new(GatedSetup(gate.clone()), opts, res_tx, pause, CancellationToken::new());
+        let (mut phase, started_at) = (runner.phase(), runner.started_at());
+        let run = tokio::spawn(runner.run());
+
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(*phase.borrow(), BenchPhase::Setup);
+        assert!(started_at.get().is_none());
+
+        let before = SystemTime::now();
+        gate.notify_one();
+        phase.wait_for(|p| *p == BenchPhase::Running).await.unwrap();
+        assert!(started_at.get().is_some_and(|&t| t >= before && t <= SystemTime::now()));
+        run.await.unwrap().unwrap();
+    }
+
+    #[cfg(feature = "rate_limit")]
+    mod rate_limit {
+        use super::*;
+
+        /// A suite whose iterations finish at once.
+        #[derive(Clone)]
+        struct NoopBench;
+
+        #[async_trait]
+        impl StatelessBenchSuite for NoopBench {
+            async fn bench(&mut self, _: &IterInfo) -> BenchResult<IterReport> {
+                Ok(IterReport::default())
+            }
+        }
+
+        /// Count the results which arrive within the given wall time. The rate limiter waits on the wall time for
+        /// its next permit, while the permits follow the clock of the benchmark.
+        async fn results_within(res_rx: &mut mpsc::UnboundedReceiver<IterResult>, wall: Duration) -> usize {
+            tokio::time::sleep(wall).await;
+            std::iter::from_fn(|| res_rx.try_recv().ok()).count()
+        }
+
+        #[tokio::test]
+        async fn no_burst_after_a_pause() {
+            let opts = BenchOpts { concurrency: 4, rate: Some(nonzero!(10u32)), ..manual_opts() };
+            let clock = opts.clock.clone();
+            let pause = Arc::new(PauseControl::new(clock.clone()));
+            let (res_tx, mut res_rx) = mpsc::unbounded_channel();
+            let cancel = CancellationToken::new();
+            let runner = Runner::new(NoopBench, opts, res_tx, pause.clone(), cancel.clone());
+            let run = tokio::spawn(runner.run());
+            let wall = Duration::from_millis(300);
+
+            // a permit at the start and one per 100ms of the clock
+            assert!(res_rx.recv().await.is_some());
+            assert_eq!(results_within(&mut res_rx, wall).await, 0);
+            clock.advance(Duration::from_millis(100));
+            assert_eq!(results_within(&mut res_rx, wall).await, 1);
+
+            // pause with a permit due, no more permits accrue while paused, however long the pause
+            clock.advance(Duration::from_millis(100));
+            pause.pause();
+            clock.advance(Duration::from_secs(10));
+            assert_eq!(results_within(&mut res_rx, wall).await, 0);
+
+            // the rate resumes where it stopped, with at most the burst size of 1 at once
+            pause.resume();
+            let burst = results_within(&mut res_rx, wall).await;
+            assert!(burst <= 1, "{burst} iterations right after the resume");
+            clock.advance(Duration::from_millis(100));
+            assert_eq!(burst + results_within(&mut res_rx, wall).await, 2);
+
+            cancel.cancel();
+            run.await.unwrap().unwrap();
+        }
+    }
+}
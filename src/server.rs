@@ -0,0 +1,66 @@
+//! A minimal HTTP server for exposing runtime endpoints during the benchmark.
+use std::{convert::Infallible, sync::Arc};
+
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::{body::Incoming, server::conn::http1, service::service_fn, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use tokio::{net::TcpListener, task::JoinSet};
+use tokio_util::sync::CancellationToken;
+
+pub(crate) type HttpResponse = Response<Full<Bytes>>;
+
+/// Serve HTTP requests from the listener with the given handler until cancelled.
+///
+/// In-flight connections are shut down gracefully once the cancellation token is triggered.
+pub(crate) async fn serve<H>(listener: TcpListener, cancel: CancellationToken, handler: H)
+where
+    H: Fn(Request<Incoming>) -> HttpResponse + Send + Sync + 'static,
+{
+    let handler = Arc::new(handler);
+    let mut conns = JoinSet::new();
+    loop {
+        let stream = tokio::select! {
+            biased;
+            _ = cancel.cancelled() => break,
+            r = listener.accept() => match r {
+                Ok((stream, _)) => stream,
+                Err(_) => continue,
+            },
+        };
+
+        let handler = handler.clone();
+        let cancel = cancel.clone();
+        conns.spawn(async move {
+            let svc = service_fn(move |req| {
+                let handler = handler.clone();
+                async move { Ok::<_, Infallible>(handler(req)) }
+            });
+            let conn = http1::Builder::new().serve_connection(TokioIo::new(stream), svc);
+            tokio::pin!(conn);
+            tokio::select! {
+                _ = conn.as_mut() => (),
+                _ = cancel.cancelled() => {
+                    conn.as_mut().graceful_shutdown();
+                    let _ = conn.await;
+                }
+            }
+        });
+    }
+    while conns.join_next().await.is_some() {}
+}
+
+/// Build a response with the given status code, content type and body.
+pub(crate) fn response(status: StatusCode, content_type: &str, body: impl Into<Bytes>) -> HttpResponse {
+    let mut resp = Response::new(Full::new(body.into()));
+    *resp.status_mut() = status;
+    if let Ok(v) = content_type.parse() {
+        resp.headers_mut().insert(hyper::header::CONTENT_TYPE, v);
+    }
+    resp
+}
+
+/// Build a `404 Not Found` response.
+pub(crate) fn not_found() -> HttpResponse {
+    response(StatusCode::NOT_FOUND, "text/plain", "not found\n")
+}
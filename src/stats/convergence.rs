@@ -0,0 +1,145 @@
+use tokio::time::Duration;
+
+use crate::{histogram::LatencyHistogram, runner::StableOpts};
+
+/// Detects the steady state of a run by comparing the p99 latency and the iteration rate of consecutive
+/// windows of the elapsed time.
+///
+/// The first window only serves as the reference of the second one, so the run converges after
+/// [`StableOpts::windows`] comparisons at the earliest, which keeps the warmup from counting as stable.
+#[derive(Clone)]
+pub struct Convergence {
+    opts: StableOpts,
+    /// Start of the current window.
+    origin: Duration,
+    /// Latencies of the current window.
+    hist: LatencyHistogram,
+    /// Iterations of the current window.
+    iters: u64,
+    /// The p99 latency and iteration rate of the previous window.
+    prev: Option<(Duration, f64)>,
+    /// Number of consecutive windows within the tolerance of their previous one.
+    stable: u32,
+}
+
+impl Convergence {
+    /// Create the checker with the windows aligned to the given elapsed time.
+    pub fn new(opts: StableOpts, origin: Duration) -> Self {
+        Self {
+            opts,
+            origin,
+            hist: LatencyHistogram::new(),
+            iters: 0,
+            prev: None,
+            stable: 0,
+        }
+    }
+
+    /// Account an iteration of the given latency which finished at the given elapsed time.
+    pub fn record(&mut self, latency: Duration, elapsed: Duration) {
+        self.advance(elapsed);
+        self.hist.record(latency);
+        self.iters += 1;
+    }
+
+    /// Close the windows which ended before the given elapsed time.
+    fn advance(&mut self, elapsed: Duration) {
+        let window = self.opts.window;
+        while elapsed >= self.origin + window {
+            let p99 = self.hist.percentile(99.0);
+            let rate = self.iters as f64 / window.as_secs_f64();
+            self.stable = match self.prev {
+                Some((prev_p99, prev_rate))
+                    if within(p99.as_secs_f64(), prev_p99.as_secs_f64(), self.opts.tolerance)
+                        && within(rate, prev_rate, self.opts.tolerance) =>
+                {
+                    self.stable + 1
+                }
+                _ => 0,
+            };
+            self.prev = Some((p99, rate));
+            self.hist = LatencyHistogram::new();
+            self.iters = 0;
+            self.origin += window;
+        }
+    }
+
+    /// The options of the checker.
+    pub fn opts(&self) -> StableOpts {
+        self.opts
+    }
+
+    /// Whether the last [`StableOpts::windows`] windows were all within the tolerance of their previous one.
+    pub fn converged(&self) -> bool {
+        self.stable >= self.opts.windows
+    }
+}
+
+/// Whether `value` deviates from `reference` by at most the given fraction of it.
+fn within(value: f64, reference: f64, tolerance: f64) -> bool {
+    (value - reference).abs() <= tolerance * reference
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opts() -> StableOpts {
+        StableOpts { tolerance: 0.05, windows: 3, window: Duration::from_secs(1) }
+    }
+
+    /// Record `rate` iterations per second during the given second, of the latency of that second.
+    fn record_second(conv: &mut Convergence, sec: u64, rate: u64, latency: Duration) {
+        for i in 0..rate {
+            conv.record(
+                latency,
+                Duration::from_secs(sec) + Duration::from_secs(1) * i as u32 / rate as u32,
+            );
+        }
+    }
+
+    #[test]
+    fn converges_on_a_stable_series() {
+        let mut conv = Convergence::new(opts(), Duration::ZERO);
+        for sec in 0..4 {
+            record_second(&mut conv, sec, 100, Duration::from_millis(10));
+            assert!(!conv.converged(), "converged during second {sec}");
+        }
+        // the fourth window closes with the first iteration of the fifth
+        record_second(&mut conv, 4, 100, Duration::from_millis(10));
+        assert!(conv.converged());
+    }
+
+    #[test]
+    fn does_not_converge_on_a_noisy_series() {
+        let mut conv = Convergence::new(opts(), Duration::ZERO);
+        for sec in 0..20 {
+            let latency = Duration::from_millis(if sec % 2 == 0 { 10 } else { 15 });
+            record_second(&mut conv, sec, 100, latency);
+            assert!(!conv.converged(), "converged during second {sec}");
+        }
+
+        let mut conv = Convergence::new(opts(), Duration::ZERO);
+        for sec in 0..20 {
+            record_second(&mut conv, sec, 100 + sec % 3 * 10, Duration::from_millis(10));
+            assert!(!conv.converged(), "converged during second {sec}");
+        }
+    }
+
+    #[test]
+    fn a_change_restarts_the_stable_windows() {
+        let mut conv = Convergence::new(opts(), Duration::from_secs(30));
+        for sec in 30..35 {
+            record_second(&mut conv, sec, 100, Duration::from_millis(10));
+        }
+        assert!(conv.converged());
+
+        record_second(&mut conv, 35, 100, Duration::from_millis(20));
+        for sec in 36..39 {
+            record_second(&mut conv, sec, 100, Duration::from_millis(20));
+            assert!(!conv.converged(), "converged during second {sec}");
+        }
+        record_second(&mut conv, 39, 100, Duration::from_millis(20));
+        assert!(conv.converged());
+    }
+}
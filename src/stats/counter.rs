@@ -6,24 +6,156 @@ use crate::report::IterReport;
 pub struct Counter {
     pub iters: u64,
     pub items: u64,
+    /// Total bytes, including the received and sent ones.
     pub bytes: u64,
+    pub bytes_in: u64,
+    pub bytes_out: u64,
     pub duration: Duration,
+    /// Number of iterations which returned an error instead of a report, which are not part of `iters`.
+    pub errors: u64,
+}
+
+impl Counter {
+    /// Whether any bytes were reported with a direction.
+    pub fn has_directions(&self) -> bool {
+        self.bytes_in > 0 || self.bytes_out > 0
+    }
 }
 
 impl std::ops::AddAssign<&IterReport> for Counter {
     fn add_assign(&mut self, stats: &IterReport) {
         self.iters += 1;
         self.items += stats.items;
-        self.bytes += stats.bytes;
+        self.bytes += stats.total_bytes();
+        self.bytes_in += stats.bytes_in;
+        self.bytes_out += stats.bytes_out;
         self.duration += stats.duration;
     }
 }
 
+impl std::ops::AddAssign<&Counter> for Counter {
+    fn add_assign(&mut self, rhs: &Counter) {
+        self.iters += rhs.iters;
+        self.items += rhs.items;
+        self.bytes += rhs.bytes;
+        self.bytes_in += rhs.bytes_in;
+        self.bytes_out += rhs.bytes_out;
+        self.duration += rhs.duration;
+        self.errors += rhs.errors;
+    }
+}
+
+/// Subtraction saturates at zero, since a later snapshot of a counter is never expected to be smaller.
 impl std::ops::SubAssign<&Counter> for Counter {
     fn sub_assign(&mut self, rhs: &Counter) {
-        self.iters -= rhs.iters;
-        self.items -= rhs.items;
-        self.bytes -= rhs.bytes;
-        self.duration -= rhs.duration;
+        self.iters = self.iters.saturating_sub(rhs.iters);
+        self.items = self.items.saturating_sub(rhs.items);
+        self.bytes = self.bytes.saturating_sub(rhs.bytes);
+        self.bytes_in = self.bytes_in.saturating_sub(rhs.bytes_in);
+        self.bytes_out = self.bytes_out.saturating_sub(rhs.bytes_out);
+        self.duration = self.duration.saturating_sub(rhs.duration);
+        self.errors = self.errors.saturating_sub(rhs.errors);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::splitmix64;
+
+    fn fields(c: &Counter) -> [u64; 7] {
+        [
+            c.iters,
+            c.items,
+            c.bytes,
+            c.bytes_in,
+            c.bytes_out,
+            c.duration.as_nanos() as u64,
+            c.errors,
+        ]
+    }
+
+    /// A counter with fields below `max`, drawn from the given seed.
+    fn random_counter(seed: &mut u64, max: u64) -> Counter {
+        let mut next = || {
+            *seed = splitmix64(*seed);
+            *seed % max
+        };
+        Counter {
+            iters: next(),
+            items: next(),
+            bytes: next(),
+            bytes_in: next(),
+            bytes_out: next(),
+            duration: Duration::from_nanos(next()),
+            errors: next(),
+        }
+    }
+
+    #[test]
+    fn sub_saturates_at_zero() {
+        let mut c = Counter {
+            iters: 1,
+            duration: Duration::from_secs(1),
+            ..Default::default()
+        };
+        c -= &Counter {
+            iters: 2,
+            items: 3,
+            duration: Duration::from_secs(2),
+            ..Default::default()
+        };
+        assert_eq!(fields(&c), [0; 7]);
+    }
+
+    #[test]
+    fn random_sub_never_underflows() {
+        let mut seed = 1;
+        for _ in 0..1000 {
+            let mut a = random_counter(&mut seed, 100);
+            let b = random_counter(&mut seed, 100);
+            let before = fields(&a);
+            a -= &b;
+            for ((after, before), b) in fields(&a).into_iter().zip(before).zip(fields(&b)) {
+                assert_eq!(after, before.saturating_sub(b));
+            }
+        }
+    }
+
+    #[test]
+    fn sub_then_add_restores_a_larger_counter() {
+        let mut seed = 2;
+        for _ in 0..1000 {
+            let b = random_counter(&mut seed, 1 << 20);
+            let mut a = random_counter(&mut seed, 1 << 20);
+            a += &b;
+            let expected = fields(&a);
+            a -= &b;
+            a += &b;
+            assert_eq!(fields(&a), expected);
+        }
+    }
+
+    #[test]
+    fn random_add_sub_sequences_stay_in_range() {
+        let mut seed = 3;
+        for _ in 0..100 {
+            let mut c = Counter::default();
+            let mut added = Counter::default();
+            for _ in 0..50 {
+                let step = random_counter(&mut seed, 1000);
+                seed = splitmix64(seed);
+                if seed % 2 == 0 {
+                    c += &step;
+                    added += &step;
+                } else {
+                    c -= &step;
+                }
+                // never wrapped around: no field exceeds the sum of everything added
+                for (c, added) in fields(&c).into_iter().zip(fields(&added)) {
+                    assert!(c <= added);
+                }
+            }
+        }
     }
 }
@@ -1,12 +1,42 @@
+mod convergence;
 mod counter;
+mod rate;
+mod saturation;
+mod slowest;
+mod timeseries;
 mod window;
 
+pub use convergence::Convergence;
 pub use counter::Counter;
-pub use window::{RotateDiffWindowGroup, RotateWindowGroup};
+pub use rate::RateStats;
+pub use saturation::SaturationDetector;
+pub use slowest::Slowest;
+pub use timeseries::TimeSeries;
+pub use window::{LatencyWindowGroup, RotateDiffWindowGroup, RotateWindow, RotateWindowGroup};
 
 use std::collections::HashMap;
 
-use crate::{report::IterReport, status::Status};
+use crate::{
+    report::{IterReport, IterResult},
+    status::Status,
+};
+
+/// The outcome of an iteration, as counted by the statistics.
+#[derive(Clone, Copy, Debug)]
+pub enum IterOutcome<'a> {
+    Report(&'a IterReport),
+    /// An iteration which returned an error.
+    Error,
+}
+
+impl<'a> From<&'a IterResult> for IterOutcome<'a> {
+    fn from(res: &'a IterResult) -> Self {
+        match &res.result {
+            Ok(report) => IterOutcome::Report(report),
+            Err(_) => IterOutcome::Error,
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct IterStats {
@@ -18,6 +48,27 @@ impl IterStats {
     pub fn new() -> Self {
         Self { counter: Counter::default(), details: HashMap::new() }
     }
+
+    /// Total number of iterations, including the failed ones.
+    pub fn total(&self) -> u64 {
+        self.counter.iters + self.counter.errors
+    }
+
+    /// Ratio of the iterations which returned an error or a non-success status.
+    ///
+    /// Redirects count as successes only if `redirect_is_success` is set.
+    pub fn error_ratio(&self, redirect_is_success: bool) -> f64 {
+        if self.total() == 0 {
+            return 0.0;
+        }
+        let non_success: u64 = self
+            .details
+            .iter()
+            .filter(|(k, _)| !k.kind().is_success(redirect_is_success))
+            .map(|(_, v)| v.iters)
+            .sum();
+        (non_success + self.counter.errors) as f64 / self.total() as f64
+    }
 }
 
 impl Default for IterStats {
@@ -34,6 +85,24 @@ impl std::ops::AddAssign<&IterReport> for IterStats {
     }
 }
 
+impl std::ops::AddAssign<IterOutcome<'_>> for IterStats {
+    fn add_assign(&mut self, outcome: IterOutcome<'_>) {
+        match outcome {
+            IterOutcome::Report(report) => *self += report,
+            IterOutcome::Error => self.counter.errors += 1,
+        }
+    }
+}
+
+impl std::ops::AddAssign<&IterStats> for IterStats {
+    fn add_assign(&mut self, rhs: &IterStats) {
+        self.counter += &rhs.counter;
+        for (k, v) in &rhs.details {
+            *self.details.entry(*k).or_default() += v;
+        }
+    }
+}
+
 impl std::ops::Sub<&IterStats> for &IterStats {
     type Output = IterStats;
 
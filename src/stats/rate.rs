@@ -0,0 +1,70 @@
+use tokio::time::Duration;
+
+/// Streaming statistics of the per-second iteration rate.
+///
+/// Iterations are counted into one second buckets of the elapsed time. Every completed bucket is folded into
+/// the mean and variance with Welford's algorithm, so the rate series itself is not retained.
+#[derive(Clone, Debug, Default)]
+pub struct RateStats {
+    /// Start of the elapsed time which the buckets are aligned to.
+    origin: Duration,
+    /// Index of the current bucket.
+    second: u64,
+    /// Iterations counted into the current bucket so far.
+    current: u64,
+    samples: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl RateStats {
+    /// Create the rate statistics with the buckets aligned to the given elapsed time.
+    pub fn new(origin: Duration) -> Self {
+        Self { origin, ..Default::default() }
+    }
+
+    /// Account an iteration which finished at the given elapsed time.
+    pub fn record(&mut self, elapsed: Duration) {
+        self.advance(elapsed);
+        self.current += 1;
+    }
+
+    /// Close the buckets which ended before the given elapsed time.
+    ///
+    /// Seconds without any iteration are accounted as a zero rate.
+    pub fn advance(&mut self, elapsed: Duration) {
+        let second = elapsed.saturating_sub(self.origin).as_secs();
+        while self.second < second {
+            let rate = std::mem::take(&mut self.current) as f64;
+            self.push(rate);
+            self.second += 1;
+        }
+    }
+
+    fn push(&mut self, rate: f64) {
+        self.samples += 1;
+        let delta = rate - self.mean;
+        self.mean += delta / self.samples as f64;
+        self.m2 += delta * (rate - self.mean);
+    }
+
+    /// Number of completed one second samples.
+    pub fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    /// Mean of the per-second iteration rate, if any second completed.
+    pub fn mean(&self) -> Option<f64> {
+        (self.samples > 0).then_some(self.mean)
+    }
+
+    /// Sample standard deviation of the per-second iteration rate, if at least two seconds completed.
+    pub fn stdev(&self) -> Option<f64> {
+        (self.samples > 1).then(|| (self.m2 / (self.samples - 1) as f64).sqrt())
+    }
+
+    /// Coefficient of variation of the per-second iteration rate, i.e. the standard deviation relative to the mean.
+    pub fn cv(&self) -> Option<f64> {
+        self.stdev().filter(|_| self.mean > 0.0).map(|stdev| stdev / self.mean)
+    }
+}
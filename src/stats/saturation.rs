@@ -0,0 +1,212 @@
+use tokio::time::Duration;
+
+use crate::{histogram::LatencyHistogram, report::Saturation};
+
+/// Length of the windows the signs of saturation are checked over.
+const WINDOW: Duration = Duration::from_secs(5);
+/// The achieved rate lags when it stays below this ratio of the rate limit.
+const RATE_LAG_RATIO: f64 = 0.9;
+/// The latency grows when the median reaches this multiple of the one of the first window.
+const LATENCY_GROWTH: f64 = 2.0;
+/// The status is healthy while the share of unsuccessful iterations of a window stays below this ratio.
+const HEALTHY_ERROR_RATIO: f64 = 0.01;
+
+/// Detects the signs of a saturated load generator over consecutive windows of the elapsed time.
+///
+/// With a rate limit, the generator is saturated when a window achieves less than 90% of the limit. Without one,
+/// all the workers are busy by definition, so it is saturated when the median latency of a window doubles the one
+/// of the first window while the iterations keep succeeding.
+///
+/// Once detected, the saturation is kept until the detector is reset, with the numbers of its latest window.
+#[derive(Clone)]
+pub struct SaturationDetector {
+    /// The rate limit of the benchmark, in iterations per second.
+    rate: Option<f64>,
+    /// Start of the current window.
+    origin: Duration,
+    /// Latencies of the current window.
+    hist: LatencyHistogram,
+    /// Iterations of the current window.
+    iters: u64,
+    /// Unsuccessful iterations of the current window.
+    errors: u64,
+    /// Median latency of the first window.
+    baseline: Option<Duration>,
+    saturation: Option<Saturation>,
+}
+
+impl SaturationDetector {
+    /// Create the detector for the given rate limit, with the windows aligned to the given elapsed time.
+    pub fn new(rate: Option<f64>, origin: Duration) -> Self {
+        Self {
+            rate,
+            origin,
+            hist: LatencyHistogram::new(),
+            iters: 0,
+            errors: 0,
+            baseline: None,
+            saturation: None,
+        }
+    }
+
+    /// Account an iteration of the given latency, if any, which finished at the given elapsed time.
+    pub fn record(&mut self, latency: Option<Duration>, success: bool, elapsed: Duration) {
+        self.advance(elapsed);
+        if let Some(latency) = latency {
+            self.hist.record(latency);
+        }
+        self.iters += 1;
+        self.errors += !success as u64;
+    }
+
+    /// Close the windows which ended before the given elapsed time.
+    fn advance(&mut self, elapsed: Duration) {
+        while elapsed >= self.origin + WINDOW {
+            match self.rate {
+                Some(target) => {
+                    let achieved = self.iters as f64 / WINDOW.as_secs_f64();
+                    if achieved < target * RATE_LAG_RATIO {
+                        self.saturation = Some(Saturation::RateLag { achieved, target });
+                    }
+                }
+                None if self.hist.is_empty() => (),
+                None => {
+                    let latest = self.hist.median();
+                    match self.baseline {
+                        None => self.baseline = Some(latest),
+                        Some(baseline) => {
+                            let healthy = (self.errors as f64) < self.iters as f64 * HEALTHY_ERROR_RATIO;
+                            if healthy && latest.as_secs_f64() >= baseline.as_secs_f64() * LATENCY_GROWTH {
+                                self.saturation = Some(Saturation::LatencyGrowth { baseline, latest });
+                            }
+                        }
+                    }
+                }
+            }
+            self.hist = LatencyHistogram::new();
+            self.iters = 0;
+            self.errors = 0;
+            self.origin += WINDOW;
+        }
+    }
+
+    /// The rate limit the detector checks against.
+    pub fn rate(&self) -> Option<f64> {
+        self.rate
+    }
+
+    /// The saturation detected so far, if any.
+    pub fn saturation(&self) -> Option<Saturation> {
+        self.saturation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    /// Feed `ips` evenly spaced iterations per second over the given seconds of the elapsed time.
+    fn feed(detector: &mut SaturationDetector, secs: std::ops::Range<u64>, ips: u64, latency: Duration, success: bool) {
+        for i in secs.start * ips..secs.end * ips {
+            let elapsed = Duration::from_secs(i) / ips as u32;
+            detector.record(Some(latency), success, elapsed);
+        }
+    }
+
+    #[test]
+    fn rate_lagging_behind_the_limit() {
+        let mut detector = SaturationDetector::new(Some(100.0), Duration::ZERO);
+        feed(&mut detector, 0..5, 100, MS, true);
+        feed(&mut detector, 5..11, 80, MS, true);
+        assert_eq!(
+            detector.saturation(),
+            Some(Saturation::RateLag { achieved: 80.0, target: 100.0 })
+        );
+    }
+
+    #[test]
+    fn rate_within_10_percent_of_the_limit() {
+        let mut detector = SaturationDetector::new(Some(100.0), Duration::ZERO);
+        feed(&mut detector, 0..11, 91, MS, true);
+        assert_eq!(detector.saturation(), None);
+    }
+
+    #[test]
+    fn only_closed_windows_are_judged() {
+        let mut detector = SaturationDetector::new(Some(100.0), Duration::ZERO);
+        feed(&mut detector, 0..5, 100, MS, true);
+        // the second window is still open
+        feed(&mut detector, 5..9, 10, MS, true);
+        assert_eq!(detector.saturation(), None);
+    }
+
+    #[test]
+    fn latency_growing_while_healthy() {
+        let mut detector = SaturationDetector::new(None, Duration::ZERO);
+        feed(&mut detector, 0..5, 100, 10 * MS, true);
+        feed(&mut detector, 5..10, 100, 15 * MS, true);
+        feed(&mut detector, 10..16, 100, 25 * MS, true);
+        let Some(Saturation::LatencyGrowth { baseline, latest }) = detector.saturation() else {
+            panic!("no latency growth in {:?}", detector.saturation());
+        };
+        assert!(baseline.abs_diff(10 * MS) < MS / 100);
+        assert!(latest.abs_diff(25 * MS) < MS / 10);
+    }
+
+    #[test]
+    fn latency_growing_with_failures_is_the_target() {
+        let mut detector = SaturationDetector::new(None, Duration::ZERO);
+        feed(&mut detector, 0..5, 100, 10 * MS, true);
+        feed(&mut detector, 5..8, 100, 30 * MS, true);
+        feed(&mut detector, 8..11, 100, 30 * MS, false);
+        assert_eq!(detector.saturation(), None);
+    }
+
+    #[test]
+    fn stable_latency() {
+        let mut detector = SaturationDetector::new(None, Duration::ZERO);
+        feed(&mut detector, 0..5, 100, 10 * MS, true);
+        feed(&mut detector, 5..31, 100, 19 * MS, true);
+        assert_eq!(detector.saturation(), None);
+    }
+
+    #[test]
+    fn saturation_is_kept_once_detected() {
+        let mut detector = SaturationDetector::new(Some(100.0), Duration::ZERO);
+        feed(&mut detector, 0..5, 50, MS, true);
+        feed(&mut detector, 5..21, 100, MS, true);
+        assert_eq!(
+            detector.saturation(),
+            Some(Saturation::RateLag { achieved: 50.0, target: 100.0 })
+        );
+    }
+
+    #[test]
+    fn windows_are_aligned_to_the_origin() {
+        let mut detector = SaturationDetector::new(Some(100.0), Duration::from_secs(60));
+        feed(&mut detector, 60..65, 100, MS, true);
+        // the first window in [60s, 65s) was full, this one in [65s, 70s) lags
+        feed(&mut detector, 65..71, 70, MS, true);
+        assert_eq!(
+            detector.saturation(),
+            Some(Saturation::RateLag { achieved: 70.0, target: 100.0 })
+        );
+    }
+
+    #[test]
+    fn report_note() {
+        let lag = Saturation::RateLag { achieved: 8100.0, target: 10000.0 };
+        assert_eq!(
+            lag.to_string(),
+            "generator may be the bottleneck: achieved 8.1k of 10k ips"
+        );
+        let growth = Saturation::LatencyGrowth { baseline: 10 * MS, latest: 25 * MS };
+        assert_eq!(
+            growth.to_string(),
+            "generator may be the bottleneck: median latency grew from 10.00ms to 25.00ms while iterations kept \
+             succeeding"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::{
+    report::{IterReport, SlowIteration},
+    runner::IterInfo,
+};
+
+/// Keeps the slowest iterations seen so far, up to a given number.
+///
+/// The iterations are kept in a min-heap by duration, so an iteration which is not among the slowest costs a
+/// single comparison.
+#[derive(Clone, Debug, Default)]
+pub struct Slowest {
+    limit: usize,
+    heap: BinaryHeap<Reverse<Entry>>,
+}
+
+/// An iteration ordered by its duration.
+#[derive(Clone, Debug)]
+struct Entry(SlowIteration);
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.duration == other.0.duration
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.duration.cmp(&other.0.duration)
+    }
+}
+
+impl Slowest {
+    /// Create a reservoir of the given number of iterations. Nothing is kept if it is zero.
+    pub fn new(limit: usize) -> Self {
+        Self { limit, heap: BinaryHeap::with_capacity(limit) }
+    }
+
+    /// Forget the iterations kept so far.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+
+    /// Account a successful iteration.
+    pub fn record(&mut self, info: &IterInfo, report: &IterReport) {
+        if self.limit == 0 {
+            return;
+        }
+        if self.heap.len() >= self.limit {
+            match self.heap.peek() {
+                Some(Reverse(min)) if min.0.duration < report.duration => _ = self.heap.pop(),
+                _ => return,
+            }
+        }
+        self.heap.push(Reverse(Entry(SlowIteration {
+            duration: report.duration,
+            worker_id: info.worker_id,
+            worker_seq: info.worker_seq,
+            status: report.status,
+            context: report.context.clone(),
+        })));
+    }
+
+    /// The kept iterations, the slowest first.
+    pub fn sorted(&self) -> Vec<SlowIteration> {
+        let mut entries = self.heap.iter().map(|Reverse(e)| e.0.clone()).collect::<Vec<_>>();
+        entries.sort_by_key(|e| Reverse(e.duration));
+        entries
+    }
+
+    /// Consume the reservoir and return the kept iterations, the slowest first.
+    pub fn into_sorted(self) -> Vec<SlowIteration> {
+        self.heap.into_sorted_vec().into_iter().map(|Reverse(e)| e.0).collect()
+    }
+}
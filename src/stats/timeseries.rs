@@ -0,0 +1,77 @@
+use tokio::time::Duration;
+
+use crate::{
+    histogram::LatencyHistogram,
+    report::{IterReport, SecondSample},
+};
+
+/// Significant digits of the per-second latency histograms, kept low to bound their memory.
+const LATENCY_SIGFIG: u8 = 2;
+
+/// Records the per-second samples of a run.
+///
+/// Only the current second is aggregated in full, while every completed second is kept as a
+/// [`SecondSample`] of less than 100 bytes, so a multi-hour run needs about a megabyte.
+#[derive(Clone)]
+pub struct TimeSeries {
+    /// Start of the elapsed time which the seconds are aligned to.
+    origin: Duration,
+    /// Index of the current second.
+    second: u64,
+    iters: u64,
+    errors: u64,
+    bytes: u64,
+    hist: LatencyHistogram,
+    samples: Vec<SecondSample>,
+}
+
+impl TimeSeries {
+    /// Create the time series with the seconds aligned to the given elapsed time.
+    pub fn new(origin: Duration) -> Self {
+        Self {
+            origin,
+            second: 0,
+            iters: 0,
+            errors: 0,
+            bytes: 0,
+            hist: LatencyHistogram::with_sigfig(LATENCY_SIGFIG),
+            samples: Vec::new(),
+        }
+    }
+
+    /// Account an iteration which finished at the given elapsed time.
+    pub fn record(&mut self, report: &IterReport, elapsed: Duration) {
+        self.advance(elapsed);
+        self.iters += 1;
+        self.bytes += report.bytes + report.bytes_in + report.bytes_out;
+        self.hist.record(report.duration);
+    }
+
+    /// Account a failed iteration which finished at the given elapsed time.
+    pub fn record_error(&mut self, elapsed: Duration) {
+        self.advance(elapsed);
+        self.errors += 1;
+    }
+
+    /// Close the seconds which ended before the given elapsed time.
+    pub fn advance(&mut self, elapsed: Duration) {
+        let second = elapsed.saturating_sub(self.origin).as_secs();
+        while self.second < second {
+            let hist = std::mem::replace(&mut self.hist, LatencyHistogram::with_sigfig(LATENCY_SIGFIG));
+            self.samples.push(SecondSample {
+                elapsed: Duration::from_secs(self.second),
+                iters: std::mem::take(&mut self.iters),
+                errors: std::mem::take(&mut self.errors),
+                bytes: std::mem::take(&mut self.bytes),
+                p50: hist.median(),
+                p99: hist.value_at_quantile(0.99),
+            });
+            self.second += 1;
+        }
+    }
+
+    /// Consume the time series and return the samples of the completed seconds.
+    pub fn into_samples(self) -> Vec<SecondSample> {
+        self.samples
+    }
+}
@@ -3,9 +3,9 @@ use std::{collections::VecDeque, num::NonZeroUsize};
 use nonzero_ext::nonzero;
 use tokio::time::Duration;
 
-use crate::report::IterReport;
+use crate::{histogram::LatencyHistogram, report::IterResult};
 
-use super::IterStats;
+use super::{IterOutcome, IterStats};
 
 pub struct RotateWindow {
     buckets: VecDeque<IterStats>,
@@ -19,9 +19,10 @@ impl RotateWindow {
         win
     }
 
-    fn push(&mut self, item: &IterReport) {
-        // SAFETY: `buckets` is never empty
-        *self.buckets.front_mut().unwrap() += item;
+    /// Add the item to the bucket `age` rotations back, or to the oldest one if the window is shorter.
+    fn push(&mut self, item: IterOutcome<'_>, age: usize) {
+        let idx = age.min(self.buckets.len() - 1);
+        self.buckets[idx] += item;
     }
 
     fn rotate(&mut self, bucket: IterStats) {
@@ -48,10 +49,24 @@ impl RotateWindow {
     pub fn iter(&self) -> impl Iterator<Item = &IterStats> {
         self.buckets.iter()
     }
+
+    /// Iterate over the completed buckets, from the oldest to the latest.
+    pub fn history(&self) -> impl Iterator<Item = &IterStats> {
+        self.buckets.iter().skip(1).rev()
+    }
 }
 
+/// Iteration statistics bucketed by second, 10 seconds, minute and 10 minutes, the current bucket first.
+///
+/// Iterations are counted in the bucket of their [start time](crate::IterResult::start), so an iteration which
+/// finishes after a rotation still lands in the bucket it started in.
+///
+/// The group is rotated on a ticker of the logical [`Clock`](crate::clock::Clock), which stands still while the
+/// benchmark is paused, so a pause neither rotates the buckets nor leaves a gap in them.
 pub struct RotateWindowGroup {
     pub counter: u64,
+    /// Elapsed time at which the current 1 second bucket started.
+    since: Duration,
     pub stats_by_sec: RotateWindow,
     pub stats_by_10sec: RotateWindow,
     pub stats_by_min: RotateWindow,
@@ -59,9 +74,11 @@ pub struct RotateWindowGroup {
 }
 
 impl RotateWindowGroup {
-    pub fn new(buckets: NonZeroUsize) -> Self {
+    /// Create the group with its current buckets starting at the given elapsed time.
+    pub fn new(buckets: NonZeroUsize, now: Duration) -> Self {
         Self {
             counter: 0,
+            since: now,
             stats_by_sec: RotateWindow::new(buckets),
             stats_by_10sec: RotateWindow::new(buckets),
             stats_by_min: RotateWindow::new(buckets),
@@ -69,28 +86,127 @@ impl RotateWindowGroup {
         }
     }
 
-    pub fn push(&mut self, stats: &IterReport) {
-        self.stats_by_sec.push(stats);
-        self.stats_by_10sec.push(stats);
-        self.stats_by_min.push(stats);
-        self.stats_by_10min.push(stats);
+    pub fn push(&mut self, res: &IterResult) {
+        let outcome = IterOutcome::from(res);
+        // number of 1 second buckets between the start of the iteration and the current bucket
+        let secs = match self.since.checked_sub(res.start) {
+            Some(d) => d.as_nanos().div_ceil(Duration::from_secs(1).as_nanos()) as u64,
+            None => 0,
+        };
+        // the coarser buckets rotated at the multiples of their length
+        let age = |len: u64| (self.counter / len - self.counter.saturating_sub(secs) / len) as usize;
+
+        let (ten_secs, mins, ten_mins) = (age(10), age(60), age(600));
+        self.stats_by_sec.push(outcome, secs as usize);
+        self.stats_by_10sec.push(outcome, ten_secs);
+        self.stats_by_min.push(outcome, mins);
+        self.stats_by_10min.push(outcome, ten_mins);
     }
 
-    pub fn rotate(&mut self) {
+    /// Called every second, at the given elapsed time.
+    pub fn rotate(&mut self, now: Duration) {
         self.counter += 1;
+        self.since = now;
         self.stats_by_sec.rotate(IterStats::new());
-        if self.counter % 10 == 0 {
+        if self.counter.is_multiple_of(10) {
             self.stats_by_10sec.rotate(IterStats::new());
         }
-        if self.counter % 60 == 0 {
+        if self.counter.is_multiple_of(60) {
             self.stats_by_min.rotate(IterStats::new());
         }
-        if self.counter % 600 == 0 {
+        if self.counter.is_multiple_of(600) {
             self.stats_by_10min.rotate(IterStats::new());
         }
     }
 }
 
+/// Significant digits of the per-bucket latency histograms, kept low to bound their memory.
+const LATENCY_SIGFIG: u8 = 2;
+
+/// Latency histograms of fixed-length time buckets, the current one first.
+struct LatencyWindow {
+    buckets: VecDeque<LatencyHistogram>,
+    size: NonZeroUsize,
+}
+
+impl LatencyWindow {
+    fn new(size: NonZeroUsize) -> Self {
+        let mut win = Self { buckets: VecDeque::with_capacity(size.get()), size };
+        win.rotate();
+        win
+    }
+
+    fn record(&mut self, latency: Duration) {
+        // SAFETY: `buckets` is never empty
+        self.buckets.front_mut().unwrap().record(latency);
+    }
+
+    fn rotate(&mut self) {
+        if self.buckets.len() == self.size.get() {
+            self.buckets.pop_back();
+        }
+        self.buckets.push_front(LatencyHistogram::with_sigfig(LATENCY_SIGFIG));
+    }
+
+    /// Merge the latest `n` buckets.
+    fn merged(&self, n: usize) -> LatencyHistogram {
+        let mut hist = LatencyHistogram::with_sigfig(LATENCY_SIGFIG);
+        for bucket in self.buckets.iter().take(n) {
+            let _ = hist.add(bucket);
+        }
+        hist
+    }
+}
+
+/// Latency of the recent past, bucketed by second and by 10 seconds.
+pub struct LatencyWindowGroup {
+    counter: u64,
+    latency_by_sec: LatencyWindow,
+    latency_by_10sec: LatencyWindow,
+}
+
+impl LatencyWindowGroup {
+    pub fn new() -> Self {
+        // the current bucket plus a full minute or ten minutes
+        let size = nonzero!(61usize);
+        Self {
+            counter: 0,
+            latency_by_sec: LatencyWindow::new(size),
+            latency_by_10sec: LatencyWindow::new(size),
+        }
+    }
+
+    pub fn record(&mut self, latency: Duration) {
+        self.latency_by_sec.record(latency);
+        self.latency_by_10sec.record(latency);
+    }
+
+    /// Called every second.
+    pub fn rotate(&mut self) {
+        self.counter += 1;
+        self.latency_by_sec.rotate();
+        if self.counter.is_multiple_of(10) {
+            self.latency_by_10sec.rotate();
+        }
+    }
+
+    /// Latency histogram of the last `secs` seconds, in addition to the current partial bucket.
+    ///
+    /// Windows up to a minute are accurate to one second, longer ones to ten seconds.
+    pub fn latency_last(&self, secs: u64) -> LatencyHistogram {
+        match secs {
+            0..=60 => self.latency_by_sec.merged(secs as usize + 1),
+            _ => self.latency_by_10sec.merged(secs.div_ceil(10) as usize + 1),
+        }
+    }
+}
+
+impl Default for LatencyWindowGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub struct RotateDiffWindowGroup {
     interval: Duration,
     stats_last_sec: RotateWindow,
@@ -144,7 +260,176 @@ impl RotateDiffWindowGroup {
     }
 
     fn diff(&self, win: &RotateWindow) -> (IterStats, Duration) {
-        let duration = (win.len() - 1) as u32 * self.interval;
+        let duration = win.len().saturating_sub(1) as u32 * self.interval;
         (win.front() - win.back(), duration)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{clock::Clock, report::IterReport, runner::IterInfo};
+
+    fn started_at(start: Duration) -> IterResult {
+        IterResult {
+            info: IterInfo::new(0, 1),
+            start,
+            result: Ok(IterReport::default()),
+        }
+    }
+
+    fn iters(win: &RotateWindow) -> Vec<u64> {
+        win.iter().map(|b| b.counter.iters).collect()
+    }
+
+    /// Push one iteration per second for the given seconds, rotating on a ticker of the clock like the TUI does.
+    async fn run_secs(clock: &Clock, ticker: &mut crate::clock::Ticker, group: &mut RotateWindowGroup, secs: u64) {
+        for _ in 0..secs {
+            group.push(&started_at(clock.elapsed()));
+            clock.advance(Duration::from_secs(1));
+            ticker.tick().await;
+            group.rotate(clock.elapsed());
+        }
+    }
+
+    #[tokio::test]
+    async fn rotates_each_scale_at_its_own_cadence() {
+        let clock = Clock::manual();
+        let mut ticker = clock.ticker(Duration::from_secs(1));
+        let mut group = RotateWindowGroup::new(nonzero!(61usize), clock.elapsed());
+        run_secs(&clock, &mut ticker, &mut group, 25).await;
+
+        assert_eq!(group.counter, 25);
+        assert_eq!(
+            iters(&group.stats_by_sec),
+            [0].into_iter().chain([1; 25]).collect::<Vec<_>>()
+        );
+        assert_eq!(iters(&group.stats_by_10sec), [5, 10, 10]);
+        assert_eq!(iters(&group.stats_by_min), [25]);
+        assert_eq!(iters(&group.stats_by_10min), [25]);
+
+        run_secs(&clock, &mut ticker, &mut group, 35).await;
+        assert_eq!(iters(&group.stats_by_10sec)[..2], [0, 10]);
+        assert_eq!(iters(&group.stats_by_min), [0, 60]);
+    }
+
+    #[tokio::test]
+    async fn counts_iterations_in_the_bucket_they_started_in() {
+        let clock = Clock::manual();
+        let mut ticker = clock.ticker(Duration::from_secs(1));
+        let mut group = RotateWindowGroup::new(nonzero!(4usize), clock.elapsed());
+        run_secs(&clock, &mut ticker, &mut group, 12).await;
+        assert_eq!(iters(&group.stats_by_sec), [0, 1, 1, 1]);
+
+        // started in the current second, the previous one, twice in the one before, then long before the window
+        for start in [12_000, 11_500, 10_000, 10_999, 1_000] {
+            group.push(&started_at(Duration::from_millis(start)));
+        }
+        assert_eq!(iters(&group.stats_by_sec), [1, 2, 3, 2]);
+        // the 10 second buckets rotated at 10s, so only the iteration from before then landed in the previous one
+        assert_eq!(iters(&group.stats_by_10sec), [6, 11]);
+    }
+
+    #[tokio::test]
+    async fn counts_errors_by_their_start_time() {
+        let clock = Clock::manual();
+        let mut group = RotateWindowGroup::new(nonzero!(4usize), clock.elapsed());
+        clock.advance(Duration::from_secs(1));
+        group.rotate(clock.elapsed());
+
+        let err = IterResult {
+            info: IterInfo::new(0, 1),
+            start: Duration::from_millis(500),
+            result: Err(anyhow::anyhow!("failed").into()),
+        };
+        group.push(&err);
+        let errors: Vec<_> = group.stats_by_sec.iter().map(|b| b.counter.errors).collect();
+        assert_eq!(errors, [0, 1]);
+    }
+
+    /// Whether the ticker ticks without the clock moving any further.
+    async fn ticks(ticker: &mut crate::clock::Ticker) -> bool {
+        tokio::time::timeout(Duration::ZERO, ticker.tick()).await.is_ok()
+    }
+
+    #[tokio::test]
+    async fn pause_mid_window_neither_rotates_nor_leaves_a_gap() {
+        let clock = Clock::manual();
+        let pause = crate::PauseControl::new(clock.clone());
+        let mut ticker = clock.ticker(Duration::from_secs(1));
+        let mut group = RotateWindowGroup::new(nonzero!(61usize), clock.elapsed());
+
+        clock.advance(Duration::from_millis(500));
+        group.push(&started_at(clock.elapsed()));
+        group.push(&started_at(clock.elapsed()));
+        pause.pause();
+        clock.advance(Duration::from_secs(30));
+        assert!(!ticks(&mut ticker).await);
+        assert_eq!(iters(&group.stats_by_sec), [2]);
+
+        // the rest of the first second passes after the resume
+        pause.resume();
+        clock.advance(Duration::from_millis(400));
+        assert!(!ticks(&mut ticker).await);
+        group.push(&started_at(clock.elapsed()));
+        clock.advance(Duration::from_millis(100));
+        assert!(ticks(&mut ticker).await);
+        group.rotate(clock.elapsed());
+        assert_eq!(clock.elapsed(), Duration::from_secs(1));
+
+        group.push(&started_at(clock.elapsed()));
+        assert_eq!(iters(&group.stats_by_sec), [1, 3]);
+        assert_eq!(iters(&group.stats_by_10sec), [4]);
+    }
+
+    #[tokio::test]
+    async fn latency_windows_stand_still_while_paused() {
+        let clock = Clock::manual();
+        let pause = crate::PauseControl::new(clock.clone());
+        let mut ticker = clock.ticker(Duration::from_secs(1));
+        let mut group = LatencyWindowGroup::new();
+        let ms = Duration::from_millis;
+
+        for latency in [ms(10), ms(20), ms(30)] {
+            group.record(latency);
+        }
+        clock.advance(ms(500));
+        pause.pause();
+        clock.advance(Duration::from_secs(30));
+        assert!(!ticks(&mut ticker).await);
+
+        pause.resume();
+        clock.advance(ms(500));
+        assert!(ticks(&mut ticker).await);
+        group.rotate();
+        group.record(ms(40));
+
+        // the bucket histograms keep 2 significant digits
+        let current = group.latency_last(0);
+        assert_eq!(current.count(), 1);
+        assert!((ms(39)..=ms(41)).contains(&current.max()));
+        let last = group.latency_last(1);
+        assert_eq!(last.count(), 4);
+        assert!((ms(9)..=ms(11)).contains(&last.min()));
+        assert_eq!(group.latency_last(60).count(), 4);
+    }
+
+    #[test]
+    fn latency_windows_drop_the_buckets_out_of_range() {
+        let mut group = LatencyWindowGroup::new();
+        group.record(Duration::from_millis(10));
+        for _ in 0..5 {
+            group.rotate();
+        }
+        group.record(Duration::from_millis(20));
+        assert_eq!(group.latency_last(4).count(), 1);
+        assert_eq!(group.latency_last(5).count(), 2);
+
+        // the 10 second buckets still hold the first latency after the minute of 1 second buckets is over
+        for _ in 0..60 {
+            group.rotate();
+        }
+        assert_eq!(group.latency_last(60).count(), 1);
+        assert_eq!(group.latency_last(70).count(), 2);
+    }
+}
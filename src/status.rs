@@ -1,11 +1,24 @@
 //! This module provides the iteration status for the benchmark.
-use std::fmt;
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+use parking_lot::Mutex;
 
 /// Represents the kind of status.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum StatusKind {
     /// Indicates success status.
     Success,
+    /// Indicates informational status, e.g. HTTP 1xx.
+    Informational,
+    /// Indicates redirect status, e.g. HTTP 3xx.
+    Redirect,
     /// Indicates uncategorized error.
     Error,
     /// Indicates client error.
@@ -14,16 +27,34 @@ pub enum StatusKind {
     ServerError,
 }
 
+/// Code of the statuses created by [`Status::other`].
+const OTHER_CODE: i64 = i64::MIN;
+
 /// The iteration status.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///
+/// Statuses are compared and hashed by their kind and code only, so labeled and unlabeled forms of the same
+/// status are aggregated together.
+#[derive(Clone, Copy, Debug)]
 pub struct Status {
     kind: StatusKind,
     code: i64,
+    label: Option<&'static str>,
 }
 
 impl Status {
     fn new(kind: StatusKind, code: i64) -> Self {
-        Self { kind, code }
+        Self { kind, code, label: None }
+    }
+
+    /// Creates the status which collects the statuses of the given kind beyond the cap of the status
+    /// distribution, shown as e.g. `Success(other)`.
+    pub fn other(kind: StatusKind) -> Self {
+        Self::new(kind, OTHER_CODE)
+    }
+
+    /// Attaches a human-readable label to the status, e.g. `DEADLINE_EXCEEDED`, shown next to the code.
+    pub fn with_label(self, label: &'static str) -> Self {
+        Self { label: Some(label), ..self }
     }
 
     /// Creates a new success status.
@@ -31,6 +62,16 @@ impl Status {
         Self::new(StatusKind::Success, code)
     }
 
+    /// Creates a new informational status.
+    pub fn informational(code: i64) -> Self {
+        Self::new(StatusKind::Informational, code)
+    }
+
+    /// Creates a new redirect status.
+    pub fn redirect(code: i64) -> Self {
+        Self::new(StatusKind::Redirect, code)
+    }
+
     /// Creates a new client error status.
     pub fn client_error(code: i64) -> Self {
         Self::new(StatusKind::ClientError, code)
@@ -55,12 +96,56 @@ impl Status {
     pub fn code(&self) -> i64 {
         self.code
     }
+
+    /// Returns the label of the status, if any.
+    pub fn label(&self) -> Option<&'static str> {
+        self.label
+    }
+}
+
+impl PartialEq for Status {
+    fn eq(&self, other: &Self) -> bool {
+        (self.kind, self.code) == (other.kind, other.code)
+    }
+}
+
+impl Eq for Status {}
+
+impl PartialOrd for Status {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Status {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.kind, self.code).cmp(&(other.kind, other.code))
+    }
+}
+
+impl Hash for Status {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.kind, self.code).hash(state);
+    }
+}
+
+impl StatusKind {
+    /// Whether the status counts as a success, with redirects only if `redirect_is_success` is set.
+    pub(crate) fn is_success(self, redirect_is_success: bool) -> bool {
+        match self {
+            Self::Success => true,
+            Self::Redirect => redirect_is_success,
+            Self::Informational | Self::Error | Self::ClientError | Self::ServerError => false,
+        }
+    }
 }
 
 impl fmt::Display for StatusKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Success => write!(f, "Success"),
+            Self::Informational => write!(f, "Informational"),
+            Self::Redirect => write!(f, "Redirect"),
             Self::Error => write!(f, "Error"),
             Self::ClientError => write!(f, "Client Error"),
             Self::ServerError => write!(f, "Server Error"),
@@ -70,7 +155,60 @@ impl fmt::Display for StatusKind {
 
 impl fmt::Display for Status {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}({})", self.kind, self.code)
+        match self.label {
+            _ if self.code == OTHER_CODE => write!(f, "{}(other)", self.kind),
+            Some(label) => write!(f, "{}({} {label})", self.kind, self.code),
+            None => write!(f, "{}({})", self.kind, self.code),
+        }
+    }
+}
+
+impl FromStr for StatusKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Success" => Ok(Self::Success),
+            "Informational" => Ok(Self::Informational),
+            "Redirect" => Ok(Self::Redirect),
+            "Error" => Ok(Self::Error),
+            "Client Error" => Ok(Self::ClientError),
+            "Server Error" => Ok(Self::ServerError),
+            _ => anyhow::bail!("unknown status kind: {s}"),
+        }
+    }
+}
+
+/// Parses a status in its display format, e.g. `Client Error(404)` or `Server Error(4 DEADLINE_EXCEEDED)`.
+impl FromStr for Status {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, code) = s
+            .strip_suffix(')')
+            .and_then(|s| s.split_once('('))
+            .ok_or_else(|| anyhow::anyhow!("invalid status: {s}"))?;
+        let status = match code.split_once(' ') {
+            _ if code == "other" => Self::other(kind.parse()?),
+            Some((code, label)) => Self::new(kind.parse()?, code.parse()?).with_label(intern(label)),
+            None => Self::new(kind.parse()?, code.parse()?),
+        };
+        Ok(status)
+    }
+}
+
+/// Intern a parsed label, so each distinct label is allocated only once.
+fn intern(label: &str) -> &'static str {
+    static LABELS: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+    let mut labels = LABELS.get_or_init(Mutex::default).lock();
+    match labels.get(label) {
+        Some(&label) => label,
+        None => {
+            let label: &'static str = Box::leak(label.into());
+            labels.insert(label);
+            label
+        }
     }
 }
 
@@ -79,6 +217,8 @@ impl From<http::StatusCode> for Status {
     fn from(status: http::StatusCode) -> Self {
         let kind = match status {
             s if s.is_success() => StatusKind::Success,
+            s if s.is_informational() => StatusKind::Informational,
+            s if s.is_redirection() => StatusKind::Redirect,
             s if s.is_client_error() => StatusKind::ClientError,
             s if s.is_server_error() => StatusKind::ServerError,
             _ => StatusKind::Error,
@@ -0,0 +1,253 @@
+//! A JSON file describing the state of a running benchmark, rewritten every second.
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::Result;
+use nonzero_ext::nonzero;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    clock::Clock,
+    collector::ReportSink,
+    pause::PauseControl,
+    report::IterResult,
+    runner::InFlight,
+    stats::{IterOutcome, IterStats, LatencyWindowGroup, RotateDiffWindowGroup},
+};
+
+/// Statistics of the benchmark, updated from the iteration report stream.
+struct Stats {
+    overall: IterStats,
+    recent: RotateDiffWindowGroup,
+    latency: LatencyWindowGroup,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self {
+            overall: IterStats::new(),
+            recent: RotateDiffWindowGroup::new(nonzero!(1usize)),
+            latency: LatencyWindowGroup::new(),
+        }
+    }
+}
+
+/// A report sink which feeds the statistics of the status file.
+pub(crate) struct StatusFileSink(Arc<Mutex<Stats>>);
+
+impl ReportSink for StatusFileSink {
+    fn on_report(&mut self, res: &IterResult) -> Result<()> {
+        let mut stats = self.0.lock();
+        stats.overall += IterOutcome::from(res);
+        if let Ok(report) = &res.result {
+            stats.latency.record(report.duration);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct Status {
+    state: &'static str,
+    elapsed: f64,
+    iters: u64,
+    errors: u64,
+    /// Iterations per second over the last 10 seconds.
+    iters_rate_10s: f64,
+    /// Errors per second over the last 10 seconds.
+    errors_rate_10s: f64,
+    success_ratio: f64,
+    /// The p99 latency of the last 10 seconds, in seconds.
+    p99_10s: f64,
+    /// The number of iterations inside the bench suite.
+    in_flight: u32,
+    paused: bool,
+}
+
+struct Writer {
+    path: PathBuf,
+    clock: Clock,
+    pause: Arc<PauseControl>,
+    cancel: CancellationToken,
+    redirect_is_success: bool,
+    in_flight: InFlight,
+    stats: Arc<Mutex<Stats>>,
+}
+
+impl Writer {
+    /// Close the current second of the recent statistics.
+    fn rotate(&self) {
+        let mut stats = self.stats.lock();
+        let overall = stats.overall.clone();
+        stats.recent.rotate(&overall);
+        stats.latency.rotate();
+    }
+
+    /// Rewrite the file with the current state of the run.
+    fn write(&self) -> Result<()> {
+        let state = if self.cancel.is_cancelled() {
+            "stopping"
+        } else if self.pause.is_paused() {
+            "paused"
+        } else {
+            "running"
+        };
+        let status = self.status(&self.stats.lock(), state);
+        write_atomic(&self.path, &status)
+    }
+
+    fn status(&self, stats: &Stats, state: &'static str) -> Status {
+        let (recent, duration) = stats.recent.stats_last_10sec();
+        let total = stats.overall.total();
+        let rate = |n: u64| match duration.is_zero() {
+            true => 0.0,
+            false => n as f64 / duration.as_secs_f64(),
+        };
+        Status {
+            state,
+            elapsed: self.clock.elapsed().as_secs_f64(),
+            iters: stats.overall.counter.iters,
+            errors: stats.overall.counter.errors,
+            iters_rate_10s: rate(recent.counter.iters),
+            errors_rate_10s: rate(recent.counter.errors),
+            success_ratio: match total {
+                0 => 0.0,
+                _ => 1.0 - stats.overall.error_ratio(self.redirect_is_success),
+            },
+            p99_10s: stats.latency.latency_last(10).percentile(99.0).as_secs_f64(),
+            in_flight: self.in_flight.get(),
+            paused: self.pause.is_paused(),
+        }
+    }
+}
+
+/// Write the status to a temporary file next to the path and rename it over the path, so readers never see a
+/// partially written file.
+fn write_atomic(path: &Path, status: &Status) -> Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    std::fs::write(&tmp, serde_json::to_vec(status)?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// A running writer of the status file.
+pub(crate) struct StatusFile {
+    cancel: CancellationToken,
+    handle: JoinHandle<Result<()>>,
+    writer: Arc<Writer>,
+}
+
+impl StatusFile {
+    /// Start rewriting the status file at the given path every second.
+    ///
+    /// Returns the writer and a sink which feeds the statistics from the iteration reports.
+    pub(crate) fn start(
+        path: impl Into<PathBuf>,
+        clock: Clock,
+        pause: Arc<PauseControl>,
+        bench_cancel: CancellationToken,
+        redirect_is_success: bool,
+        in_flight: InFlight,
+    ) -> Result<(Self, StatusFileSink)> {
+        let stats = Arc::new(Mutex::new(Stats::default()));
+        let sink = StatusFileSink(stats.clone());
+        let writer = Arc::new(Writer {
+            path: path.into(),
+            clock,
+            pause,
+            cancel: bench_cancel,
+            redirect_is_success,
+            in_flight,
+            stats,
+        });
+        writer.write()?;
+
+        let cancel = CancellationToken::new();
+        let handle = tokio::spawn({
+            let (writer, cancel) = (writer.clone(), cancel.clone());
+            async move {
+                // the statistics rotate on the logical clock, which stands still while the benchmark is paused,
+                // while the file keeps being rewritten to report the pause
+                let mut rotate_ticker = writer.clock.ticker(Duration::from_secs(1));
+                let mut write_ticker = tokio::time::interval(Duration::from_secs(1));
+                write_ticker.tick().await;
+                loop {
+                    tokio::select! {
+                        _ = cancel.cancelled() => return Ok(()),
+                        _ = rotate_ticker.tick() => writer.rotate(),
+                        _ = write_ticker.tick() => writer.write()?,
+                    }
+                }
+            }
+        });
+
+        Ok((Self { cancel, handle, writer }, sink))
+    }
+
+    /// Stop the periodic updates and write the file one final time with the `finished` state.
+    pub(crate) async fn shutdown(self) -> Result<()> {
+        self.cancel.cancel();
+        self.handle.await??;
+        let status = self.writer.status(&self.writer.stats.lock(), "finished");
+        write_atomic(&self.writer.path, &status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{report::IterReport, runner::IterInfo, status::Status as IterStatus};
+
+    fn read(path: &Path) -> serde_json::Value {
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn writes_the_state_of_the_run() {
+        let path = std::env::temp_dir().join(format!("rlt-status-file-{}.json", std::process::id()));
+        let clock = Clock::manual();
+        let pause = Arc::new(PauseControl::new(clock.clone()));
+        let cancel = CancellationToken::new();
+        let (status_file, mut sink) =
+            StatusFile::start(&path, clock.clone(), pause, cancel, false, InFlight::default()).unwrap();
+
+        let status = read(&path);
+        assert_eq!(status["state"], "running");
+        assert_eq!(
+            (status["iters"].as_u64(), status["success_ratio"].as_f64()),
+            (Some(0), Some(0.0))
+        );
+
+        for i in 0..10 {
+            let result = match i {
+                0 | 1 => Err(anyhow::anyhow!("connection reset").into()),
+                _ => Ok(IterReport::new(Duration::from_millis(5), IterStatus::success(200))),
+            };
+            let start = Duration::from_millis(i * 100);
+            sink.on_report(&IterResult { info: IterInfo::new(0, 1), start, result })
+                .unwrap();
+        }
+        clock.advance(Duration::from_secs(2));
+        status_file.shutdown().await.unwrap();
+
+        let status = read(&path);
+        assert_eq!(status["state"], "finished");
+        assert_eq!(status["elapsed"], 2.0);
+        assert_eq!(status["iters"], 8);
+        assert_eq!(status["errors"], 2);
+        assert_eq!(status["success_ratio"], 0.8);
+        assert_eq!(
+            (status["in_flight"].as_u64(), status["paused"].as_bool()),
+            (Some(0), Some(false))
+        );
+        assert!(!path.with_extension("json.tmp").exists());
+        std::fs::remove_file(path).unwrap();
+    }
+}
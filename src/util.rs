@@ -1,24 +1,90 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
 use anyhow::anyhow;
 use byte_unit::{Byte, UnitType};
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// The unit convention used to display byte sizes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ByteUnit {
+    /// Powers of 1024, e.g. KiB, MiB, GiB.
+    #[default]
+    Binary,
+
+    /// Powers of 1000, e.g. KB, MB, GB.
+    Decimal,
+}
+
+impl From<ByteUnit> for UnitType {
+    fn from(unit: ByteUnit) -> Self {
+        match unit {
+            ByteUnit::Binary => UnitType::Binary,
+            ByteUnit::Decimal => UnitType::Decimal,
+        }
+    }
+}
 
 pub trait TryIntoAdjustedByte {
-    fn adjusted(self) -> anyhow::Result<byte_unit::AdjustedByte>;
+    fn adjusted(self, unit: ByteUnit) -> anyhow::Result<byte_unit::AdjustedByte>;
 }
 
 pub trait IntoAdjustedByte {
-    fn adjusted(self) -> byte_unit::AdjustedByte;
+    fn adjusted(self, unit: ByteUnit) -> byte_unit::AdjustedByte;
 }
 
 impl TryIntoAdjustedByte for f64 {
-    fn adjusted(self) -> anyhow::Result<byte_unit::AdjustedByte> {
+    fn adjusted(self, unit: ByteUnit) -> anyhow::Result<byte_unit::AdjustedByte> {
         Byte::from_f64(self)
             .ok_or(anyhow!("size too large"))
-            .map(|b| b.get_appropriate_unit(UnitType::Binary))
+            .map(|b| b.get_appropriate_unit(unit.into()))
     }
 }
 
 impl IntoAdjustedByte for u64 {
-    fn adjusted(self) -> byte_unit::AdjustedByte {
-        Byte::from_u64(self).get_appropriate_unit(UnitType::Binary)
+    fn adjusted(self, unit: ByteUnit) -> byte_unit::AdjustedByte {
+        Byte::from_u64(self).get_appropriate_unit(unit.into())
+    }
+}
+
+/// Write a file through a temporary file next to the path, which is renamed over the path once it is complete,
+/// so readers never see a partially written file.
+pub(crate) fn write_atomic(
+    path: &Path,
+    write: impl FnOnce(&mut dyn Write) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let mut w = BufWriter::new(File::create(&tmp)?);
+    write(&mut w)?;
+    w.flush()?;
+    drop(w);
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Removes the ANSI escape sequences, e.g. colors and text attributes, from the text.
+pub fn strip_ansi(text: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len());
+    let mut bytes = text.iter().copied().peekable();
+    while let Some(b) = bytes.next() {
+        if b != 0x1b {
+            out.push(b);
+            continue;
+        }
+        // CSI sequences end with a byte in the range 0x40..=0x7e
+        if bytes.next_if_eq(&b'[').is_some() {
+            for b in bytes.by_ref() {
+                if (0x40..=0x7e).contains(&b) {
+                    break;
+                }
+            }
+        }
     }
+    out
 }
@@ -0,0 +1,74 @@
+use std::net::SocketAddr;
+
+use bytes::Bytes;
+use http::{Method, Response, StatusCode};
+use http_body_util::{BodyExt, Full};
+use hyper::{body::Incoming, server::conn::http1, service::service_fn, Request};
+use hyper_util::rt::TokioIo;
+use rlt::{BenchSuite, HttpBenchSuite, HttpRequest, IterInfo, Status};
+use tokio::net::TcpListener;
+
+const ITEMS: &str = r#"[{"name":"rlt"},{"name":"hyper"}]"#;
+
+/// Serve `GET /items` with a fixed body, `POST /items` by echoing the body, and everything else with a 404.
+async fn serve() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            tokio::spawn(http1::Builder::new().serve_connection(TokioIo::new(stream), service_fn(handle)));
+        }
+    });
+    addr
+}
+
+async fn handle(req: Request<Incoming>) -> hyper::Result<Response<Full<Bytes>>> {
+    let (status, body) = match (req.method(), req.uri().path()) {
+        (&Method::GET, "/items") => (StatusCode::OK, Bytes::from(ITEMS)),
+        (&Method::POST, "/items") => (StatusCode::CREATED, req.into_body().collect().await?.to_bytes()),
+        _ => (StatusCode::NOT_FOUND, Bytes::new()),
+    };
+    let mut resp = Response::new(Full::new(body));
+    *resp.status_mut() = status;
+    Ok(resp)
+}
+
+/// Run one iteration of a suite which sends only `req`.
+async fn bench(req: HttpRequest) -> rlt::IterReport {
+    let mut suite = HttpBenchSuite::new([req]).unwrap();
+    let mut worker = suite.state(0).await.unwrap();
+    suite.bench(&mut worker, &IterInfo::new(0, 1)).await.unwrap()
+}
+
+#[tokio::test]
+async fn reports_the_status_and_bytes() {
+    let addr = serve().await;
+    let uri = |path: &str| format!("http://{addr}{path}").parse().unwrap();
+
+    let get = bench(HttpRequest::get(uri("/items"))).await;
+    assert_eq!(get.status, Status::success(200));
+    assert_eq!((get.bytes_in, get.bytes_out), (ITEMS.len() as u64, 0));
+    assert_eq!(
+        get.context.as_deref(),
+        Some(format!("GET http://{addr}/items").as_str())
+    );
+
+    let post = bench(HttpRequest::new(Method::POST, uri("/items")).body(r#"{"name":"tokio"}"#)).await;
+    assert_eq!(post.status, Status::success(201));
+    assert_eq!((post.bytes_in, post.bytes_out), (16, 16));
+
+    let missing = bench(HttpRequest::get(uri("/missing"))).await;
+    assert_eq!(missing.status, Status::client_error(404));
+    assert_eq!((missing.bytes_in, missing.bytes_out), (0, 0));
+}
+
+#[tokio::test]
+async fn preflight_fails_on_a_client_error() {
+    let addr = serve().await;
+    let suite = |path: &str| HttpBenchSuite::new([HttpRequest::get(format!("http://{addr}{path}").parse().unwrap())]);
+
+    suite("/items").unwrap().preflight().await.unwrap();
+    let err = suite("/missing").unwrap().preflight().await.unwrap_err();
+    assert!(err.to_string().contains("404"), "{err}");
+}
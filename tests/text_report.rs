@@ -0,0 +1,61 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use rlt::{
+    collector::ReportAggregator,
+    reporter::{BenchReporter, TextReporter},
+    BenchOpts, BenchReport, Bound, ByteUnit, IterInfo, IterReport, IterResult, RunMetadata, Status,
+};
+
+const FIXTURE: &str = "tests/fixtures/text_report.txt";
+
+/// A deterministic report of 200 iterations of two workers, with a mix of statuses, errors and bytes.
+fn report() -> BenchReport {
+    let mut opts = BenchOpts::default();
+    (opts.concurrency, opts.slowest) = (2, 3);
+    let mut agg = ReportAggregator::new(&opts);
+    for i in 0..200u64 {
+        let mut info = IterInfo::new(i as u32 % 2, 2);
+        info.worker_seq = i / 2;
+        let start = Duration::from_millis(i * 50);
+        let duration = Duration::from_micros(500 + i * 37);
+        let result = match i % 20 {
+            0 => Err(anyhow::anyhow!("connection reset").into()),
+            1 | 2 => Ok(IterReport::new(duration, Status::server_error(503))),
+            _ => Ok(IterReport {
+                bytes_in: 1000 + i,
+                bytes_out: 100,
+                items: 2,
+                context: Some(format!("GET /items/{i}")),
+                ..IterReport::new(duration, Status::success(200))
+            }),
+        };
+        agg.ingest(IterResult { info, start, result }, start + duration)
+            .unwrap();
+    }
+
+    let mut report = agg.into_report(Duration::from_secs(10), 2);
+    report.metadata = RunMetadata {
+        started_at: Some(UNIX_EPOCH + Duration::from_secs(1_717_243_203)),
+        finished_at: Some(UNIX_EPOCH + Duration::from_secs(1_717_243_213)),
+        command: vec!["bench".into(), "-c".into(), "2".into()],
+        duration: Some(Duration::from_secs(10)),
+        finished_by: Some(Bound::Duration),
+        ..Default::default()
+    };
+    report
+}
+
+/// Set `RLT_BLESS=1` to regenerate the fixture after an intended change of the output.
+#[test]
+fn matches_fixture() {
+    let reporter = TextReporter { byte_unit: ByteUnit::Binary, ascii: true, color: false };
+    let mut out = Vec::new();
+    reporter.print(&mut out, &report()).unwrap();
+    let out = String::from_utf8(out).unwrap();
+
+    if std::env::var_os("RLT_BLESS").is_some() {
+        std::fs::write(FIXTURE, &out).unwrap();
+    }
+    let expected = std::fs::read_to_string(FIXTURE).unwrap();
+    assert_eq!(out, expected, "the text report differs from {FIXTURE}");
+}